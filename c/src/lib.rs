@@ -185,6 +185,7 @@ impl Into<MinifyOptions> for TransformOptions {
         None
       },
       unused_symbols,
+      ..MinifyOptions::default()
     }
   }
 }
@@ -640,6 +640,7 @@ fn compile<'i>(
     stylesheet.minify(MinifyOptions {
       targets: config.targets,
       unused_symbols: config.unused_symbols.clone().unwrap_or_default(),
+      ..MinifyOptions::default()
     })?;
 
     stylesheet.to_css(PrinterOptions {
@@ -754,6 +755,7 @@ fn compile_bundle<
     stylesheet.minify(MinifyOptions {
       targets: config.targets,
       unused_symbols: config.unused_symbols.clone().unwrap_or_default(),
+      ..MinifyOptions::default()
     })?;
 
     stylesheet.to_css(PrinterOptions {
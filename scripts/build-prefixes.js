@@ -252,6 +252,7 @@ let mdnFeatures = {
   logicalPadding: mdn.css.properties['padding-inline-start'].__compat.support,
   logicalPaddingShorthand: mdn.css.properties['padding-inline'].__compat.support,
   logicalInset: mdn.css.properties['inset-inline-start'].__compat.support,
+  logicalOverscrollBehavior: mdn.css.properties['overscroll-behavior-inline'].__compat.support,
   logicalSize: mdn.css.properties['inline-size'].__compat.support,
   logicalTextAlign: mdn.css.properties['text-align']['flow_relative_values_start_and_end'].__compat.support,
   labColors: mdn.css.types.color.lab.__compat.support,
@@ -289,6 +290,25 @@ let mdnFeatures = {
   absFunction: mdn.css.types.abs.__compat.support,
   signFunction: mdn.css.types.sign.__compat.support,
   hypotFunction: mdn.css.types.hypot.__compat.support,
+  sinFunction: mdn.css.types.sin.__compat.support,
+  cosFunction: mdn.css.types.cos.__compat.support,
+  tanFunction: mdn.css.types.tan.__compat.support,
+  asinFunction: mdn.css.types.asin.__compat.support,
+  acosFunction: mdn.css.types.acos.__compat.support,
+  atanFunction: mdn.css.types.atan.__compat.support,
+  atan2Function: mdn.css.types.atan2.__compat.support,
+  powFunction: mdn.css.types.pow.__compat.support,
+  sqrtFunction: mdn.css.types.sqrt.__compat.support,
+  logFunction: mdn.css.types.log.__compat.support,
+  expFunction: mdn.css.types.exp.__compat.support,
+  anchorFunction: mdn.css.types.anchor.__compat.support,
+  anchorSizeFunction: mdn.css.types['anchor-size'].__compat.support,
+  gradientInterpolation: mdn.css.types.gradient['linear-gradient'].interpolation_color_space.__compat.support,
+  subgrid: mdn.css.properties['grid-template-columns'].subgrid.__compat.support,
+  masonry: mdn.css.properties['grid-template-columns'].masonry.__compat.support,
+  cascadeLayers: mdn.css['at-rules'].layer.__compat.support,
+  lightDark: mdn.css.types.color['light-dark'].__compat.support,
+  atScope: mdn.css['at-rules'].scope.__compat.support,
 };
 
 for (let key in mdn.css.types.length) {
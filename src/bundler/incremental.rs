@@ -0,0 +1,426 @@
+//! Incremental bundling.
+//!
+//! An [IncrementalBundler](IncrementalBundler) keeps the files it has parsed between builds, so
+//! that only the files that changed need to be read and parsed again, e.g. in a development server.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use lightningcss::{
+//!   bundler::{incremental::IncrementalBundler, Bundler, FileProvider},
+//!   stylesheet::ParserOptions,
+//! };
+//!
+//! let fs = FileProvider::new();
+//! let bundler = Bundler::new(&fs, None, ParserOptions::default());
+//! let mut bundler = IncrementalBundler::new(bundler, &[Path::new("style.css")]);
+//! let build = bundler.build(&[]).unwrap();
+//!
+//! // Later, after a file was saved.
+//! let build = bundler.build(&[Path::new("colors.css")]).unwrap();
+//! for entry in &build.changed_entries {
+//!   println!("{} changed", entry.display());
+//! }
+//! ```
+
+use super::{
+  reachable, BundleCache, BundleErrorKind, BundleManifest, BundleStyleSheet, Bundler, Chunk, MultiBundle,
+  SourceProvider,
+};
+use crate::error::Error;
+use crate::media_query::MediaList;
+use crate::rules::layer::LayerName;
+use crate::rules::supports::SupportsCondition;
+use crate::stylesheet::StyleSheet;
+use crate::traits::{AtRuleParser, ToCss};
+use dashmap::DashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// A bundler that keeps the graph of parsed files between builds.
+///
+/// Each build resolves the imports of all entries again, so that the `@layer`, `@media`, and
+/// `@supports` conditions that files are imported with are re-evaluated. However, only files that
+/// were passed as changed (or that were not loaded before) are read and parsed again. Source
+/// indexes stay the same between builds, so CSS modules hashes are stable.
+pub struct IncrementalBundler<'a, 'o, 's, P, T: AtRuleParser<'a>> {
+  bundler: Bundler<'a, 'o, 's, P, T>,
+  entries: Vec<PathBuf>,
+  shared_threshold: Option<usize>,
+  /// The files reachable from each entry, as of the last successful build.
+  previous: Option<Vec<HashMap<u32, ImportConditions<'a>>>>,
+  /// The manifest of the last successful build.
+  previous_manifest: Option<BundleManifest>,
+  /// Files that changed since the last successful build.
+  modified: HashSet<u32>,
+}
+
+/// The result of an incremental build.
+#[derive(Debug)]
+pub struct IncrementalBuild<'i, 'o, T> {
+  /// The bundled style sheets of all entries.
+  pub bundle: MultiBundle<'i, 'o, T>,
+  /// The entries whose bundles changed since the previous build, in order. All entries are
+  /// included after the first build, or after a build that failed.
+  pub changed_entries: Vec<PathBuf>,
+}
+
+/// The conditions that a file was imported with.
+#[derive(PartialEq)]
+struct ImportConditions<'i> {
+  layer: Option<Option<LayerName<'i>>>,
+  media: MediaList<'i>,
+  supports: Option<SupportsCondition<'i>>,
+}
+
+impl<'i> ImportConditions<'i> {
+  fn new<T>(stylesheet: &BundleStyleSheet<'i, '_, T>) -> Self {
+    ImportConditions {
+      layer: stylesheet.layer.clone(),
+      media: stylesheet.media.clone(),
+      supports: stylesheet.supports.clone(),
+    }
+  }
+}
+
+impl<'a, 'o, 's, P: SourceProvider, T: AtRuleParser<'a> + Clone + Sync + Send> IncrementalBundler<'a, 'o, 's, P, T>
+where
+  T::AtRule: Sync + Send + ToCss + Clone,
+{
+  /// Creates an incremental bundler for the given entries. Options such as the output directory
+  /// should be set on the given bundler beforehand.
+  pub fn new(mut bundler: Bundler<'a, 'o, 's, P, T>, entries: &[&Path]) -> Self {
+    bundler.cache = Some(BundleCache {
+      source_indexes: DashMap::new(),
+      code: DashMap::new(),
+      stylesheets: DashMap::new(),
+      assets: DashMap::new(),
+      clone_stylesheet: StyleSheet::clone,
+    });
+
+    IncrementalBundler {
+      bundler,
+      entries: entries.iter().map(|entry| entry.to_path_buf()).collect(),
+      shared_threshold: None,
+      previous: None,
+      previous_manifest: None,
+      modified: HashSet::new(),
+    }
+  }
+
+  /// Sets the minimum number of entries that must import a file for it to be extracted into a
+  /// shared chunk. See [Bundler::bundle_entries](Bundler::bundle_entries).
+  pub fn set_shared_threshold(&mut self, shared_threshold: Option<usize>) {
+    self.shared_threshold = shared_threshold;
+  }
+
+  /// Bundles all entries, re-reading and re-parsing only the given changed files. Paths must
+  /// match those returned by the [SourceProvider](SourceProvider) when resolving imports.
+  /// Changed assets mark the files that reference them as changed when asset inlining is enabled.
+  /// Other changed files that are not part of the bundle are ignored.
+  ///
+  /// The first build reads and parses all files.
+  pub fn build(
+    &mut self,
+    changed: &[&Path],
+  ) -> Result<IncrementalBuild<'a, 'o, T::AtRule>, Error<BundleErrorKind<'a, P::Error>>> {
+    let cache = self.bundler.cache.as_ref().unwrap();
+    for file in changed {
+      let source_index = match cache.source_indexes.get(*file) {
+        Some(source_index) => *source_index,
+        None => {
+          // Inlined assets are read again on every build, but the files that reference them changed.
+          if let Some(importers) = cache.assets.get(*file) {
+            self.modified.extend(importers.iter().copied());
+          }
+          continue;
+        }
+      };
+
+      // Only invalidate the parsed style sheet if the contents actually changed. If the file cannot
+      // be read (e.g. because it was deleted), the error is reported if it is still imported.
+      match self.bundler.fs.read(file) {
+        Ok(code) if matches!(cache.code.get(&source_index), Some(existing) if *existing == code) => continue,
+        Ok(code) => {
+          cache.code.insert(source_index, code);
+        }
+        Err(_) => {
+          cache.code.remove(&source_index);
+        }
+      }
+
+      cache.stylesheets.remove(&source_index);
+      self.modified.insert(source_index);
+    }
+
+    // Resolve the imports of all entries again. Files that are already cached are not re-parsed.
+    self.bundler.source_indexes.clear();
+    let entries: Vec<&Path> = self.entries.iter().map(PathBuf::as_path).collect();
+    let bundle = match self.bundler.bundle_entries(&entries, self.shared_threshold) {
+      Ok(bundle) => bundle,
+      Err(err) => {
+        self.previous = None;
+        self.previous_manifest = None;
+        return Err(err);
+      }
+    };
+
+    let stylesheets = self.bundler.stylesheets.get_mut().unwrap();
+    let current: Vec<HashMap<u32, ImportConditions<'a>>> = self
+      .entries
+      .iter()
+      .map(|entry| {
+        let root = *self.bundler.source_indexes.get(entry).unwrap();
+        reachable(stylesheets, root)
+          .into_iter()
+          .map(|source_index| {
+            let conditions = ImportConditions::new(&stylesheets[source_index as usize]);
+            (source_index, conditions)
+          })
+          .collect()
+      })
+      .collect();
+
+    // An entry changed if it includes a modified file, or if the set of files it includes
+    // or the conditions they are imported with changed. Files can also move between chunks
+    // without any of this changing, e.g. into the shared chunk when another entry imports them.
+    let manifest = &bundle.manifest;
+    let changed_entries = self
+      .entries
+      .iter()
+      .enumerate()
+      .filter(|(index, _)| match (&self.previous, &self.previous_manifest) {
+        (Some(previous), Some(previous_manifest)) => {
+          previous[*index] != current[*index]
+            || current[*index].keys().any(|source_index| self.modified.contains(source_index))
+            || previous_manifest.entries[*index] != manifest.entries[*index]
+            || (manifest.entries[*index].chunks.contains(&Chunk::Shared)
+              && previous_manifest.shared != manifest.shared)
+        }
+        _ => true,
+      })
+      .map(|(_, entry)| entry.clone())
+      .collect();
+
+    self.previous = Some(current);
+    self.previous_manifest = Some(manifest.clone());
+    self.modified.clear();
+
+    Ok(IncrementalBuild {
+      bundle,
+      changed_entries,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::bundler::AssetInliningOptions;
+  use crate::stylesheet::{ParserOptions, PrinterOptions};
+  use std::sync::Mutex;
+
+  /// An in-memory file system that can be modified between builds, and records which files were read.
+  struct MemoryProvider {
+    files: Mutex<HashMap<PathBuf, &'static str>>,
+    reads: Mutex<Vec<PathBuf>>,
+  }
+
+  impl MemoryProvider {
+    fn write(&self, file: &str, code: &str) {
+      let code: &'static str = Box::leak(code.to_owned().into_boxed_str());
+      self.files.lock().unwrap().insert(PathBuf::from(file), code);
+    }
+
+    fn take_reads(&self) -> Vec<PathBuf> {
+      let mut reads = std::mem::take(&mut *self.reads.lock().unwrap());
+      reads.sort();
+      reads
+    }
+  }
+
+  impl SourceProvider for MemoryProvider {
+    type Error = std::io::Error;
+
+    fn read<'a>(&'a self, file: &Path) -> Result<&'a str, Self::Error> {
+      self.reads.lock().unwrap().push(file.to_owned());
+      self
+        .files
+        .lock()
+        .unwrap()
+        .get(file)
+        .copied()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "not found"))
+    }
+
+    fn resolve(&self, specifier: &str, originating_file: &Path) -> Result<PathBuf, Self::Error> {
+      Ok(originating_file.with_file_name(specifier))
+    }
+  }
+
+  fn print(build: &IncrementalBuild<crate::parser::DefaultAtRule>) -> Vec<String> {
+    build
+      .bundle
+      .entries
+      .iter()
+      .map(|stylesheet| {
+        stylesheet
+          .to_css(PrinterOptions {
+            minify: true,
+            ..PrinterOptions::default()
+          })
+          .unwrap()
+          .code
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_incremental_build() {
+    let fs = MemoryProvider {
+      files: Mutex::new(HashMap::new()),
+      reads: Mutex::new(Vec::new()),
+    };
+    fs.write(
+      "/a.css",
+      r#"@import "base.css"; @import "theme.css"; .a { color: red }"#,
+    );
+    fs.write("/b.css", r#"@import "base.css"; .b { color: green }"#);
+    fs.write("/base.css", ".base { color: black }");
+    fs.write("/theme.css", ".theme { color: blue }");
+
+    let bundler = Bundler::new(&fs, None, ParserOptions::default());
+    let mut bundler = IncrementalBundler::new(bundler, &[Path::new("/a.css"), Path::new("/b.css")]);
+
+    // The first build reads all files.
+    let build = bundler.build(&[]).unwrap();
+    assert_eq!(
+      print(&build),
+      vec![
+        ".base{color:#000}.theme{color:#00f}.a{color:red}",
+        ".base{color:#000}.b{color:green}"
+      ]
+    );
+    assert_eq!(
+      build.changed_entries,
+      vec![PathBuf::from("/a.css"), PathBuf::from("/b.css")]
+    );
+    assert_eq!(fs.take_reads().len(), 4);
+
+    // Only the changed file is read again, and only entries that include it changed.
+    fs.write("/theme.css", ".theme { color: purple }");
+    let build = bundler.build(&[Path::new("/theme.css")]).unwrap();
+    assert_eq!(
+      print(&build),
+      vec![
+        ".base{color:#000}.theme{color:purple}.a{color:red}",
+        ".base{color:#000}.b{color:green}"
+      ]
+    );
+    assert_eq!(build.changed_entries, vec![PathBuf::from("/a.css")]);
+    assert_eq!(fs.take_reads(), vec![PathBuf::from("/theme.css")]);
+
+    // Files with the same contents don't cause any changes.
+    let build = bundler.build(&[Path::new("/base.css")]).unwrap();
+    assert_eq!(build.changed_entries, Vec::<PathBuf>::new());
+    assert_eq!(fs.take_reads(), vec![PathBuf::from("/base.css")]);
+
+    // New imports of cached files are resolved without reading them again.
+    fs.write(
+      "/b.css",
+      r#"@import "base.css"; @import "theme.css"; .b { color: green }"#,
+    );
+    let build = bundler.build(&[Path::new("/b.css")]).unwrap();
+    assert_eq!(
+      print(&build),
+      vec![
+        ".base{color:#000}.theme{color:purple}.a{color:red}",
+        ".base{color:#000}.theme{color:purple}.b{color:green}"
+      ]
+    );
+    assert_eq!(build.changed_entries, vec![PathBuf::from("/b.css")]);
+    assert_eq!(fs.take_reads(), vec![PathBuf::from("/b.css")]);
+
+    // Files that are not part of the bundle are ignored.
+    let build = bundler.build(&[Path::new("/unknown.css")]).unwrap();
+    assert_eq!(build.changed_entries, Vec::<PathBuf>::new());
+    assert_eq!(fs.take_reads(), Vec::<PathBuf>::new());
+
+    // Errors are reported, and all entries are considered changed after a failed build.
+    fs.write("/theme.css", "@import \"missing.css\";");
+    assert!(bundler.build(&[Path::new("/theme.css")]).is_err());
+    fs.write("/theme.css", ".theme { color: purple }");
+    let build = bundler.build(&[Path::new("/theme.css")]).unwrap();
+    assert_eq!(
+      build.changed_entries,
+      vec![PathBuf::from("/a.css"), PathBuf::from("/b.css")]
+    );
+  }
+
+  #[test]
+  fn test_incremental_asset_inlining() {
+    let fs = MemoryProvider {
+      files: Mutex::new(HashMap::new()),
+      reads: Mutex::new(Vec::new()),
+    };
+    fs.write("/a.css", ".a { background: url(icon.svg) }");
+    fs.write("/b.css", ".b { color: green }");
+    fs.write("/icon.svg", "<svg/>");
+
+    let mut bundler = Bundler::new(&fs, None, ParserOptions::default());
+    bundler.set_asset_inlining(AssetInliningOptions {
+      threshold: 16,
+      exclude: Vec::new(),
+    });
+    let mut bundler = IncrementalBundler::new(bundler, &[Path::new("/a.css"), Path::new("/b.css")]);
+    let build = bundler.build(&[]).unwrap();
+    assert_eq!(print(&build)[0], ".a{background:url(data:image/svg+xml,<svg/>)}");
+
+    // Changed assets are inlined again, and the entries that reference them changed.
+    fs.write("/icon.svg", "<svg></svg>");
+    let build = bundler.build(&[Path::new("/icon.svg")]).unwrap();
+    assert_eq!(print(&build)[0], ".a{background:url(data:image/svg+xml,<svg></svg>)}");
+    assert_eq!(build.changed_entries, vec![PathBuf::from("/a.css")]);
+
+    // Assets that grow past the threshold are no longer inlined.
+    fs.write("/icon.svg", "<svg><g></g></svg>");
+    let build = bundler.build(&[Path::new("/icon.svg")]).unwrap();
+    assert_eq!(print(&build)[0], ".a{background:url(icon.svg)}");
+    assert_eq!(build.changed_entries, vec![PathBuf::from("/a.css")]);
+  }
+
+  #[test]
+  fn test_incremental_shared_chunk() {
+    let fs = MemoryProvider {
+      files: Mutex::new(HashMap::new()),
+      reads: Mutex::new(Vec::new()),
+    };
+    fs.write("/a.css", r#"@import "theme.css"; .a { color: red }"#);
+    fs.write("/b.css", ".b { color: green }");
+    fs.write("/c.css", ".c { color: yellow }");
+    fs.write("/theme.css", ".theme { color: blue }");
+
+    let bundler = Bundler::new(&fs, None, ParserOptions::default());
+    let mut bundler = IncrementalBundler::new(
+      bundler,
+      &[Path::new("/a.css"), Path::new("/b.css"), Path::new("/c.css")],
+    );
+    bundler.set_shared_threshold(Some(2));
+    bundler.build(&[]).unwrap();
+
+    // A new import in one entry moves the file into the shared chunk, which changes the
+    // bundle of the other entry that imports it even though it did not change itself.
+    fs.write("/b.css", r#"@import "theme.css"; .b { color: green }"#);
+    let build = bundler.build(&[Path::new("/b.css")]).unwrap();
+    assert_eq!(
+      print(&build),
+      vec![".a{color:red}", ".b{color:green}", ".c{color:#ff0}"]
+    );
+    assert_eq!(build.bundle.manifest.shared, vec![PathBuf::from("/theme.css")]);
+    assert_eq!(
+      build.changed_entries,
+      vec![PathBuf::from("/a.css"), PathBuf::from("/b.css")]
+    );
+  }
+}
@@ -22,8 +22,17 @@
 //! let mut bundler = Bundler::new(&fs, None, ParserOptions::default());
 //! let stylesheet = bundler.bundle(Path::new("style.css")).unwrap();
 //! ```
+//!
+//! To resolve bare specifiers such as `@import "normalize.css"` from `node_modules`, create the
+//! [FileProvider](FileProvider) with a [Resolver](resolver::Resolver) using [FileProvider::with_resolver](FileProvider::with_resolver).
+
+pub mod incremental;
+pub mod resolver;
+mod urls;
 
 use crate::{
+  css_modules::ExternalComposes,
+  dependencies::InlinedAsset,
   error::ErrorLocation,
   parser::DefaultAtRuleParser,
   properties::{
@@ -38,7 +47,7 @@ use crate::{
     Location,
   },
   traits::{AtRuleParser, ToCss},
-  values::ident::DashedIdentReference,
+  values::{ident::DashedIdentReference, url::Url},
 };
 use crate::{
   error::{Error, ParserError},
@@ -54,11 +63,12 @@ use crate::{
 use dashmap::DashMap;
 use parcel_sourcemap::SourceMap;
 use rayon::prelude::*;
+use resolver::Resolver;
 use std::{
-  collections::HashSet,
+  collections::{HashMap, HashSet},
   fs,
   path::{Path, PathBuf},
-  sync::Mutex,
+  sync::{Arc, Mutex},
 };
 
 /// A Bundler combines a CSS file and all imported dependencies together into
@@ -70,6 +80,88 @@ pub struct Bundler<'a, 'o, 's, P, T: AtRuleParser<'a>> {
   stylesheets: Mutex<Vec<BundleStyleSheet<'a, 'o, T::AtRule>>>,
   options: ParserOptions<'o, 'a>,
   at_rule_parser: Mutex<AtRuleParserValue<'s, T>>,
+  output_dir: Option<PathBuf>,
+  url_base: PathBuf,
+  asset_inlining: Option<AssetInliningOptions>,
+  cache: Option<BundleCache<'a, 'o, T::AtRule>>,
+}
+
+/// Files that are kept between builds by an [IncrementalBundler](incremental::IncrementalBundler).
+struct BundleCache<'a, 'o, T> {
+  /// The source index of every file that was loaded, which stays the same between builds.
+  source_indexes: DashMap<PathBuf, u32>,
+  /// The contents of each file, by source index.
+  code: DashMap<u32, &'a str>,
+  /// The parsed style sheet of each file, before any changes that depend on how it was imported.
+  stylesheets: DashMap<u32, StyleSheet<'a, 'o, T>>,
+  /// The source indexes of the files that reference each asset that could be inlined.
+  assets: DashMap<PathBuf, HashSet<u32>>,
+  clone_stylesheet: fn(&StyleSheet<'a, 'o, T>) -> StyleSheet<'a, 'o, T>,
+}
+
+/// Options for inlining small assets referenced via `url()` as `data:` URIs when bundling.
+///
+/// Assets are read using [SourceProvider::read_bytes](SourceProvider::read_bytes). SVG files are
+/// inlined as percent-encoded text, and other files as base64. Only relative urls to files with a
+/// known image or font extension are inlined. Urls with a query string or fragment are left as is.
+///
+/// See [Bundler::set_asset_inlining](Bundler::set_asset_inlining).
+#[derive(Debug, Clone, Default)]
+pub struct AssetInliningOptions {
+  /// Assets smaller than this number of bytes are inlined.
+  pub threshold: usize,
+  /// Paths of asset files that should never be inlined, regardless of their size.
+  pub exclude: Vec<PathBuf>,
+}
+
+/// The result of bundling multiple entries with [Bundler::bundle_entries](Bundler::bundle_entries).
+#[derive(Debug)]
+pub struct MultiBundle<'i, 'o, T> {
+  /// The bundled style sheet of each entry, in the same order as the entries were given.
+  /// Files that were extracted into the shared chunk are not included.
+  pub entries: Vec<StyleSheet<'i, 'o, T>>,
+  /// A style sheet containing the files shared by multiple entries, if any were extracted.
+  pub shared: Option<StyleSheet<'i, 'o, T>>,
+  /// A description of the chunks that each entry needs.
+  pub manifest: BundleManifest,
+}
+
+/// Describes the chunks produced by [Bundler::bundle_entries](Bundler::bundle_entries).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(any(feature = "serde", feature = "nodejs"), derive(serde::Serialize))]
+#[cfg_attr(any(feature = "serde", feature = "nodejs"), serde(rename_all = "camelCase"))]
+pub struct BundleManifest {
+  /// The files included in the shared chunk, in order.
+  pub shared: Vec<PathBuf>,
+  /// The chunks needed by each entry, in the same order as the entries were given.
+  pub entries: Vec<EntryManifest>,
+}
+
+/// Describes the chunks needed by a single entry of a [BundleManifest](BundleManifest).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(any(feature = "serde", feature = "nodejs"), derive(serde::Serialize))]
+#[cfg_attr(any(feature = "serde", feature = "nodejs"), serde(rename_all = "camelCase"))]
+pub struct EntryManifest {
+  /// The path of the entry file.
+  pub entry: PathBuf,
+  /// The chunks that must be loaded for the entry, in order.
+  pub chunks: Vec<Chunk>,
+  /// The files included in the entry's own chunk, in order.
+  pub files: Vec<PathBuf>,
+}
+
+/// A chunk produced by [Bundler::bundle_entries](Bundler::bundle_entries).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(any(feature = "serde", feature = "nodejs"), derive(serde::Serialize))]
+#[cfg_attr(
+  any(feature = "serde", feature = "nodejs"),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+pub enum Chunk {
+  /// The shared chunk.
+  Shared,
+  /// The chunk of the entry at the given index.
+  Entry(usize),
 }
 
 enum AtRuleParserValue<'a, T> {
@@ -100,6 +192,13 @@ pub trait SourceProvider: Send + Sync {
   /// Reads the contents of the given file path to a string.
   fn read<'a>(&'a self, file: &Path) -> Result<&'a str, Self::Error>;
 
+  /// Reads the contents of the given asset file, e.g. an image referenced via `url()`.
+  /// This is used to inline assets when [asset inlining](Bundler::set_asset_inlining) is enabled.
+  /// By default, the file is read as a string using [read](SourceProvider::read).
+  fn read_bytes(&self, file: &Path) -> Result<Vec<u8>, Self::Error> {
+    self.read(file).map(|contents| contents.as_bytes().to_vec())
+  }
+
   /// Resolves the given import specifier to a file path given the file
   /// which the import originated from.
   fn resolve(&self, specifier: &str, originating_file: &Path) -> Result<PathBuf, Self::Error>;
@@ -109,6 +208,7 @@ pub trait SourceProvider: Send + Sync {
 /// that reads files from the file system.
 pub struct FileProvider {
   inputs: Mutex<Vec<*mut String>>,
+  resolver: Option<Resolver>,
 }
 
 impl FileProvider {
@@ -116,6 +216,21 @@ impl FileProvider {
   pub fn new() -> FileProvider {
     FileProvider {
       inputs: Mutex::new(Vec::new()),
+      resolver: None,
+    }
+  }
+
+  /// Creates a new FileProvider that resolves specifiers using the given [Resolver](resolver::Resolver),
+  /// including packages within `node_modules` directories.
+  ///
+  /// Resolution failures are returned as an `std::io::Error` wrapping a [ResolverError](resolver::ResolverError),
+  /// with the kind `InvalidData` for invalid `package.json` files and `NotFound` otherwise. The typed error can
+  /// be retrieved from a bundle error using [BundleErrorKind::resolver_error](BundleErrorKind::resolver_error),
+  /// or by downcasting the result of `std::io::Error::get_ref`.
+  pub fn with_resolver(resolver: Resolver) -> FileProvider {
+    FileProvider {
+      inputs: Mutex::new(Vec::new()),
+      resolver: Some(resolver),
     }
   }
 }
@@ -136,7 +251,23 @@ impl SourceProvider for FileProvider {
     Ok(unsafe { &*ptr })
   }
 
+  fn read_bytes(&self, file: &Path) -> Result<Vec<u8>, Self::Error> {
+    fs::read(file)
+  }
+
   fn resolve(&self, specifier: &str, originating_file: &Path) -> Result<PathBuf, Self::Error> {
+    if let Some(resolver) = &self.resolver {
+      return resolver.resolve(specifier, originating_file).map_err(|err| {
+        let kind = match err {
+          resolver::ResolverError::InvalidPackageJson { .. } => std::io::ErrorKind::InvalidData,
+          resolver::ResolverError::NotFound { .. } | resolver::ResolverError::NotExported { .. } => {
+            std::io::ErrorKind::NotFound
+          }
+        };
+        std::io::Error::new(kind, err)
+      });
+    }
+
     // Assume the specifier is a releative file path and join it with current path.
     Ok(originating_file.with_file_name(specifier))
   }
@@ -188,6 +319,17 @@ impl<'i, T: std::error::Error> std::fmt::Display for BundleErrorKind<'i, T> {
   }
 }
 
+impl<'i> BundleErrorKind<'i, std::io::Error> {
+  /// Returns the [ResolverError](resolver::ResolverError) that caused this error, if an `@import`
+  /// could not be resolved by a [FileProvider](FileProvider) created with a [Resolver](resolver::Resolver).
+  pub fn resolver_error(&self) -> Option<&resolver::ResolverError> {
+    match self {
+      BundleErrorKind::ResolverError(err) => err.get_ref()?.downcast_ref(),
+      _ => None,
+    }
+  }
+}
+
 impl<'i, T: std::error::Error> BundleErrorKind<'i, T> {
   #[deprecated(note = "use `BundleErrorKind::to_string()` or `std::fmt::Display` instead")]
   #[allow(missing_docs)]
@@ -212,6 +354,10 @@ impl<'a, 'o, 's, P: SourceProvider> Bundler<'a, 'o, 's, P, DefaultAtRuleParser>
       stylesheets: Mutex::new(Vec::new()),
       options,
       at_rule_parser: Mutex::new(AtRuleParserValue::Owned(DefaultAtRuleParser)),
+      output_dir: None,
+      url_base: PathBuf::new(),
+      asset_inlining: None,
+      cache: None,
     }
   }
 }
@@ -236,14 +382,42 @@ where
       stylesheets: Mutex::new(Vec::new()),
       options,
       at_rule_parser: Mutex::new(AtRuleParserValue::Borrowed(at_rule_parser)),
+      output_dir: None,
+      url_base: PathBuf::new(),
+      asset_inlining: None,
+      cache: None,
     }
   }
 
+  /// Sets the directory that the bundle will be written to. Relative `url()` references in bundled
+  /// files are rewritten to be relative to this directory. By default, they are rewritten to be
+  /// relative to the directory of the entry file.
+  pub fn set_output_dir(&mut self, dir: &Path) {
+    self.output_dir = Some(dir.to_owned());
+  }
+
+  /// Enables inlining of small assets referenced via `url()` as `data:` URIs.
+  /// Inlined assets are reported in [ToCssResult](crate::stylesheet::ToCssResult)
+  /// when analyzing dependencies while printing the bundle.
+  pub fn set_asset_inlining(&mut self, options: AssetInliningOptions) {
+    self.asset_inlining = Some(options);
+  }
+
   /// Bundles the given entry file and all dependencies into a single style sheet.
+  ///
+  /// Relative `url()` references in dependencies are rewritten so that they resolve to the same
+  /// files from the output directory. When analyzing dependencies while printing the bundle, the
+  /// resulting [UrlDependency](crate::dependencies::UrlDependency) items still refer to the original
+  /// url and its location within the file it was imported from.
   pub fn bundle<'e>(
     &mut self,
     entry: &'e Path,
   ) -> Result<StyleSheet<'a, 'o, T::AtRule>, Error<BundleErrorKind<'a, P::Error>>> {
+    self.url_base = match &self.output_dir {
+      Some(output_dir) => output_dir.clone(),
+      None => entry.parent().map(Path::to_owned).unwrap_or_default(),
+    };
+
     // Phase 1: load and parse all files. This is done in parallel.
     self.load_file(
       &entry,
@@ -261,31 +435,202 @@ where
     )?;
 
     // Phase 2: determine the order that the files should be concatenated.
-    self.order();
+    self.order(0);
 
     // Phase 3: concatenate.
     let mut rules: Vec<CssRule<'a, T::AtRule>> = Vec::new();
-    self.inline(&mut rules);
+    self.inline(0, &mut rules, &|_| true, std::mem::take);
 
-    let sources = self
-      .stylesheets
-      .get_mut()
-      .unwrap()
-      .iter()
-      .flat_map(|s| s.stylesheet.as_ref().unwrap().sources.iter().cloned())
-      .collect();
+    Ok(self.build_stylesheet(rules, 0))
+  }
 
-    let mut stylesheet = StyleSheet::new(sources, CssRuleList(rules), self.options.clone());
+  /// Bundles multiple entry files at once, e.g. a style sheet per route of an application.
+  ///
+  /// Each file is only loaded and parsed once, even if it is imported by multiple entries.
+  /// If `shared_threshold` is given, files that are imported (directly or indirectly) by at
+  /// least that many entries are extracted into a single shared chunk rather than being
+  /// duplicated in each entry's bundle. The returned [BundleManifest](BundleManifest) lists
+  /// the chunks that each entry needs, in the order they should be loaded.
+  ///
+  /// The shared chunk is always loaded before an entry's own chunk. If an entry imports a file
+  /// that is not shared before one that is, e.g. `@import "page.css"; @import "common.css";`,
+  /// the rules of the shared file now come first, which can change the result of the cascade
+  /// when rules of equal specificity in both files apply to the same element. Import shared
+  /// files first, or raise the threshold, if their order matters.
+  ///
+  /// All returned style sheets share the same list of sources, so CSS modules hashes and
+  /// references between files are consistent across chunks. Relative `url()` references are
+  /// rewritten relative to the output directory, or the directory of the first entry.
+  ///
+  /// Classes composed from files in the shared chunk are resolved as usual, including the classes
+  /// that they compose themselves.
+  ///
+  /// Note that a file imported with different `@media`, `@supports`, or `layer()` conditions
+  /// by different entries is included with the combined conditions in every chunk, as is the
+  /// case when a file is imported multiple times within a single bundle.
+  pub fn bundle_entries<'e>(
+    &mut self,
+    entries: &[&'e Path],
+    shared_threshold: Option<usize>,
+  ) -> Result<MultiBundle<'a, 'o, T::AtRule>, Error<BundleErrorKind<'a, P::Error>>>
+  where
+    T::AtRule: Clone,
+  {
+    self.url_base = match (&self.output_dir, entries.first()) {
+      (Some(output_dir), _) => output_dir.clone(),
+      (None, Some(entry)) => entry.parent().map(Path::to_owned).unwrap_or_default(),
+      (None, None) => PathBuf::new(),
+    };
 
-    stylesheet.source_map_urls = self
-      .stylesheets
-      .get_mut()
-      .unwrap()
-      .iter()
-      .flat_map(|s| s.stylesheet.as_ref().unwrap().source_map_urls.iter().cloned())
-      .collect();
+    // Phase 1: load and parse all files reachable from any entry. Files that were already
+    // loaded by a previous entry are reused from the cache.
+    let mut roots = Vec::with_capacity(entries.len());
+    for entry in entries {
+      roots.push(self.load_file(
+        entry,
+        ImportRule {
+          url: "".into(),
+          layer: None,
+          supports: None,
+          media: MediaList::new(),
+          loc: Location {
+            source_index: 0,
+            line: 0,
+            column: 1,
+          },
+        },
+      )?);
+    }
 
-    Ok(stylesheet)
+    // Phase 2: determine which files are shared by enough entries to be extracted.
+    let stylesheets = self.stylesheets.get_mut().unwrap();
+    let mut counts = vec![0; stylesheets.len()];
+    for root in roots.iter().collect::<HashSet<_>>() {
+      for source_index in reachable(stylesheets, *root) {
+        counts[source_index as usize] += 1;
+      }
+    }
+
+    let shared: HashSet<u32> = match shared_threshold {
+      Some(threshold) => (0..counts.len() as u32)
+        .filter(|i| counts[*i as usize] >= threshold.max(2))
+        .collect(),
+      None => HashSet::new(),
+    };
+
+    // Phase 3: concatenate the shared chunk. The dependencies of a shared file are reachable
+    // from at least as many entries, so they are shared too. The chunk can therefore be built by
+    // visiting each entry in order and emitting the shared files it reaches that haven't been
+    // emitted yet, without leaving out any of their dependencies.
+    let mut shared_files = Vec::new();
+    let mut shared_rules = Vec::new();
+    if !shared.is_empty() {
+      for root in &roots {
+        self.order(*root);
+        let emitted = self.inline(
+          *root,
+          &mut shared_rules,
+          &|source_index| shared.contains(&source_index) && !shared_files.contains(&source_index),
+          |rules| rules.clone(),
+        );
+        shared_files.extend(emitted);
+      }
+    }
+
+    // Phase 4: concatenate the remaining files for each entry. Classes composed from the shared
+    // chunk are resolved using the `composes` declarations collected from it.
+    let shared_rules = CssRuleList(shared_rules);
+    let external_composes = if self.options.css_modules.is_some() && !shared_files.is_empty() {
+      Some(Arc::new(ExternalComposes::new(
+        &shared_rules,
+        shared_files.iter().copied().collect(),
+      )))
+    } else {
+      None
+    };
+    let mut stylesheets = Vec::with_capacity(roots.len());
+    let mut manifest = BundleManifest {
+      shared: shared_files.iter().map(|i| self.find_path(*i)).collect(),
+      entries: Vec::with_capacity(roots.len()),
+    };
+
+    for (index, root) in roots.iter().enumerate() {
+      let mut rules = Vec::new();
+      self.order(*root);
+      let files = self.inline(
+        *root,
+        &mut rules,
+        &|source_index| !shared.contains(&source_index),
+        |rules| rules.clone(),
+      );
+
+      let mut chunks = Vec::new();
+      if reachable(self.stylesheets.get_mut().unwrap(), *root)
+        .iter()
+        .any(|i| shared.contains(i))
+      {
+        chunks.push(Chunk::Shared);
+      }
+      if !files.is_empty() {
+        chunks.push(Chunk::Entry(index));
+      }
+
+      manifest.entries.push(EntryManifest {
+        entry: entries[index].to_owned(),
+        chunks,
+        files: files.iter().map(|i| self.find_path(*i)).collect(),
+      });
+      let mut stylesheet = self.build_stylesheet(rules, *root);
+      stylesheet.external_composes = external_composes.clone();
+      stylesheets.push(stylesheet);
+    }
+
+    Ok(MultiBundle {
+      shared: if shared_files.is_empty() {
+        None
+      } else {
+        Some(self.build_stylesheet(shared_rules.0, shared_files[0]))
+      },
+      entries: stylesheets,
+      manifest,
+    })
+  }
+
+  /// Creates a style sheet from the given bundled rules. The sources and other metadata of all
+  /// loaded files are included, so that source indexes are consistent between bundles.
+  /// The CSS module exports of the given entry are returned when printing the style sheet.
+  fn build_stylesheet(&mut self, rules: Vec<CssRule<'a, T::AtRule>>, entry: u32) -> StyleSheet<'a, 'o, T::AtRule> {
+    let mut sources = Vec::new();
+    let mut source_map_urls = Vec::new();
+    let mut original_urls = HashMap::new();
+    let mut inlined_urls = HashMap::new();
+    for s in self.stylesheets.get_mut().unwrap().iter() {
+      match &s.stylesheet {
+        Some(stylesheet) => {
+          sources.extend(stylesheet.sources.iter().cloned());
+          source_map_urls.extend(stylesheet.source_map_urls.iter().cloned());
+          original_urls.extend(stylesheet.original_urls.clone());
+          inlined_urls.extend(stylesheet.inlined_urls.clone());
+        }
+        // A file that failed to load in a previous incremental build still occupies its source index.
+        None => {
+          sources.push(String::new());
+          source_map_urls.push(None);
+        }
+      }
+    }
+
+    let mut stylesheet = StyleSheet::new(sources, CssRuleList(rules), self.options.clone());
+    stylesheet.source_map_urls = source_map_urls;
+    stylesheet.original_urls = original_urls;
+    stylesheet.inlined_urls = inlined_urls;
+    stylesheet.entry_source_index = entry;
+    stylesheet
+  }
+
+  fn find_path(&self, source_index: u32) -> PathBuf {
+    let entry = self.source_indexes.iter().find(|x| *x.value() == source_index).unwrap();
+    entry.key().clone()
   }
 
   fn find_filename(&self, source_index: u32) -> String {
@@ -346,10 +691,7 @@ where
         return Ok(*source_index);
       }
       None => {
-        let source_index = stylesheets.len() as u32;
-        self.source_indexes.insert(file.to_owned(), source_index);
-
-        stylesheets.push(BundleStyleSheet {
+        let entry = BundleStyleSheet {
           stylesheet: None,
           layer: rule.layer.clone(),
           media: rule.media.clone(),
@@ -359,44 +701,51 @@ where
           css_modules_deps: Vec::new(),
           parent_source_index: 0,
           parent_dep_index: 0,
-        });
+        };
+
+        // Files loaded by a previous build keep their source index.
+        let source_index = match self.cache.as_ref().and_then(|cache| cache.source_indexes.get(file)) {
+          Some(source_index) => {
+            stylesheets[*source_index as usize] = entry;
+            *source_index
+          }
+          None => {
+            let source_index = stylesheets.len() as u32;
+            stylesheets.push(entry);
+            if let Some(cache) = &self.cache {
+              cache.source_indexes.insert(file.to_owned(), source_index);
+            }
+            source_index
+          }
+        };
 
+        self.source_indexes.insert(file.to_owned(), source_index);
         source_index
       }
     };
 
     drop(stylesheets); // ensure we aren't holding the lock anymore
 
-    let code = self.fs.read(file).map_err(|e| Error {
-      kind: BundleErrorKind::ResolverError(e),
-      loc: Some(ErrorLocation::new(rule.loc, self.find_filename(rule.loc.source_index))),
-    })?;
-
-    let mut opts = self.options.clone();
-    let filename = file.to_str().unwrap();
-    opts.filename = filename.to_owned();
-    opts.source_index = source_index;
-
-    let mut stylesheet = {
-      let mut at_rule_parser = self.at_rule_parser.lock().unwrap();
-      let at_rule_parser = match &mut *at_rule_parser {
-        AtRuleParserValue::Owned(owned) => owned,
-        AtRuleParserValue::Borrowed(borrowed) => *borrowed,
-      };
+    // Reuse the parsed style sheet from a previous build if the file hasn't changed.
+    let cached = self.cache.as_ref().and_then(|cache| {
+      cache
+        .stylesheets
+        .get(&source_index)
+        .map(|stylesheet| (cache.clone_stylesheet)(&stylesheet))
+    });
 
-      StyleSheet::<T::AtRule>::parse_with(code, opts, at_rule_parser)?
+    let mut stylesheet = match cached {
+      Some(stylesheet) => stylesheet,
+      None => {
+        let stylesheet = self.parse_file(file, source_index, &rule)?;
+        if let Some(cache) = &self.cache {
+          cache.stylesheets.insert(source_index, (cache.clone_stylesheet)(&stylesheet));
+        }
+        stylesheet
+      }
     };
 
-    if let Some(source_map) = &self.source_map {
-      // Only add source if we don't have an input source map.
-      // If we do, this will be handled by the printer when remapping locations.
-      let sm = stylesheet.source_map_url(0);
-      if sm.is_none() || !sm.unwrap().starts_with("data") {
-        let mut source_map = source_map.lock().unwrap();
-        let source_index = source_map.add_source(filename);
-        let _ = source_map.set_source_content(source_index as usize, code);
-      }
-    }
+    self.rewrite_urls(&mut stylesheet, file, source_index);
 
     // Collect and load dependencies for this stylesheet in parallel.
     let dependencies: Result<Vec<u32>, _> = stylesheet
@@ -541,6 +890,86 @@ where
     Ok(source_index)
   }
 
+  /// Reads and parses the given file. The result only depends on the contents of the file,
+  /// so it can be reused between builds.
+  fn parse_file(
+    &self,
+    file: &Path,
+    source_index: u32,
+    rule: &ImportRule<'a>,
+  ) -> Result<StyleSheet<'a, 'o, T::AtRule>, Error<BundleErrorKind<'a, P::Error>>> {
+    let cached_code = self
+      .cache
+      .as_ref()
+      .and_then(|cache| cache.code.get(&source_index).map(|code| *code));
+    let code = match cached_code {
+      Some(code) => code,
+      None => self.fs.read(file).map_err(|e| Error {
+        kind: BundleErrorKind::ResolverError(e),
+        loc: Some(ErrorLocation::new(rule.loc, self.find_filename(rule.loc.source_index))),
+      })?,
+    };
+
+    if let Some(cache) = &self.cache {
+      cache.code.insert(source_index, code);
+    }
+
+    let mut opts = self.options.clone();
+
+    let filename = file.to_str().unwrap();
+    opts.filename = filename.to_owned();
+    opts.source_index = source_index;
+
+    let stylesheet = {
+      let mut at_rule_parser = self.at_rule_parser.lock().unwrap();
+      let at_rule_parser = match &mut *at_rule_parser {
+        AtRuleParserValue::Owned(owned) => owned,
+        AtRuleParserValue::Borrowed(borrowed) => *borrowed,
+      };
+
+      StyleSheet::<T::AtRule>::parse_with(code, opts, at_rule_parser)?
+    };
+
+    if let Some(source_map) = &self.source_map {
+      // Only add source if we don't have an input source map.
+      // If we do, this will be handled by the printer when remapping locations.
+      let sm = stylesheet.source_map_url(0);
+      if sm.is_none() || !sm.unwrap().starts_with("data") {
+        let mut source_map = source_map.lock().unwrap();
+        let source_index = source_map.add_source(filename);
+        let _ = source_map.set_source_content(source_index as usize, code);
+      }
+    }
+
+    Ok(stylesheet)
+  }
+
+  /// Inlines small assets, and rewrites other relative urls so they resolve from the output
+  /// directory rather than the given file. This is done after the parsed style sheet is cached,
+  /// because the result depends on the contents of the referenced assets as well.
+  fn rewrite_urls(&self, stylesheet: &mut StyleSheet<'a, 'o, T::AtRule>, file: &Path, source_index: u32) {
+    urls::visit_urls(&mut stylesheet.rules, &mut |url| {
+      if url.is_absolute() {
+        return;
+      }
+
+      if let Some((data_url, asset)) = self.inline_asset(url, file, source_index) {
+        url.url = data_url.into();
+        stylesheet
+          .inlined_urls
+          .insert((source_index, url.loc.line, url.loc.column), asset);
+        return;
+      }
+
+      if let Some(rebased) = urls::rebase_url(&url.url, file, &self.url_base) {
+        let original = std::mem::replace(&mut url.url, rebased.into());
+        stylesheet
+          .original_urls
+          .insert((source_index, url.loc.line, url.loc.column), original.to_string());
+      }
+    });
+  }
+
   fn add_css_module_dep(
     &self,
     file: &Path,
@@ -587,8 +1016,41 @@ where
     }
   }
 
-  fn order(&mut self) {
-    process(self.stylesheets.get_mut().unwrap(), 0, &mut HashSet::new());
+  /// Reads the asset referenced by a relative url within the given file, and returns a `data:` URI
+  /// for it if it should be inlined according to the asset inlining options.
+  fn inline_asset(&self, url: &Url, file: &Path, source_index: u32) -> Option<(String, InlinedAsset)> {
+    let options = self.asset_inlining.as_ref()?;
+    if url.url.is_empty() || url.url.contains(['?', '#']) {
+      return None;
+    }
+
+    let path = urls::normalize(&file.parent()?.join(url.url.as_ref()));
+    let mime_type = urls::mime_type(&path)?;
+    if options.exclude.iter().any(|excluded| urls::normalize(excluded) == path) {
+      return None;
+    }
+
+    if let Some(cache) = &self.cache {
+      cache.assets.entry(path.clone()).or_default().insert(source_index);
+    }
+
+    let bytes = self.fs.read_bytes(&path).ok()?;
+    if bytes.len() >= options.threshold {
+      return None;
+    }
+
+    let asset = InlinedAsset::new(
+      url,
+      file.to_str()?,
+      path.to_string_lossy().into_owned(),
+      mime_type,
+      bytes.len(),
+    );
+    Some((urls::data_url(mime_type, &bytes), asset))
+  }
+
+  fn order(&mut self, root: u32) {
+    process(self.stylesheets.get_mut().unwrap(), root, &mut HashSet::new());
 
     fn process<'i, T>(
       stylesheets: &mut Vec<BundleStyleSheet<'i, '_, T>>,
@@ -630,16 +1092,43 @@ where
     }
   }
 
-  fn inline(&mut self, dest: &mut Vec<CssRule<'a, T::AtRule>>) {
-    process(self.stylesheets.get_mut().unwrap(), 0, dest);
+  /// Concatenates the rules of the given root file and its dependencies in the order computed by
+  /// [order](Bundler::order). Only files for which `emit` returns true are included, but the
+  /// dependencies of all files are visited. The rules of each emitted file are retrieved using
+  /// `get_rules`, which either takes or clones them. Returns the emitted files in order.
+  fn inline(
+    &mut self,
+    root: u32,
+    dest: &mut Vec<CssRule<'a, T::AtRule>>,
+    emit: &dyn Fn(u32) -> bool,
+    get_rules: GetRules<'a, T::AtRule>,
+  ) -> Vec<u32> {
+    let mut emitted = Vec::new();
+    process(
+      self.stylesheets.get_mut().unwrap(),
+      root,
+      dest,
+      &mut emitted,
+      emit,
+      get_rules,
+    );
+    return emitted;
 
     fn process<'a, T>(
       stylesheets: &mut Vec<BundleStyleSheet<'a, '_, T>>,
       source_index: u32,
       dest: &mut Vec<CssRule<'a, T>>,
+      emitted: &mut Vec<u32>,
+      emit: &dyn Fn(u32) -> bool,
+      get_rules: GetRules<'a, T>,
     ) {
+      let should_emit = emit(source_index);
       let stylesheet = &mut stylesheets[source_index as usize];
-      let mut rules = std::mem::take(&mut stylesheet.stylesheet.as_mut().unwrap().rules.0);
+      let mut rules = if should_emit {
+        get_rules(&mut stylesheet.stylesheet.as_mut().unwrap().rules.0)
+      } else {
+        Vec::new()
+      };
 
       // Hoist css modules deps
       let mut dep_index = 0;
@@ -649,12 +1138,25 @@ where
 
         // Include the dependency if this is the first instance as computed earlier.
         if resolved.parent_source_index == source_index && resolved.parent_dep_index == dep_index as u32 {
-          process(stylesheets, dep_source_index, dest);
+          process(stylesheets, dep_source_index, dest, emitted, emit, get_rules);
         }
 
         dep_index += 1;
       }
 
+      if !should_emit {
+        // Only visit the dependencies of files that are not emitted.
+        for import_index in 0..stylesheets[source_index as usize].dependencies.len() {
+          let dep_source_index = stylesheets[source_index as usize].dependencies[import_index];
+          let resolved = &stylesheets[dep_source_index as usize];
+          if resolved.parent_source_index == source_index && resolved.parent_dep_index == dep_index {
+            process(stylesheets, dep_source_index, dest, emitted, emit, get_rules);
+          }
+          dep_index += 1;
+        }
+        return;
+      }
+
       let mut import_index = 0;
       for rule in &mut rules {
         match rule {
@@ -664,7 +1166,7 @@ where
 
             // Include the dependency if this is the last instance as computed earlier.
             if resolved.parent_source_index == source_index && resolved.parent_dep_index == dep_index {
-              process(stylesheets, dep_source_index, dest);
+              process(stylesheets, dep_source_index, dest, emitted, emit, get_rules);
             }
 
             *rule = CssRule::Ignored;
@@ -683,11 +1185,11 @@ where
       }
 
       // Wrap rules in the appropriate @layer, @media, and @supports rules.
-      let stylesheet = &mut stylesheets[source_index as usize];
+      let stylesheet = &stylesheets[source_index as usize];
 
-      if stylesheet.layer.is_some() {
+      if let Some(layer) = &stylesheet.layer {
         rules = vec![CssRule::LayerBlock(LayerBlockRule {
-          name: stylesheet.layer.take().unwrap(),
+          name: layer.clone(),
           rules: CssRuleList(rules),
           loc: stylesheet.loc,
         })]
@@ -695,25 +1197,42 @@ where
 
       if !stylesheet.media.media_queries.is_empty() {
         rules = vec![CssRule::Media(MediaRule {
-          query: std::mem::replace(&mut stylesheet.media, MediaList::new()),
+          query: stylesheet.media.clone(),
           rules: CssRuleList(rules),
           loc: stylesheet.loc,
         })]
       }
 
-      if stylesheet.supports.is_some() {
+      if let Some(supports) = &stylesheet.supports {
         rules = vec![CssRule::Supports(SupportsRule {
-          condition: stylesheet.supports.take().unwrap(),
+          condition: supports.clone(),
           rules: CssRuleList(rules),
           loc: stylesheet.loc,
         })]
       }
 
       dest.extend(rules);
+      emitted.push(source_index);
     }
   }
 }
 
+/// Retrieves the rules of a file when concatenating a bundle, either by taking or cloning them.
+type GetRules<'a, T> = fn(&mut Vec<CssRule<'a, T>>) -> Vec<CssRule<'a, T>>;
+
+/// Returns the source indexes of the given file and all files it depends on.
+fn reachable<T>(stylesheets: &[BundleStyleSheet<'_, '_, T>], root: u32) -> HashSet<u32> {
+  let mut visited = HashSet::new();
+  let mut stack = vec![root];
+  while let Some(source_index) = stack.pop() {
+    if visited.insert(source_index) {
+      let stylesheet = &stylesheets[source_index as usize];
+      stack.extend(stylesheet.dependencies.iter().chain(stylesheet.css_modules_deps.iter()));
+    }
+  }
+  visited
+}
+
 fn combine_supports<'a>(
   a: Option<SupportsCondition<'a>>,
   b: &Option<SupportsCondition<'a>>,
@@ -754,6 +1273,10 @@ fn visit_vars<'a, 'b>(
           UnresolvedColor::RGB { alpha, .. } | UnresolvedColor::HSL { alpha, .. } => {
             stack.push(alpha.0.iter_mut());
           }
+          UnresolvedColor::LightDark { light, dark } => {
+            stack.push(light.0.iter_mut());
+            stack.push(dark.0.iter_mut());
+          }
         },
         None => {
           stack.pop();
@@ -1960,6 +2483,419 @@ mod tests {
     assert_eq!(code, expected);
   }
 
+  #[test]
+  fn test_url_rebasing() {
+    let fs = TestProvider {
+      map: fs! {
+        "/src/index.css": r#"
+          @import "../components/button.css";
+          .index { background: url(images/bg.png) }
+        "#,
+        "/src/../components/button.css": r##"
+          @import "icons/icons.css";
+          @font-face {
+            font-family: Icons;
+            src: url(./fonts/icons.woff2) format("woff2"), local(Icons);
+          }
+          .button {
+            background: url(./icon.svg), url("../shared/bg.png?v=1#top");
+            mask-image: image-set("./mask.png" 1x, url(mask@2x.png) 2x);
+            cursor: url(cursor.cur), auto;
+            --icon: url(icon.svg);
+          }
+          .button:before {
+            content: url(./arrow.svg) / "Next";
+          }
+          .absolute {
+            background: url(/static/a.png), url(https://example.com/b.png);
+            filter: url(#blur);
+            list-style-image: url("data:image/png;base64,iVBORw0KGgo=");
+          }
+        "##,
+        "/src/../components/icons/icons.css": r#"
+          .icon { background: url(../../icons/star.svg) }
+        "#
+      },
+    };
+
+    let res = bundle(fs.clone(), "/src/index.css");
+    assert_eq!(
+      res,
+      indoc! { r##"
+      .icon {
+        background: url("../icons/star.svg");
+      }
+
+      @font-face {
+        font-family: Icons;
+        src: url("../components/fonts/icons.woff2") format("woff2"), local(Icons);
+      }
+
+      .button {
+        background: url("../components/icon.svg"), url("../shared/bg.png?v=1#top");
+        mask-image: image-set("../components/mask.png" 1x, "../components/mask@2x.png" 2x);
+        cursor: url("../components/cursor.cur"), auto;
+        --icon: url("../components/icon.svg");
+      }
+
+      .button:before {
+        content: url("../components/arrow.svg") / "Next";
+      }
+
+      .absolute {
+        background: url("/static/a.png"), url("https://example.com/b.png");
+        filter: url("#blur");
+        list-style-image: url("data:image/png;base64,iVBORw0KGgo=");
+      }
+
+      .index {
+        background: url("images/bg.png");
+      }
+    "##}
+    );
+
+    let mut bundler = Bundler::new(&fs, None, ParserOptions::default());
+    bundler.set_output_dir(Path::new("/dist/css"));
+    let stylesheet = bundler.bundle(Path::new("/src/index.css")).unwrap();
+    let res = stylesheet.to_css(PrinterOptions::default()).unwrap().code;
+    assert!(res.contains(r#"url("../../icons/star.svg")"#));
+    assert!(res.contains(r#"url("../../components/icon.svg")"#));
+    assert!(res.contains(r#"url("../../src/images/bg.png")"#));
+
+    // A relative output dir is resolved from the current directory.
+    let mut bundler = Bundler::new(&fs, None, ParserOptions::default());
+    bundler.set_output_dir(Path::new("dist"));
+    let stylesheet = bundler.bundle(Path::new("/src/index.css")).unwrap();
+    let res = stylesheet.to_css(PrinterOptions::default()).unwrap().code;
+    let depth = std::env::current_dir().unwrap().components().count();
+    assert!(res.contains(&format!(r#"url("{}src/images/bg.png")"#, "../".repeat(depth))));
+    assert!(!res.contains(r#"url("//"#));
+
+    // Dependencies refer to the original url and its location in the imported file.
+    let fs = TestProvider {
+      map: fs! {
+        "/src/index.css": r#"
+          @import "../components/button.css";
+        "#,
+        "/src/../components/button.css": r#"
+          .button {
+            cursor: url(cursor.cur), auto;
+          }
+        "#
+      },
+    };
+    let mut bundler = Bundler::new(&fs, None, ParserOptions::default());
+    let stylesheet = bundler.bundle(Path::new("/src/index.css")).unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        analyze_dependencies: Some(Default::default()),
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    let dependencies = res.dependencies.unwrap();
+    assert_eq!(dependencies.len(), 1);
+    match &dependencies[0] {
+      crate::dependencies::Dependency::Url(dep) => {
+        assert_eq!(dep.url, "cursor.cur");
+        assert_eq!(dep.loc.file_path, "/src/../components/button.css");
+        assert_eq!((dep.loc.start.line, dep.loc.start.column), (3, 24));
+        assert_eq!((dep.loc.end.line, dep.loc.end.column), (3, 33));
+      }
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn test_asset_inlining() {
+    let fs = TestProvider {
+      map: fs! {
+        "/src/index.css": r#"
+          @import "components/button.css";
+          .logo {
+            background: url(logo.svg);
+          }
+          .hero {
+            background: url(hero.png);
+          }
+        "#,
+        "/src/components/button.css": r#"
+          .button {
+            background-image: url(icons/arrow.svg);
+            cursor: url(cursor.png), auto;
+          }
+          .skip {
+            background: url(icons/skip.svg);
+          }
+          .sprite {
+            background: url(icons/arrow.svg#right);
+          }
+        "#,
+        "/src/logo.svg": r#"<svg xmlns="http://www.w3.org/2000/svg"><circle r="1"/></svg>"#,
+        "/src/hero.png": "x".repeat(100),
+        "/src/components/icons/arrow.svg": "<svg><path d='M0 0'/></svg>\n",
+        "/src/components/icons/skip.svg": "<svg/>",
+        "/src/components/cursor.png": "PNG"
+      },
+    };
+    let mut bundler = Bundler::new(&fs, None, ParserOptions::default());
+    bundler.set_asset_inlining(AssetInliningOptions {
+      threshold: 100,
+      exclude: vec![PathBuf::from("/src/components/../components/icons/skip.svg")],
+    });
+    let stylesheet = bundler.bundle(Path::new("/src/index.css")).unwrap();
+    let res = stylesheet.to_css(PrinterOptions::default()).unwrap();
+    assert_eq!(
+      res.code,
+      indoc! { r#"
+      .button {
+        background-image: url("data:image/svg+xml,<svg><path%20d=%27M0%200%27/></svg>");
+        cursor: url("data:image/png;base64,UE5H"), auto;
+      }
+
+      .skip {
+        background: url("components/icons/skip.svg");
+      }
+
+      .sprite {
+        background: url("components/icons/arrow.svg#right");
+      }
+
+      .logo {
+        background: url("data:image/svg+xml,<svg%20xmlns=%22http://www.w3.org/2000/svg%22><circle%20r=%221%22/></svg>");
+      }
+
+      .hero {
+        background: url("hero.png");
+      }
+    "#}
+    );
+    assert!(res.inlined_assets.is_none());
+
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        analyze_dependencies: Some(Default::default()),
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    assert!(res.code.contains("url(\"data:image/png;base64,UE5H\")"));
+
+    let dependencies = res.dependencies.unwrap();
+    let urls: Vec<_> = dependencies
+      .iter()
+      .map(|dep| match dep {
+        crate::dependencies::Dependency::Url(dep) => dep.url.as_str(),
+        _ => unreachable!(),
+      })
+      .collect();
+    assert_eq!(urls, vec!["icons/skip.svg", "icons/arrow.svg#right", "hero.png"]);
+
+    let inlined_assets = res.inlined_assets.unwrap();
+    let assets: Vec<_> = inlined_assets
+      .iter()
+      .map(|asset| {
+        (
+          asset.url.as_str(),
+          asset.file_path.as_str(),
+          asset.mime_type.as_str(),
+          asset.size,
+        )
+      })
+      .collect();
+    assert_eq!(
+      assets,
+      vec![
+        (
+          "icons/arrow.svg",
+          "/src/components/icons/arrow.svg",
+          "image/svg+xml",
+          28
+        ),
+        ("cursor.png", "/src/components/cursor.png", "image/png", 3),
+        ("logo.svg", "/src/logo.svg", "image/svg+xml", 61),
+      ]
+    );
+    assert_eq!(inlined_assets[1].loc.file_path, "/src/components/button.css");
+    assert_eq!(
+      (inlined_assets[1].loc.start.line, inlined_assets[1].loc.start.column),
+      (4, 24)
+    );
+  }
+
+  #[test]
+  fn test_bundle_entries() {
+    fn bundle_entries(shared_threshold: Option<usize>) -> (Vec<String>, Option<String>, BundleManifest) {
+      let fs = TestProvider {
+        map: fs! {
+          "/a.css": r#"
+            @import "base.css";
+            @import "widgets.css";
+            .a { color: red }
+          "#,
+          "/b.css": r#"
+            @import "base.css";
+            @import "widgets.css";
+            .b { color: green }
+          "#,
+          "/c.css": r#"
+            @import "base.css";
+            .c { color: blue }
+          "#,
+          "/base.css": r#"
+            @import "reset.css";
+            .base { color: black }
+          "#,
+          "/reset.css": r#"
+            * { margin: 0 }
+          "#,
+          "/widgets.css": r#"
+            .widget { color: yellow }
+          "#
+        },
+      };
+      let mut bundler = Bundler::new(&fs, None, ParserOptions::default());
+      let entries = [Path::new("/a.css"), Path::new("/b.css"), Path::new("/c.css")];
+      let bundle = bundler.bundle_entries(&entries, shared_threshold).unwrap();
+      let print = |stylesheet: &StyleSheet| {
+        stylesheet
+          .to_css(PrinterOptions {
+            minify: true,
+            ..PrinterOptions::default()
+          })
+          .unwrap()
+          .code
+      };
+      (
+        bundle.entries.iter().map(print).collect(),
+        bundle.shared.as_ref().map(print),
+        bundle.manifest,
+      )
+    }
+
+    let (entries, shared, manifest) = bundle_entries(None);
+    assert_eq!(
+      entries,
+      vec![
+        "*{margin:0}.base{color:#000}.widget{color:#ff0}.a{color:red}",
+        "*{margin:0}.base{color:#000}.widget{color:#ff0}.b{color:green}",
+        "*{margin:0}.base{color:#000}.c{color:#00f}",
+      ]
+    );
+    assert_eq!(shared, None);
+    assert_eq!(manifest.shared, Vec::<PathBuf>::new());
+    assert_eq!(manifest.entries[2].chunks, vec![Chunk::Entry(2)]);
+    assert_eq!(
+      manifest.entries[2].files,
+      vec![
+        PathBuf::from("/reset.css"),
+        PathBuf::from("/base.css"),
+        PathBuf::from("/c.css")
+      ]
+    );
+
+    let (entries, shared, manifest) = bundle_entries(Some(3));
+    assert_eq!(
+      entries,
+      vec![
+        ".widget{color:#ff0}.a{color:red}",
+        ".widget{color:#ff0}.b{color:green}",
+        ".c{color:#00f}",
+      ]
+    );
+    assert_eq!(shared.unwrap(), "*{margin:0}.base{color:#000}");
+    assert_eq!(
+      manifest,
+      BundleManifest {
+        shared: vec![PathBuf::from("/reset.css"), PathBuf::from("/base.css")],
+        entries: vec![
+          EntryManifest {
+            entry: PathBuf::from("/a.css"),
+            chunks: vec![Chunk::Shared, Chunk::Entry(0)],
+            files: vec![PathBuf::from("/widgets.css"), PathBuf::from("/a.css")],
+          },
+          EntryManifest {
+            entry: PathBuf::from("/b.css"),
+            chunks: vec![Chunk::Shared, Chunk::Entry(1)],
+            files: vec![PathBuf::from("/widgets.css"), PathBuf::from("/b.css")],
+          },
+          EntryManifest {
+            entry: PathBuf::from("/c.css"),
+            chunks: vec![Chunk::Shared, Chunk::Entry(2)],
+            files: vec![PathBuf::from("/c.css")],
+          },
+        ],
+      }
+    );
+
+    let (entries, shared, manifest) = bundle_entries(Some(2));
+    assert_eq!(entries, vec![".a{color:red}", ".b{color:green}", ".c{color:#00f}"]);
+    assert_eq!(shared.unwrap(), "*{margin:0}.base{color:#000}.widget{color:#ff0}");
+    assert_eq!(
+      manifest.shared,
+      vec![
+        PathBuf::from("/reset.css"),
+        PathBuf::from("/base.css"),
+        PathBuf::from("/widgets.css")
+      ]
+    );
+
+    // CSS modules hashes are consistent between the shared chunk and the entries.
+    let fs = TestProvider {
+      map: fs! {
+        "/a.css": r#"
+          .a { composes: button from "./button.css"; color: red }
+        "#,
+        "/b.css": r#"
+          .b { composes: button from "./button.css"; color: green }
+        "#,
+        "/button.css": r#"
+          .button { composes: base from "./base.css"; padding: 0 }
+        "#,
+        "/base.css": r#"
+          .base { margin: 0 }
+        "#
+      },
+    };
+    let mut bundler = Bundler::new(
+      &fs,
+      None,
+      ParserOptions {
+        css_modules: Some(Default::default()),
+        ..ParserOptions::default()
+      },
+    );
+    let entries = [Path::new("/a.css"), Path::new("/b.css")];
+    let bundle = bundler.bundle_entries(&entries, Some(2)).unwrap();
+    let shared = bundle.shared.unwrap().to_css(PrinterOptions::default()).unwrap();
+    assert_eq!(
+      shared.code,
+      indoc! { r#"
+      .hDvoAa_base {
+        margin: 0;
+      }
+
+      .bZ78Tq_button {
+        padding: 0;
+      }
+    "#}
+    );
+    for (stylesheet, name) in bundle.entries.iter().zip(["a", "b"]) {
+      let res = stylesheet.to_css(PrinterOptions::default()).unwrap();
+      let export = &res.exports.unwrap()[name];
+      // Classes composed by shared files are resolved transitively.
+      assert_eq!(
+        export.composes,
+        vec![
+          CssModuleReference::Local {
+            name: "bZ78Tq_button".into()
+          },
+          CssModuleReference::Local {
+            name: "hDvoAa_base".into()
+          }
+        ]
+      );
+    }
+  }
+
   #[test]
   fn test_source_map() {
     let source = r#".imported {
@@ -0,0 +1,558 @@
+//! Node-style resolution of `@import` specifiers.
+//!
+//! A [Resolver](Resolver) resolves bare specifiers such as `normalize.css` or `@acme/tokens/base.css`
+//! to files inside `node_modules` directories, following the same lookup rules as Node.js. The `style`
+//! field and the `exports` map of a package's `package.json` are used to find the stylesheet of a package.
+//! It can be used with a [FileProvider](super::FileProvider) via [FileProvider::with_resolver](super::FileProvider::with_resolver).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use lightningcss::bundler::resolver::Resolver;
+//!
+//! let mut resolver = Resolver::default();
+//! resolver.aliases.insert("@tokens".into(), "@acme/tokens/dist".into());
+//! let path = resolver.resolve("~@tokens/base.css", Path::new("src/style.css")).unwrap();
+//! ```
+
+use serde_json::Value;
+use std::{
+  collections::HashMap,
+  fs,
+  path::{Path, PathBuf},
+};
+
+/// Resolves `@import` specifiers relative to the importing file, or to packages
+/// within `node_modules` directories.
+#[derive(Debug, Clone)]
+pub struct Resolver {
+  /// A map of specifier prefixes to replace before resolving, e.g. `@tokens` → `@acme/tokens/dist`.
+  /// A prefix matches the whole specifier or a leading set of path segments. Replacements may be
+  /// bare specifiers or absolute paths.
+  pub aliases: HashMap<String, String>,
+  /// The conditions that match entries in the `exports` map of a `package.json`. As in Node.js, the first
+  /// matching condition in the order of the `exports` map is used.
+  pub conditions: Vec<String>,
+}
+
+impl Default for Resolver {
+  fn default() -> Resolver {
+    Resolver {
+      aliases: HashMap::new(),
+      conditions: vec!["style".into(), "default".into()],
+    }
+  }
+}
+
+/// An error that occurred while resolving an `@import` specifier.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolverError {
+  /// The specifier could not be resolved to an existing file.
+  NotFound {
+    /// The specifier that was resolved.
+    specifier: String,
+    /// The paths that were tried, in order.
+    attempted: Vec<PathBuf>,
+  },
+  /// The `exports` map of a package does not expose the requested path.
+  NotExported {
+    /// The specifier that was resolved.
+    specifier: String,
+    /// The `package.json` file of the package.
+    package_json: PathBuf,
+  },
+  /// A `package.json` file could not be read or parsed.
+  InvalidPackageJson {
+    /// The path of the `package.json` file.
+    path: PathBuf,
+    /// The reason the file is invalid.
+    reason: String,
+  },
+}
+
+impl std::fmt::Display for ResolverError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    use ResolverError::*;
+    match self {
+      NotFound { specifier, attempted } => {
+        write!(f, "Could not resolve `{}`", specifier)?;
+        if !attempted.is_empty() {
+          write!(f, ", tried:")?;
+          for path in attempted {
+            write!(f, "\n  {}", path.display())?;
+          }
+        }
+        Ok(())
+      }
+      NotExported {
+        specifier,
+        package_json,
+      } => write!(
+        f,
+        "`{}` is not exported by the package at {}",
+        specifier,
+        package_json.display()
+      ),
+      InvalidPackageJson { path, reason } => write!(f, "Invalid package.json at {}: {}", path.display(), reason),
+    }
+  }
+}
+
+impl std::error::Error for ResolverError {}
+
+impl Resolver {
+  /// Resolves the given import specifier to a file path given the file which the import originated from.
+  ///
+  /// Specifiers are first resolved relative to the originating file, as in CSS. If no such file exists
+  /// and the specifier is a bare specifier, it is then looked up in the `node_modules` directories of
+  /// the originating file and its ancestors. Specifiers prefixed with `~` are only looked up as packages.
+  pub fn resolve(&self, specifier: &str, originating_file: &Path) -> Result<PathBuf, ResolverError> {
+    let mut attempted = Vec::new();
+    let (module, is_package) = match specifier.strip_prefix('~') {
+      Some(module) => (module, true),
+      None => (specifier, false),
+    };
+
+    let aliased = self.apply_alias(module);
+    let module = aliased.as_deref().unwrap_or(module);
+    if Path::new(module).is_absolute() {
+      return self
+        .resolve_file(Path::new(module), &mut attempted)
+        .ok_or_else(|| ResolverError::NotFound {
+          specifier: specifier.into(),
+          attempted,
+        });
+    }
+
+    if !is_package && aliased.is_none() {
+      let path = originating_file.with_file_name(module);
+      if let Some(path) = self.resolve_file(&path, &mut attempted) {
+        return Ok(path);
+      }
+    }
+
+    if !is_relative(module) {
+      if let Some(path) = self.resolve_package(module, originating_file, &mut attempted)? {
+        return Ok(path);
+      }
+    }
+
+    Err(ResolverError::NotFound {
+      specifier: specifier.into(),
+      attempted,
+    })
+  }
+
+  /// Replaces the longest alias that matches the start of the specifier.
+  fn apply_alias(&self, specifier: &str) -> Option<String> {
+    self
+      .aliases
+      .iter()
+      .filter(|(alias, _)| {
+        matches!(specifier.strip_prefix(alias.as_str()), Some(rest) if rest.is_empty() || rest.starts_with('/'))
+      })
+      .max_by_key(|(alias, _)| alias.len())
+      .map(|(alias, replacement)| format!("{}{}", replacement, &specifier[alias.len()..]))
+  }
+
+  /// Looks up a package in the `node_modules` directories of the originating file and its ancestors.
+  fn resolve_package(
+    &self,
+    specifier: &str,
+    originating_file: &Path,
+    attempted: &mut Vec<PathBuf>,
+  ) -> Result<Option<PathBuf>, ResolverError> {
+    // Scoped packages have two path segments, e.g. `@acme/tokens`.
+    let segments = if specifier.starts_with('@') { 2 } else { 1 };
+    let mut parts = specifier.splitn(segments + 1, '/');
+    let name = parts.by_ref().take(segments).collect::<Vec<_>>().join("/");
+    let subpath = parts.next().filter(|subpath| !subpath.is_empty());
+
+    for dir in originating_file.ancestors().skip(1) {
+      if dir.file_name() == Some("node_modules".as_ref()) {
+        continue;
+      }
+
+      let package_dir = dir.join("node_modules").join(&name);
+      if !package_dir.is_dir() {
+        attempted.push(package_dir);
+        continue;
+      }
+
+      let package_json_path = package_dir.join("package.json");
+      let package_json = read_package_json(&package_json_path)?;
+      if let Some(exports) = package_json.as_ref().and_then(|json| json.get("exports")) {
+        let subpath = match subpath {
+          Some(subpath) => format!("./{}", subpath),
+          None => ".".into(),
+        };
+
+        let target = self
+          .resolve_exports(exports, &subpath)
+          .ok_or_else(|| ResolverError::NotExported {
+            specifier: specifier.into(),
+            package_json: package_json_path.clone(),
+          })?;
+        let target = target.strip_prefix("./").unwrap_or(&target);
+        return Ok(self.resolve_file(&package_dir.join(target), attempted));
+      }
+
+      let path = match subpath {
+        Some(subpath) => self.resolve_file(&package_dir.join(subpath), attempted),
+        None => self.resolve_package_entry(&package_dir, package_json.as_ref(), attempted),
+      };
+      if path.is_some() {
+        return Ok(path);
+      }
+    }
+
+    Ok(None)
+  }
+
+  /// Resolves the stylesheet of a package without an `exports` map, using the `style` field, a `main`
+  /// field pointing to a CSS file, or an `index.css` file.
+  fn resolve_package_entry(
+    &self,
+    package_dir: &Path,
+    package_json: Option<&Value>,
+    attempted: &mut Vec<PathBuf>,
+  ) -> Option<PathBuf> {
+    let fields = package_json
+      .into_iter()
+      .flat_map(|json| [json.get("style"), json.get("main").filter(|main| is_css(main))])
+      .flatten()
+      .filter_map(|field| field.as_str());
+
+    for field in fields {
+      if let Some(path) = self.resolve_file(&package_dir.join(field), attempted) {
+        return Some(path);
+      }
+    }
+
+    self.resolve_file(&package_dir.join("index.css"), attempted)
+  }
+
+  /// Resolves a subpath (e.g. `.` or `./base.css`) using the `exports` map of a package.
+  fn resolve_exports(&self, exports: &Value, subpath: &str) -> Option<String> {
+    // An `exports` value without subpath keys applies to the package root only.
+    let map = match exports {
+      Value::Object(map) if map.keys().all(|key| key.starts_with('.')) => map,
+      _ if subpath == "." => return self.resolve_target(exports, false).map(Into::into),
+      _ => return None,
+    };
+
+    if let Some(target) = map.get(subpath) {
+      return self.resolve_target(target, false).map(Into::into);
+    }
+
+    // Subpath patterns, e.g. `"./*": "./dist/*.css"`. The longest matching prefix wins.
+    map
+      .iter()
+      .filter_map(|(key, target)| {
+        let (prefix, suffix) = key.split_once('*')?;
+        let matched = subpath.strip_prefix(prefix)?.strip_suffix(suffix)?;
+        Some((prefix.len(), matched, target))
+      })
+      .max_by_key(|(len, ..)| *len)
+      .and_then(|(_, matched, target)| Some(self.resolve_target(target, true)?.replace('*', matched)))
+  }
+
+  /// Resolves an `exports` target, which may be a path, an array of fallbacks, or a map of conditions.
+  /// Targets of subpath patterns must contain a `*`, which is substituted by the caller.
+  fn resolve_target<'a>(&self, target: &'a Value, pattern: bool) -> Option<&'a str> {
+    match target {
+      Value::String(path) if pattern && !path.contains('*') => None,
+      Value::String(path) => Some(path),
+      Value::Array(targets) => targets.iter().find_map(|target| self.resolve_target(target, pattern)),
+      // Conditions are matched in the order of the object's keys. If the target of a matching
+      // condition resolves to nothing, the next matching condition is tried.
+      Value::Object(conditions) => conditions
+        .iter()
+        .filter(|(condition, _)| self.conditions.contains(condition))
+        .find_map(|(_, target)| self.resolve_target(target, pattern)),
+      _ => None,
+    }
+  }
+
+  /// Returns the given path if it is a file, and records it as attempted otherwise.
+  fn resolve_file(&self, path: &Path, attempted: &mut Vec<PathBuf>) -> Option<PathBuf> {
+    if path.is_file() {
+      Some(path.to_owned())
+    } else {
+      attempted.push(path.to_owned());
+      None
+    }
+  }
+}
+
+fn is_relative(specifier: &str) -> bool {
+  specifier.starts_with("./") || specifier.starts_with("../") || specifier == "." || specifier == ".."
+}
+
+fn is_css(value: &Value) -> bool {
+  matches!(value.as_str(), Some(value) if value.ends_with(".css"))
+}
+
+fn read_package_json(path: &Path) -> Result<Option<Value>, ResolverError> {
+  let contents = match fs::read_to_string(path) {
+    Ok(contents) => contents,
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+    Err(err) => {
+      return Err(ResolverError::InvalidPackageJson {
+        path: path.to_owned(),
+        reason: err.to_string(),
+      })
+    }
+  };
+
+  serde_json::from_str(&contents)
+    .map(Some)
+    .map_err(|err| ResolverError::InvalidPackageJson {
+      path: path.to_owned(),
+      reason: err.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    bundler::{Bundler, FileProvider},
+    stylesheet::{ParserOptions, PrinterOptions},
+  };
+  use assert_fs::{prelude::*, TempDir};
+  use indoc::indoc;
+
+  fn project() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    dir.child("src/style.css").write_str(".a { color: red }").unwrap();
+    dir.child("src/local.css").write_str(".local { color: red }").unwrap();
+    dir
+      .child("node_modules/normalize.css/package.json")
+      .write_str(r#"{ "main": "normalize.css" }"#)
+      .unwrap();
+    dir
+      .child("node_modules/normalize.css/normalize.css")
+      .write_str("html { line-height: 1.15 }")
+      .unwrap();
+    dir
+      .child("node_modules/styled/package.json")
+      .write_str(r#"{ "main": "index.js", "style": "dist/styled.css" }"#)
+      .unwrap();
+    dir
+      .child("node_modules/styled/dist/styled.css")
+      .write_str(".styled {}")
+      .unwrap();
+    dir.child("node_modules/plain/index.css").write_str(".plain {}").unwrap();
+    dir.child("node_modules/plain/theme.css").write_str(".theme {}").unwrap();
+    dir
+      .child("node_modules/@acme/tokens/package.json")
+      .write_str(
+        r#"{
+          "exports": {
+            ".": { "import": "./index.js", "style": "./dist/tokens.css" },
+            "./base.css": "./dist/base.css",
+            "./themes/*": { "style": "./dist/themes/*.css" },
+            "./internal/*": null
+          }
+        }"#,
+      )
+      .unwrap();
+    dir
+      .child("node_modules/@acme/tokens/dist/tokens.css")
+      .write_str(":root {}")
+      .unwrap();
+    dir
+      .child("node_modules/@acme/tokens/dist/base.css")
+      .write_str(".base {}")
+      .unwrap();
+    dir
+      .child("node_modules/@acme/tokens/dist/themes/dark.css")
+      .write_str(".dark {}")
+      .unwrap();
+    dir
+      .child("node_modules/ordered/package.json")
+      .write_str(r#"{ "exports": { "default": "./a.css", "style": "./b.css" } }"#)
+      .unwrap();
+    dir.child("node_modules/ordered/a.css").write_str(".a {}").unwrap();
+    dir.child("node_modules/ordered/b.css").write_str(".b {}").unwrap();
+    dir
+      .child("node_modules/fallback/package.json")
+      .write_str(r#"{ "exports": { "style": { "sass": "./index.scss" }, "default": "./index.css" } }"#)
+      .unwrap();
+    dir.child("node_modules/fallback/index.css").write_str(".fallback {}").unwrap();
+    dir
+      .child("node_modules/broken/package.json")
+      .write_str("{ \"style\": ")
+      .unwrap();
+    dir
+  }
+
+  #[test]
+  fn test_resolve() {
+    let dir = project();
+    let from = dir.path().join("src/style.css");
+    let resolver = Resolver::default();
+    let resolve = |specifier: &str| resolver.resolve(specifier, &from);
+
+    assert_eq!(resolve("local.css"), Ok(dir.path().join("src/local.css")));
+    assert_eq!(
+      resolve("normalize.css"),
+      Ok(dir.path().join("node_modules/normalize.css/normalize.css"))
+    );
+    assert_eq!(
+      resolve("~normalize.css"),
+      Ok(dir.path().join("node_modules/normalize.css/normalize.css"))
+    );
+    assert_eq!(
+      resolve("styled"),
+      Ok(dir.path().join("node_modules/styled/dist/styled.css"))
+    );
+    assert_eq!(resolve("plain"), Ok(dir.path().join("node_modules/plain/index.css")));
+    assert_eq!(
+      resolve("~plain/theme.css"),
+      Ok(dir.path().join("node_modules/plain/theme.css"))
+    );
+    assert_eq!(
+      resolve("@acme/tokens"),
+      Ok(dir.path().join("node_modules/@acme/tokens/dist/tokens.css"))
+    );
+    assert_eq!(
+      resolve("@acme/tokens/base.css"),
+      Ok(dir.path().join("node_modules/@acme/tokens/dist/base.css"))
+    );
+    assert_eq!(
+      resolve("@acme/tokens/themes/dark"),
+      Ok(dir.path().join("node_modules/@acme/tokens/dist/themes/dark.css"))
+    );
+    assert_eq!(
+      resolve("@acme/tokens/internal/secret.css"),
+      Err(ResolverError::NotExported {
+        specifier: "@acme/tokens/internal/secret.css".into(),
+        package_json: dir.path().join("node_modules/@acme/tokens/package.json"),
+      })
+    );
+    assert_eq!(resolve("ordered"), Ok(dir.path().join("node_modules/ordered/a.css")));
+    assert_eq!(
+      resolve("fallback"),
+      Ok(dir.path().join("node_modules/fallback/index.css"))
+    );
+    assert!(matches!(
+      resolve("broken"),
+      Err(ResolverError::InvalidPackageJson { .. })
+    ));
+    assert_eq!(
+      resolve("./missing.css"),
+      Err(ResolverError::NotFound {
+        specifier: "./missing.css".into(),
+        attempted: vec![dir.path().join("src/./missing.css")],
+      })
+    );
+
+    let err = resolve("missing").unwrap_err();
+    match &err {
+      ResolverError::NotFound { attempted, .. } => {
+        assert_eq!(attempted[0], dir.path().join("src/missing"));
+        assert_eq!(attempted[1], dir.path().join("src/node_modules/missing"));
+        assert_eq!(attempted[2], dir.path().join("node_modules/missing"));
+      }
+      _ => unreachable!(),
+    }
+    assert!(err.to_string().starts_with("Could not resolve `missing`, tried:\n  "));
+  }
+
+  #[test]
+  fn test_resolve_aliases() {
+    let dir = project();
+    let from = dir.path().join("src/style.css");
+    let mut resolver = Resolver::default();
+    resolver.aliases.insert("@tokens".into(), "@acme/tokens".into());
+    resolver.aliases.insert("@tokens/themes".into(), "@acme/tokens/themes".into());
+    resolver
+      .aliases
+      .insert("@src".into(), dir.path().join("src").to_str().unwrap().into());
+
+    assert_eq!(
+      resolver.resolve("~@tokens/base.css", &from),
+      Ok(dir.path().join("node_modules/@acme/tokens/dist/base.css"))
+    );
+    assert_eq!(
+      resolver.resolve("@tokens/themes/dark", &from),
+      Ok(dir.path().join("node_modules/@acme/tokens/dist/themes/dark.css"))
+    );
+    assert_eq!(
+      resolver.resolve("@src/local.css", &from),
+      Ok(dir.path().join("src/local.css"))
+    );
+    assert!(resolver.resolve("@tokensfoo", &from).is_err());
+  }
+
+  #[test]
+  fn test_bundle_with_resolver() {
+    let dir = project();
+    dir
+      .child("src/index.css")
+      .write_str(
+        r#"
+        @import "normalize.css";
+        @import "~@acme/tokens/base.css" layer(tokens);
+        @import "local.css";
+        "#,
+      )
+      .unwrap();
+
+    let fs = FileProvider::with_resolver(Resolver::default());
+    let mut bundler = Bundler::new(&fs, None, ParserOptions::default());
+    let stylesheet = bundler.bundle(&dir.path().join("src/index.css")).unwrap();
+    assert_eq!(
+      stylesheet.to_css(PrinterOptions::default()).unwrap().code,
+      indoc! {r#"
+        html {
+          line-height: 1.15;
+        }
+
+        @layer tokens {
+          .base {
+          }
+        }
+
+        .local {
+          color: red;
+        }
+      "#}
+    );
+
+    dir.child("src/missing.css").write_str("@import \"missing\";").unwrap();
+    let fs = FileProvider::with_resolver(Resolver::default());
+    let mut bundler = Bundler::new(&fs, None, ParserOptions::default());
+    let err = bundler.bundle(&dir.path().join("src/missing.css")).unwrap_err();
+    assert!(matches!(
+      err.kind.resolver_error(),
+      Some(ResolverError::NotFound { .. })
+    ));
+    match err.kind {
+      crate::bundler::BundleErrorKind::ResolverError(err) => {
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        let err = err.get_ref().unwrap().downcast_ref::<ResolverError>().unwrap();
+        assert!(matches!(err, ResolverError::NotFound { .. }));
+      }
+      _ => unreachable!(),
+    }
+
+    dir.child("src/broken.css").write_str("@import \"broken\";").unwrap();
+    let fs = FileProvider::with_resolver(Resolver::default());
+    let mut bundler = Bundler::new(&fs, None, ParserOptions::default());
+    let err = bundler.bundle(&dir.path().join("src/broken.css")).unwrap_err();
+    assert!(matches!(
+      err.kind.resolver_error(),
+      Some(ResolverError::InvalidPackageJson { .. })
+    ));
+    match err.kind {
+      crate::bundler::BundleErrorKind::ResolverError(err) => {
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData)
+      }
+      _ => unreachable!(),
+    }
+  }
+}
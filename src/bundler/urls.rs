@@ -0,0 +1,174 @@
+//! Rewriting of `url()` references within bundled style sheets.
+
+use crate::rules::{CssRule, CssRuleList};
+use crate::values::url::Url;
+use crate::visit_types;
+use crate::visitor::{Visit, VisitTypes, Visitor};
+use data_encoding::BASE64;
+use std::convert::Infallible;
+use std::path::{Component, Path, PathBuf};
+
+/// Calls the given function for each `url()` within the rules, including those within nested
+/// rules, declarations, and unparsed or custom property values. Custom at-rules are skipped.
+pub(crate) fn visit_urls<'i, T, F: FnMut(&mut Url<'i>)>(rules: &mut CssRuleList<'i, T>, f: &mut F) {
+  visit_rules(rules, &mut UrlVisitor(f)).unwrap()
+}
+
+/// Visits all rules except custom at-rules, which are not required to implement [Visit](Visit).
+fn visit_rules<'i, T, V: Visitor<'i>>(rules: &mut CssRuleList<'i, T>, visitor: &mut V) -> Result<(), V::Error> {
+  for rule in &mut rules.0 {
+    match rule {
+      CssRule::Style(style) => {
+        style.declarations.visit(visitor)?;
+        visit_rules(&mut style.rules, visitor)?;
+      }
+      CssRule::Nesting(nesting) => {
+        nesting.style.declarations.visit(visitor)?;
+        visit_rules(&mut nesting.style.rules, visitor)?;
+      }
+      CssRule::Media(media) => visit_rules(&mut media.rules, visitor)?,
+      CssRule::Supports(supports) => visit_rules(&mut supports.rules, visitor)?,
+      CssRule::Container(container) => visit_rules(&mut container.rules, visitor)?,
+      CssRule::LayerBlock(layer) => visit_rules(&mut layer.rules, visitor)?,
+      CssRule::Scope(scope) => visit_rules(&mut scope.rules, visitor)?,
+      CssRule::StartingStyle(starting_style) => visit_rules(&mut starting_style.rules, visitor)?,
+      CssRule::MozDocument(document) => visit_rules(&mut document.rules, visitor)?,
+      CssRule::FontFace(font_face) => font_face.visit(visitor)?,
+      CssRule::FontPaletteValues(font_palette_values) => font_palette_values.visit(visitor)?,
+      CssRule::FontFeatureValues(font_feature_values) => font_feature_values.visit(visitor)?,
+      CssRule::Keyframes(keyframes) => keyframes.visit(visitor)?,
+      CssRule::Page(page) => page.visit(visitor)?,
+      CssRule::Viewport(viewport) => viewport.visit(visitor)?,
+      CssRule::CounterStyle(counter_style) => counter_style.visit(visitor)?,
+      CssRule::PositionTry(position_try) => position_try.visit(visitor)?,
+      CssRule::Property(property) => property.visit(visitor)?,
+      CssRule::Unknown(unknown) => unknown.visit(visitor)?,
+      // @import rules are resolved by the bundler itself.
+      CssRule::Import(..)
+      | CssRule::Namespace(..)
+      | CssRule::CustomMedia(..)
+      | CssRule::LayerStatement(..)
+      | CssRule::Ignored
+      | CssRule::Custom(..) => {}
+    }
+  }
+
+  Ok(())
+}
+
+struct UrlVisitor<'a, F>(&'a mut F);
+
+impl<'a, 'i, F: FnMut(&mut Url<'i>)> Visitor<'i> for UrlVisitor<'a, F> {
+  type Error = Infallible;
+
+  const TYPES: VisitTypes = visit_types!(URLS);
+
+  fn visit_url(&mut self, url: &mut Url<'i>) -> Result<(), Self::Error> {
+    (self.0)(url);
+    Ok(())
+  }
+}
+
+/// Rewrites a relative url found in the given file so that it refers to the same target when
+/// resolved relative to the base directory. Query strings and fragments are preserved.
+/// Returns `None` if the url does not need to be rewritten.
+pub(crate) fn rebase_url(url: &str, file: &Path, base: &Path) -> Option<String> {
+  // Diffing an absolute path against a relative one returns the absolute path unchanged,
+  // which would be written as a protocol-relative url, so resolve both from the current directory.
+  let dir = absolute(file.parent()?)?;
+  let base = absolute(base)?;
+  if dir == base {
+    return None;
+  }
+
+  let end = url.find(['?', '#']).unwrap_or(url.len());
+  let (path, suffix) = url.split_at(end);
+  if path.is_empty() {
+    return None;
+  }
+
+  let target = normalize(&dir.join(path));
+  let relative = pathdiff::diff_paths(&target, &base)?;
+  let mut rebased = relative
+    .components()
+    .map(|component| component.as_os_str().to_string_lossy())
+    .collect::<Vec<_>>()
+    .join("/");
+
+  // Keep a trailing slash, e.g. for directory urls.
+  if path.ends_with('/') && !rebased.is_empty() {
+    rebased.push('/');
+  }
+
+  rebased.push_str(suffix);
+  Some(rebased)
+}
+
+/// Resolves a path relative to the current directory, and normalizes it.
+fn absolute(path: &Path) -> Option<PathBuf> {
+  if path.is_absolute() {
+    Some(normalize(path))
+  } else {
+    Some(normalize(&std::env::current_dir().ok()?.join(path)))
+  }
+}
+
+/// Lexically resolves `.` and `..` components of a path.
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+  let mut result = PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        if matches!(result.components().next_back(), Some(Component::Normal(_))) {
+          result.pop();
+        } else {
+          result.push("..");
+        }
+      }
+      component => result.push(component),
+    }
+  }
+  result
+}
+
+/// Returns the MIME type of an asset that may be inlined, based on its file extension.
+pub(crate) fn mime_type(path: &Path) -> Option<&'static str> {
+  let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+  let mime_type = match extension.as_str() {
+    "svg" => "image/svg+xml",
+    "png" => "image/png",
+    "jpg" | "jpeg" => "image/jpeg",
+    "gif" => "image/gif",
+    "webp" => "image/webp",
+    "avif" => "image/avif",
+    "ico" => "image/x-icon",
+    "bmp" => "image/bmp",
+    "woff" => "font/woff",
+    "woff2" => "font/woff2",
+    "ttf" => "font/ttf",
+    "otf" => "font/otf",
+    _ => return None,
+  };
+  Some(mime_type)
+}
+
+/// Encodes the contents of an asset as a `data:` URI. SVG files are percent-encoded, which is
+/// usually smaller than base64 and compresses better. Other files are base64 encoded.
+pub(crate) fn data_url(mime_type: &str, bytes: &[u8]) -> String {
+  if mime_type == "image/svg+xml" {
+    if let Ok(svg) = std::str::from_utf8(bytes) {
+      let mut url = format!("data:{},", mime_type);
+      for b in svg.trim().bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~!$&*+,;=:@/?<>".contains(&b) {
+          url.push(b as char);
+        } else {
+          url.push_str(&format!("%{:02X}", b));
+        }
+      }
+      return url;
+    }
+  }
+
+  format!("data:{};base64,{}", mime_type, BASE64.encode(bytes))
+}
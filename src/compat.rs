@@ -5,13 +5,22 @@ use crate::targets::Browsers;
 #[derive(Clone, Copy, PartialEq)]
 pub enum Feature {
   AbsFunction,
+  AcosFunction,
+  AnchorFunction,
+  AnchorSizeFunction,
   AnyPseudo,
+  AsinFunction,
+  AtScope,
+  Atan2Function,
+  AtanFunction,
   Calc,
   CapUnit,
+  CascadeLayers,
   ChUnit,
   Clamp,
   ColorFunction,
   ContainerQueryLengthUnits,
+  CosFunction,
   CssAnyLink,
   CssAutofill,
   CssCaseInsensitive,
@@ -44,25 +53,31 @@ pub enum Feature {
   Dialog,
   DoublePositionGradients,
   ExUnit,
+  ExpFunction,
   FontFamilySystemUi,
   FormValidation,
   Fullscreen,
+  GradientInterpolation,
   HypotFunction,
   IcUnit,
   ImageSet,
   LabColors,
   LangList,
   LhUnit,
+  LightDark,
+  LogFunction,
   LogicalBorderRadius,
   LogicalBorderShorthand,
   LogicalBorders,
   LogicalInset,
   LogicalMargin,
   LogicalMarginShorthand,
+  LogicalOverscrollBehavior,
   LogicalPadding,
   LogicalPaddingShorthand,
   LogicalSize,
   LogicalTextAlign,
+  Masonry,
   MaxFunction,
   MediaIntervalSyntax,
   MediaRangeSyntax,
@@ -75,6 +90,7 @@ pub enum Feature {
   PlaceContent,
   PlaceItems,
   PlaceSelf,
+  PowFunction,
   QUnit,
   RemFunction,
   RemUnit,
@@ -82,7 +98,11 @@ pub enum Feature {
   RoundFunction,
   Shadowdomv1,
   SignFunction,
+  SinFunction,
   SpaceSeparatedColorFunction,
+  SqrtFunction,
+  Subgrid,
+  TanFunction,
   TextDecorationThicknessPercent,
   TextDecorationThicknessShorthand,
   VbUnit,
@@ -1395,6 +1415,51 @@ impl Feature {
           return false;
         }
       }
+      Feature::CascadeLayers => {
+        if let Some(version) = browsers.edge {
+          if version < 6488064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 6356992 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.chrome {
+          if version < 6488064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 984064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 5570560 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 984064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 6488064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 1179648 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
       Feature::Calc => {
         if let Some(version) = browsers.edge {
           if version < 786432 {
@@ -1971,6 +2036,51 @@ impl Feature {
           return false;
         }
       }
+      Feature::LogicalOverscrollBehavior => {
+        if let Some(version) = browsers.chrome {
+          if version < 5046272 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 5177344 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 4784128 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 4194304 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1048576 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1048576 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 786432 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 5046272 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
       Feature::LogicalSize => {
         if let Some(version) = browsers.chrome {
           if version < 3735552 {
@@ -2581,6 +2691,336 @@ impl Feature {
           return false;
         }
       }
+      Feature::AnchorFunction | Feature::AnchorSizeFunction => {
+        if let Some(version) = browsers.chrome {
+          if version < 8192000 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 8192000 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 7274496 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1703936 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1703936 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 1769472 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 8192000 {
+            return false;
+          }
+        }
+        if browsers.firefox.is_some() || browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::GradientInterpolation => {
+        if let Some(version) = browsers.chrome {
+          if version < 7274496 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 7274496 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 8323072 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 6356992 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1049088 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1049088 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 1441792 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 7274496 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::Subgrid => {
+        if let Some(version) = browsers.chrome {
+          if version < 7667712 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 7667712 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 4653056 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 6750208 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1048576 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1048576 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 1572864 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 7667712 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::Masonry => {
+        if browsers.android.is_some()
+          || browsers.chrome.is_some()
+          || browsers.edge.is_some()
+          || browsers.firefox.is_some()
+          || browsers.ie.is_some()
+          || browsers.ios_saf.is_some()
+          || browsers.opera.is_some()
+          || browsers.safari.is_some()
+          || browsers.samsung.is_some()
+        {
+          return false;
+        }
+      }
+      Feature::LightDark => {
+        if let Some(version) = browsers.chrome {
+          if version < 8060928 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 8060928 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 7864320 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 7143424 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1115392 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1115392 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 1638400 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 8060928 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::SinFunction
+      | Feature::CosFunction
+      | Feature::TanFunction
+      | Feature::AsinFunction
+      | Feature::AcosFunction
+      | Feature::AtanFunction
+      | Feature::Atan2Function => {
+        if let Some(version) = browsers.chrome {
+          if version < 7274496 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 7274496 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 7077888 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 6356992 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 984064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 984064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 1441792 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 7274496 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::PowFunction | Feature::SqrtFunction | Feature::LogFunction | Feature::ExpFunction => {
+        if let Some(version) = browsers.chrome {
+          if version < 7864320 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 7864320 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 7733248 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 6946816 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 984064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 984064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 1638400 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 7864320 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::AtScope => {
+        if let Some(version) = browsers.chrome {
+          if version < 7733248 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 7733248 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 9568256 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 6815744 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1115136 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1115136 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 1638400 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 7733248 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
       Feature::QUnit => {
         if let Some(version) = browsers.chrome {
           if version < 4128768 {
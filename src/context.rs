@@ -2,12 +2,18 @@ use std::collections::HashSet;
 
 use crate::compat::Feature;
 use crate::declaration::DeclarationBlock;
+use crate::media_query::{
+  MediaCondition, MediaFeatureId, MediaFeatureName, MediaFeatureValue, MediaList, MediaQuery, MediaType,
+  QueryFeature,
+};
 use crate::properties::custom::UnparsedProperty;
 use crate::properties::Property;
+use crate::rules::media::MediaRule;
 use crate::rules::supports::{SupportsCondition, SupportsRule};
 use crate::rules::{style::StyleRule, CssRule, CssRuleList};
 use crate::selector::{Direction, PseudoClass};
 use crate::targets::Browsers;
+use crate::values::ident::Ident;
 use crate::vendor_prefix::VendorPrefix;
 use parcel_selectors::parser::Component;
 
@@ -33,6 +39,7 @@ pub(crate) struct PropertyHandlerContext<'i, 'o> {
   supports: Vec<SupportsEntry<'i>>,
   ltr: Vec<Property<'i>>,
   rtl: Vec<Property<'i>>,
+  dark: Vec<Property<'i>>,
   pub context: DeclarationContext,
   pub unused_symbols: &'o HashSet<String>,
 }
@@ -45,6 +52,7 @@ impl<'i, 'o> PropertyHandlerContext<'i, 'o> {
       supports: Vec::new(),
       ltr: Vec::new(),
       rtl: Vec::new(),
+      dark: Vec::new(),
       context: DeclarationContext::None,
       unused_symbols,
     }
@@ -108,6 +116,40 @@ impl<'i, 'o> PropertyHandlerContext<'i, 'o> {
     dest
   }
 
+  pub fn add_dark_rule(&mut self, property: Property<'i>) {
+    self.dark.push(property);
+  }
+
+  pub fn get_dark_rules<T>(&mut self, style_rule: &StyleRule<'i, T>) -> Vec<CssRule<'i, T>> {
+    if self.dark.is_empty() {
+      return Vec::new();
+    }
+
+    vec![CssRule::Media(MediaRule {
+      query: MediaList {
+        media_queries: vec![MediaQuery {
+          qualifier: None,
+          media_type: MediaType::All,
+          condition: Some(MediaCondition::Feature(QueryFeature::Plain {
+            name: MediaFeatureName::Standard(MediaFeatureId::PrefersColorScheme),
+            value: MediaFeatureValue::Ident(Ident("dark".into())),
+          })),
+        }],
+      },
+      rules: CssRuleList(vec![CssRule::Style(StyleRule {
+        selectors: style_rule.selectors.clone(),
+        vendor_prefix: VendorPrefix::None,
+        declarations: DeclarationBlock {
+          declarations: std::mem::take(&mut self.dark),
+          important_declarations: vec![],
+        },
+        rules: CssRuleList(vec![]),
+        loc: style_rule.loc.clone(),
+      })]),
+      loc: style_rule.loc.clone(),
+    })]
+  }
+
   pub fn add_conditional_property(&mut self, condition: SupportsCondition<'i>, property: Property<'i>) {
     if self.context != DeclarationContext::StyleRule {
       return;
@@ -10,6 +10,8 @@
 
 use crate::error::PrinterErrorKind;
 use crate::properties::css_modules::{Composes, Specifier};
+use crate::properties::Property;
+use crate::rules::{CssRule, CssRuleList};
 use crate::selector::SelectorList;
 use data_encoding::{Encoding, Specification};
 use lazy_static::lazy_static;
@@ -19,7 +21,7 @@ use serde::Serialize;
 use smallvec::{smallvec, SmallVec};
 use std::borrow::Cow;
 use std::collections::hash_map::DefaultHasher;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 use std::hash::{Hash, Hasher};
 use std::path::Path;
@@ -210,6 +212,86 @@ pub type CssModuleExports = HashMap<String, CssModuleExport>;
 /// A map of placeholders to references.
 pub type CssModuleReferences = HashMap<String, CssModuleReference>;
 
+/// The classes composed by files that a bundle prints in another chunk, e.g. the shared chunk.
+/// Local names are hashed when printing, so they are stored by source index and original name.
+#[derive(Debug, Default)]
+pub(crate) struct ExternalComposes {
+  /// The source indexes of the files printed in another chunk.
+  pub sources: HashSet<u32>,
+  /// The classes composed by each class in those files, keyed by source index and class name.
+  pub composes: HashMap<(u32, String), Vec<ExternalReference>>,
+}
+
+/// A class composed by a file in another chunk. See [ExternalComposes](ExternalComposes).
+#[derive(Debug)]
+pub(crate) enum ExternalReference {
+  /// A class in a file of the bundle.
+  Local { source_index: u32, name: String },
+  /// A global name, or a name in a file outside of the bundle.
+  Reference(CssModuleReference),
+}
+
+impl ExternalComposes {
+  /// Collects the classes composed by the given rules, which are printed in another chunk.
+  pub fn new<'i, T>(rules: &CssRuleList<'i, T>, sources: HashSet<u32>) -> Self {
+    let mut external = ExternalComposes {
+      sources,
+      composes: HashMap::new(),
+    };
+    external.visit_rules(rules);
+    external
+  }
+
+  fn visit_rules<'i, T>(&mut self, rules: &CssRuleList<'i, T>) {
+    for rule in &rules.0 {
+      match rule {
+        CssRule::Style(style) => {
+          for (property, _) in style.declarations.iter() {
+            if let Property::Composes(composes) = property {
+              self.visit_composes(&style.selectors, composes, style.loc.source_index);
+            }
+          }
+        }
+        CssRule::Media(media) => self.visit_rules(&media.rules),
+        CssRule::Supports(supports) => self.visit_rules(&supports.rules),
+        CssRule::LayerBlock(layer) => self.visit_rules(&layer.rules),
+        _ => {}
+      }
+    }
+  }
+
+  fn visit_composes(&mut self, selectors: &SelectorList, composes: &Composes, source_index: u32) {
+    for sel in &selectors.0 {
+      if sel.len() != 1 {
+        continue;
+      }
+
+      if let parcel_selectors::parser::Component::Class(ref id) = sel.iter_raw_match_order().next().unwrap() {
+        let references = self.composes.entry((source_index, id.0.as_ref().to_owned())).or_default();
+        for name in &composes.names {
+          references.push(match &composes.from {
+            None => ExternalReference::Local {
+              source_index,
+              name: name.0.as_ref().to_owned(),
+            },
+            Some(Specifier::SourceIndex(dep_source_index)) => ExternalReference::Local {
+              source_index: *dep_source_index,
+              name: name.0.as_ref().to_owned(),
+            },
+            Some(Specifier::Global) => ExternalReference::Reference(CssModuleReference::Global {
+              name: name.0.as_ref().into(),
+            }),
+            Some(Specifier::File(file)) => ExternalReference::Reference(CssModuleReference::Dependency {
+              name: name.0.to_string(),
+              specifier: file.to_string(),
+            }),
+          });
+        }
+      }
+    }
+  }
+}
+
 lazy_static! {
   static ref ENCODER: Encoding = {
     let mut spec = Specification::new();
@@ -226,6 +308,7 @@ pub(crate) struct CssModule<'a, 'b, 'c> {
   pub hashes: Vec<String>,
   pub exports_by_source_index: Vec<CssModuleExports>,
   pub references: &'a mut HashMap<String, CssModuleReference>,
+  pub external: Option<&'a ExternalComposes>,
 }
 
 impl<'a, 'b, 'c> CssModule<'a, 'b, 'c> {
@@ -259,6 +342,7 @@ impl<'a, 'b, 'c> CssModule<'a, 'b, 'c> {
       sources,
       hashes,
       references,
+      external: None,
     }
   }
 
@@ -385,6 +469,40 @@ impl<'a, 'b, 'c> CssModule<'a, 'b, 'c> {
     Some(hash)
   }
 
+  /// Resolves a class in a file printed in another chunk, followed by the classes it composes.
+  fn resolve_external(&self, source_index: u32, name: &str, references: &mut Vec<CssModuleReference>) {
+    let reference = CssModuleReference::Local {
+      name: self
+        .config
+        .pattern
+        .write_to_string(
+          String::new(),
+          &self.hashes[source_index as usize],
+          self.sources[source_index as usize],
+          name,
+        )
+        .unwrap(),
+    };
+    if references.contains(&reference) {
+      return;
+    }
+
+    references.push(reference);
+    let composes = self
+      .external
+      .and_then(|external| external.composes.get(&(source_index, name.to_owned())));
+    for composed in composes.into_iter().flatten() {
+      match composed {
+        ExternalReference::Local { source_index, name } => self.resolve_external(*source_index, name, references),
+        ExternalReference::Reference(reference) => {
+          if !references.contains(reference) {
+            references.push(reference.clone());
+          }
+        }
+      }
+    }
+  }
+
   pub fn handle_composes(
     &mut self,
     selectors: &SelectorList,
@@ -419,8 +537,24 @@ impl<'a, 'b, 'c> CssModule<'a, 'b, 'c> {
                       .get_mut(&id.0.as_ref().to_owned())
                       .unwrap();
 
-                    export.composes.push(CssModuleReference::Local { name });
-                    export.composes.extend(composes);
+                    for reference in std::iter::once(CssModuleReference::Local { name }).chain(composes) {
+                      if !export.composes.contains(&reference) {
+                        export.composes.push(reference);
+                      }
+                    }
+                  } else if matches!(self.external, Some(external) if external.sources.contains(dep_source_index))
+                  {
+                    // The dependency is printed in another chunk of the bundle.
+                    let mut references = Vec::new();
+                    self.resolve_external(*dep_source_index, name.0.as_ref(), &mut references);
+                    let export = self.exports_by_source_index[source_index as usize]
+                      .get_mut(id.0.as_ref())
+                      .unwrap();
+                    for reference in references {
+                      if !export.composes.contains(&reference) {
+                        export.composes.push(reference);
+                      }
+                    }
                   }
                   continue;
                 }
@@ -14,6 +14,7 @@ use crate::properties::{
   animation::AnimationHandler,
   background::BackgroundHandler,
   border::BorderHandler,
+  columns::ColumnHandler,
   contain::ContainerHandler,
   display::DisplayHandler,
   flex::FlexHandler,
@@ -25,10 +26,12 @@ use crate::properties::{
   overflow::OverflowHandler,
   position::PositionHandler,
   prefix_handler::{FallbackHandler, PrefixHandler},
+  scroll::OverscrollBehaviorHandler,
   size::SizeHandler,
   text::TextDecorationHandler,
   transform::TransformHandler,
   transition::TransitionHandler,
+  ui::ColorSchemeHandler,
 };
 use crate::properties::{Property, PropertyId};
 use crate::targets::Browsers;
@@ -466,6 +469,7 @@ pub(crate) struct DeclarationHandler<'i> {
   background: BackgroundHandler<'i>,
   border: BorderHandler<'i>,
   outline: OutlineHandler,
+  columns: ColumnHandler,
   flex: FlexHandler,
   grid: GridHandler<'i>,
   align: AlignHandler,
@@ -483,10 +487,12 @@ pub(crate) struct DeclarationHandler<'i> {
   position: PositionHandler,
   inset: InsetHandler<'i>,
   overflow: OverflowHandler,
+  overscroll_behavior: OverscrollBehaviorHandler,
   transform: TransformHandler,
   box_shadow: BoxShadowHandler,
   mask: MaskHandler<'i>,
   container: ContainerHandler<'i>,
+  color_scheme: ColorSchemeHandler,
   fallback: FallbackHandler,
   prefix: PrefixHandler,
   decls: DeclarationList<'i>,
@@ -498,6 +504,7 @@ impl<'i> DeclarationHandler<'i> {
       background: BackgroundHandler::new(targets),
       border: BorderHandler::new(targets),
       outline: OutlineHandler::new(targets),
+      columns: ColumnHandler::new(targets),
       flex: FlexHandler::new(targets),
       grid: GridHandler::default(),
       align: AlignHandler::new(targets),
@@ -515,10 +522,12 @@ impl<'i> DeclarationHandler<'i> {
       position: PositionHandler::new(targets),
       inset: InsetHandler::default(),
       overflow: OverflowHandler::new(targets),
+      overscroll_behavior: OverscrollBehaviorHandler::default(),
       transform: TransformHandler::new(targets),
       box_shadow: BoxShadowHandler::new(targets),
       mask: MaskHandler::default(),
       container: ContainerHandler::default(),
+      color_scheme: ColorSchemeHandler::default(),
       fallback: FallbackHandler::new(targets),
       prefix: PrefixHandler::new(targets),
       decls: DeclarationList::new(),
@@ -539,6 +548,7 @@ impl<'i> DeclarationHandler<'i> {
     self.background.handle_property(property, &mut self.decls, context)
       || self.border.handle_property(property, &mut self.decls, context)
       || self.outline.handle_property(property, &mut self.decls, context)
+      || self.columns.handle_property(property, &mut self.decls, context)
       || self.flex.handle_property(property, &mut self.decls, context)
       || self.grid.handle_property(property, &mut self.decls, context)
       || self.align.handle_property(property, &mut self.decls, context)
@@ -556,10 +566,12 @@ impl<'i> DeclarationHandler<'i> {
       || self.position.handle_property(property, &mut self.decls, context)
       || self.inset.handle_property(property, &mut self.decls, context)
       || self.overflow.handle_property(property, &mut self.decls, context)
+      || self.overscroll_behavior.handle_property(property, &mut self.decls, context)
       || self.transform.handle_property(property, &mut self.decls, context)
       || self.box_shadow.handle_property(property, &mut self.decls, context)
       || self.mask.handle_property(property, &mut self.decls, context)
       || self.container.handle_property(property, &mut self.decls, context)
+      || self.color_scheme.handle_property(property, &mut self.decls, context)
       || self.fallback.handle_property(property, &mut self.decls, context)
       || self.prefix.handle_property(property, &mut self.decls, context)
   }
@@ -568,6 +580,7 @@ impl<'i> DeclarationHandler<'i> {
     self.background.finalize(&mut self.decls, context);
     self.border.finalize(&mut self.decls, context);
     self.outline.finalize(&mut self.decls, context);
+    self.columns.finalize(&mut self.decls, context);
     self.flex.finalize(&mut self.decls, context);
     self.grid.finalize(&mut self.decls, context);
     self.align.finalize(&mut self.decls, context);
@@ -585,10 +598,12 @@ impl<'i> DeclarationHandler<'i> {
     self.position.finalize(&mut self.decls, context);
     self.inset.finalize(&mut self.decls, context);
     self.overflow.finalize(&mut self.decls, context);
+    self.overscroll_behavior.finalize(&mut self.decls, context);
     self.transform.finalize(&mut self.decls, context);
     self.box_shadow.finalize(&mut self.decls, context);
     self.mask.finalize(&mut self.decls, context);
     self.container.finalize(&mut self.decls, context);
+    self.color_scheme.finalize(&mut self.decls, context);
     self.fallback.finalize(&mut self.decls, context);
     self.prefix.finalize(&mut self.decls, context);
   }
@@ -117,8 +117,40 @@ impl UrlDependency {
   }
 }
 
+/// An asset referenced via `url()` that was inlined as a `data:` URI by the bundler.
+///
+/// See [AssetInliningOptions](crate::bundler::AssetInliningOptions).
+#[derive(Debug, Clone)]
+#[cfg_attr(any(feature = "serde", feature = "nodejs"), derive(Serialize))]
+#[cfg_attr(any(feature = "serde", feature = "nodejs"), serde(rename_all = "camelCase"))]
+pub struct InlinedAsset {
+  /// The original url of the asset.
+  pub url: String,
+  /// The resolved path of the asset file.
+  pub file_path: String,
+  /// The MIME type of the asset, detected from its file extension.
+  pub mime_type: String,
+  /// The size of the asset file in bytes.
+  pub size: usize,
+  /// The location of the url in the source file.
+  pub loc: SourceRange,
+}
+
+impl InlinedAsset {
+  /// Creates a new inlined asset for the given url.
+  pub(crate) fn new(url: &Url, filename: &str, file_path: String, mime_type: &str, size: usize) -> InlinedAsset {
+    InlinedAsset {
+      url: url.url.to_string(),
+      file_path,
+      mime_type: mime_type.to_owned(),
+      size,
+      loc: SourceRange::new(filename, url.loc, 4, url.url.len()),
+    }
+  }
+}
+
 /// Represents the range of source code where a dependency was found.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(any(feature = "serde", feature = "nodejs"), derive(Serialize))]
 #[cfg_attr(any(feature = "serde", feature = "nodejs"), serde(rename_all = "camelCase"))]
 pub struct SourceRange {
@@ -335,6 +335,10 @@ pub enum MinifyErrorKind {
     /// The source location of the `@custom-media` rule with unsupported boolean logic.
     custom_media_loc: Location,
   },
+  /// An `@import` rule with a layer cannot be flattened for browsers without cascade layer support.
+  UnsupportedLayerImport,
+  /// An `@layer` rule nested within a style rule cannot be flattened for browsers without cascade layer support.
+  UnsupportedNestedLayer,
 }
 
 impl fmt::Display for MinifyErrorKind {
@@ -347,6 +351,14 @@ impl fmt::Display for MinifyErrorKind {
         f,
         "Boolean logic with media types in @custom-media rules is not supported by Lightning CSS"
       ),
+      UnsupportedLayerImport => write!(
+        f,
+        "@import rules with a layer cannot be lowered for browsers without cascade layer support unless they are bundled"
+      ),
+      UnsupportedNestedLayer => write!(
+        f,
+        "@layer rules nested within style rules cannot be lowered for browsers without cascade layer support"
+      ),
     }
   }
 }
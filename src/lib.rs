@@ -38,6 +38,7 @@ pub mod selector;
 pub mod stylesheet;
 pub mod targets;
 pub mod traits;
+mod tree_shaking;
 pub mod values;
 pub mod vendor_prefix;
 #[cfg(feature = "visitor")]
@@ -2658,6 +2659,147 @@ mod tests {
     );
   }
 
+  #[test]
+  pub fn test_columns() {
+    test(
+      r#"
+      .foo {
+        column-width: 10em;
+        column-count: 3;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        columns: 10em 3;
+      }
+    "#
+      },
+    );
+
+    test(
+      r#"
+      .foo {
+        column-rule-width: 2px;
+        column-rule-style: dotted;
+        column-rule-color: blue;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        column-rule: 2px dotted #00f;
+      }
+    "#
+      },
+    );
+
+    test(
+      r#"
+      .foo {
+        columns: 10em 3;
+        column-count: auto;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        columns: 10em;
+      }
+    "#
+      },
+    );
+
+    test(
+      r#"
+      .foo {
+        column-rule: 2px solid red;
+        column-rule-color: var(--color);
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        column-rule: 2px solid red;
+        column-rule-color: var(--color);
+      }
+    "#
+      },
+    );
+
+    minify_test(".foo { columns: auto }", ".foo{columns:auto}");
+    minify_test(".foo { columns: auto auto }", ".foo{columns:auto}");
+    minify_test(".foo { columns: 3 auto }", ".foo{columns:3}");
+    minify_test(".foo { columns: auto 12em }", ".foo{columns:12em}");
+    minify_test(".foo { columns: 3 12em }", ".foo{columns:12em 3}");
+    minify_test(".foo { column-count: 0 }", ".foo{column-count:0}");
+    minify_test(".foo { column-width: -10px }", ".foo{column-width:-10px}");
+    minify_test(".foo { column-span: all }", ".foo{column-span:all}");
+    minify_test(".foo { column-fill: balance-all }", ".foo{column-fill:balance-all}");
+    minify_test(".foo { column-rule: none }", ".foo{column-rule:none}");
+    minify_test(
+      ".foo { -webkit-columns: 10em 3; columns: 10em 3 }",
+      ".foo{-webkit-columns:10em 3;columns:10em 3}",
+    );
+    minify_test(
+      ".foo { -webkit-column-count: 3; -webkit-column-width: 10em }",
+      ".foo{-webkit-columns:10em 3}",
+    );
+    minify_test(
+      ".foo { -webkit-column-count: 3; column-count: 4 }",
+      ".foo{-webkit-column-count:3;column-count:4}",
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        columns: 10em 3;
+        column-rule: 1px solid lab(40% 56.6 39);
+        column-span: all;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -webkit-columns: 10em 3;
+        -moz-columns: 10em 3;
+        columns: 10em 3;
+        -webkit-column-rule: 1px solid #b32323;
+        -moz-column-rule: 1px solid #b32323;
+        column-rule: 1px solid #b32323;
+        -webkit-column-rule: 1px solid lab(40% 56.6 39);
+        -moz-column-rule: 1px solid lab(40% 56.6 39);
+        column-rule: 1px solid lab(40% 56.6 39);
+        -webkit-column-span: all;
+        -moz-column-span: all;
+        column-span: all;
+      }
+    "#
+      },
+      Browsers {
+        chrome: Some(40 << 16),
+        firefox: Some(40 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        -webkit-columns: 10em 3;
+        -moz-columns: 10em 3;
+        columns: 10em 3;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        columns: 10em 3;
+      }
+    "#
+      },
+      Browsers {
+        chrome: Some(100 << 16),
+        firefox: Some(100 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
   #[test]
   pub fn test_outline() {
     test(
@@ -5544,6 +5686,162 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_font_variant() {
+    minify_test(
+      ".foo { font-feature-settings: normal }",
+      ".foo{font-feature-settings:normal}",
+    );
+    minify_test(
+      ".foo { font-feature-settings: 'liga' 1, \"tnum\" on, 'smcp' off, 'swsh' 2 }",
+      ".foo{font-feature-settings:\"liga\",\"tnum\",\"smcp\" 0,\"swsh\" 2}",
+    );
+    minify_test(
+      ".foo { font-feature-settings: 'liga' 0, 'tnum', 'liga' }",
+      ".foo{font-feature-settings:\"tnum\",\"liga\"}",
+    );
+    minify_test(
+      ".foo { font-feature-settings: 'liga' 0; font-feature-settings: 'tnum' }",
+      ".foo{font-feature-settings:\"tnum\"}",
+    );
+    minify_test(
+      ".foo { font-feature-settings: 'liga' -1 }",
+      ".foo{font-feature-settings:\"liga\" -1}",
+    );
+    minify_test(
+      ".foo { font-feature-settings: 'ligatures' }",
+      ".foo{font-feature-settings:\"ligatures\"}",
+    );
+    minify_test(
+      ".foo { font-variation-settings: 'wght' 400, \"wdth\" 75.5, 'wght' 700 }",
+      ".foo{font-variation-settings:\"wdth\" 75.5,\"wght\" 700}",
+    );
+    minify_test(
+      ".foo { font-variation-settings: normal }",
+      ".foo{font-variation-settings:normal}",
+    );
+
+    minify_test(
+      ".foo { font-variant-ligatures: no-common-ligatures contextual }",
+      ".foo{font-variant-ligatures:no-common-ligatures contextual}",
+    );
+    minify_test(
+      ".foo { font-variant-ligatures: none }",
+      ".foo{font-variant-ligatures:none}",
+    );
+    minify_test(
+      ".foo { font-variant-numeric: slashed-zero tabular-nums lining-nums }",
+      ".foo{font-variant-numeric:lining-nums tabular-nums slashed-zero}",
+    );
+    minify_test(
+      ".foo { font-variant-east-asian: ruby full-width jis78 }",
+      ".foo{font-variant-east-asian:jis78 full-width ruby}",
+    );
+    minify_test(
+      ".foo { font-variant-position: super }",
+      ".foo{font-variant-position:super}",
+    );
+    minify_test(
+      ".foo { font-variant-alternates: swash(fancy) historical-forms styleset(a, b) }",
+      ".foo{font-variant-alternates:historical-forms styleset(a,b) swash(fancy)}",
+    );
+    minify_test(".foo { font-kerning: none }", ".foo{font-kerning:none}");
+    minify_test(".foo { font-optical-sizing: auto }", ".foo{font-optical-sizing:auto}");
+    minify_test(
+      ".foo { font-synthesis: style weight }",
+      ".foo{font-synthesis:weight style}",
+    );
+    minify_test(".foo { font-synthesis: none }", ".foo{font-synthesis:none}");
+    minify_test(
+      ".foo { font-synthesis: weight weight }",
+      ".foo{font-synthesis:weight weight}",
+    );
+
+    minify_test(".foo { font-variant: normal }", ".foo{font-variant:normal}");
+    minify_test(".foo { font-variant: none }", ".foo{font-variant:none}");
+    minify_test(
+      ".foo { font-variant: small-caps oldstyle-nums common-ligatures sub }",
+      ".foo{font-variant:common-ligatures small-caps oldstyle-nums sub}",
+    );
+    minify_test(
+      ".foo { font-variant: none small-caps }",
+      ".foo{font-variant:none small-caps}",
+    );
+    minify_test(
+      ".foo { font-variant-ligatures: none; font-variant-caps: small-caps; font-variant-alternates: normal; font-variant-numeric: normal; font-variant-east-asian: normal; font-variant-position: normal }",
+      ".foo{font-variant-caps:small-caps;font-variant-ligatures:none;font-variant-alternates:normal;font-variant-numeric:normal;font-variant-east-asian:normal;font-variant-position:normal}",
+    );
+    minify_test(
+      ".foo { font-variant-ligatures: common-ligatures; font-variant-caps: small-caps; font-variant-alternates: normal; font-variant-numeric: ordinal; font-variant-east-asian: normal; font-variant-position: normal }",
+      ".foo{font-variant:common-ligatures small-caps ordinal}",
+    );
+    minify_test(
+      ".foo { font-variant: small-caps ordinal; font-variant-numeric: normal }",
+      ".foo{font-variant:small-caps}",
+    );
+
+    // The font shorthand resets the variant, kerning, and settings longhands.
+    minify_test(
+      ".foo { font-kerning: none; font-variant-numeric: ordinal; font-feature-settings: 'liga'; font-synthesis: none; font: 12px Helvetica }",
+      ".foo{font:12px Helvetica;font-synthesis:none}",
+    );
+    minify_test(
+      ".foo { font: 12px Helvetica; font-variant: all-small-caps ordinal; font-kerning: none }",
+      ".foo{font:12px Helvetica;font-variant:all-small-caps ordinal;font-kerning:none}",
+    );
+    minify_test(
+      ".foo { font: small-caps 12px Helvetica; font-variant: ordinal }",
+      ".foo{font:12px Helvetica;font-variant:ordinal}",
+    );
+    minify_test(
+      ".foo { font: 12px Helvetica; font-variant-numeric: normal; font-variant-ligatures: none }",
+      ".foo{font:12px Helvetica;font-variant-ligatures:none}",
+    );
+
+    minify_test(
+      ".foo { -webkit-font-feature-settings: 'liga'; font-feature-settings: 'liga' }",
+      ".foo{-webkit-font-feature-settings:\"liga\";font-feature-settings:\"liga\"}",
+    );
+    prefix_test(
+      r#"
+      .foo {
+        font-feature-settings: "liga" 0, "tnum", "liga" 0;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -webkit-font-feature-settings: "tnum", "liga" 0;
+        -moz-font-feature-settings: "tnum", "liga" 0;
+        font-feature-settings: "tnum", "liga" 0;
+      }
+    "#
+      },
+      Browsers {
+        chrome: Some(40 << 16),
+        firefox: Some(30 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      r#"
+      .foo {
+        -webkit-font-feature-settings: "liga";
+        font-feature-settings: "liga";
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        font-feature-settings: "liga";
+      }
+    "#
+      },
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
   #[test]
   fn test_vertical_align() {
     minify_test(".foo { vertical-align: middle }", ".foo{vertical-align:middle}");
@@ -6139,6 +6437,34 @@ mod tests {
       ParserError::UnexpectedToken(Token::Ident("NONE".into())),
     );
 
+    minify_test(
+      r#"
+      @keyframes test {
+        entry 0% {
+          opacity: 0
+        }
+        ENTRY 100%, exit-crossing 50% {
+          opacity: 1
+        }
+      }
+    "#,
+      "@keyframes test{entry 0%{opacity:0}entry 100%,exit-crossing 50%{opacity:1}}",
+    );
+
+    minify_test(
+      r#"
+      @keyframes test {
+        entry {
+          opacity: 0
+        }
+        to {
+          opacity: 1
+        }
+      }
+    "#,
+      "@keyframes test{to{opacity:1}}",
+    );
+
     minify_test(
       r#"
       @-webkit-keyframes test {
@@ -6844,6 +7170,85 @@ mod tests {
     minify_test(".foo { rotate: atan2(-1, 1)", ".foo{rotate:-45deg}");
     // incompatible units
     minify_test(".foo { rotate: atan2(1px, -1vw)", ".foo{rotate:atan2(1px,-1vw)}");
+
+    // unresolved arguments are preserved
+    minify_test(
+      ".foo { background: conic-gradient(red atan2(1px, -1vw), blue) }",
+      ".foo{background:conic-gradient(red atan2(1px,-1vw),#00f)}",
+    );
+    minify_test(
+      ".foo { background: conic-gradient(red calc(atan2(1px, 1vw) + 10deg + 5deg), blue) }",
+      ".foo{background:conic-gradient(red calc(atan2(1px,1vw) + 15deg),#00f)}",
+    );
+    minify_test(
+      ".foo { background: conic-gradient(red asin(sin(atan2(1px, 1vw) + 1deg)), blue) }",
+      ".foo{background:conic-gradient(red asin(sin(atan2(1px,1vw) + 1deg)),#00f)}",
+    );
+    minify_test(
+      ".foo { background: conic-gradient(red atan(pow(sin(atan2(1px, 1vw)), 2)), blue) }",
+      ".foo{background:conic-gradient(red atan(pow(sin(atan2(1px,1vw)),2)),#00f)}",
+    );
+    minify_test(
+      ".foo { width: calc(1px * sin(atan2(1px, 1vw)))",
+      ".foo{width:calc(1px*sin(atan2(1px,1vw)))}",
+    );
+
+    // atan2() accepts any arguments of the same type
+    use crate::values::{
+      angle::Angle,
+      calc::{Atan2Arguments, Calc, MathFunction},
+    };
+    for (source, expected) in [
+      ("atan2(sin(atan2(1px, 1vw)), 1)", "atan2(sin(atan2(1px, 1vw)), 1)"),
+      ("atan2(10%, 1px)", "atan2(10%, 1px)"),
+      ("atan2(calc(1px + 10%), 1px)", "atan2(1px + 10%, 1px)"),
+    ] {
+      let calc = Calc::<Angle>::parse_string(source).unwrap();
+      assert!(matches!(&calc, Calc::Function(f) if matches!(**f, MathFunction::Atan2(_))));
+      assert_eq!(calc.to_css_string(PrinterOptions::default()).unwrap(), expected);
+    }
+    assert!(Calc::<Angle>::parse_string("atan2(1px, 1)").is_err());
+    assert!(Calc::<Angle>::parse_string("atan2(1s, 1px)").is_err());
+
+    // values constructed after parsing are folded when minifying, or when targets don't support them
+    let calc = Calc::<Angle>::Function(Box::new(MathFunction::Atan2(Atan2Arguments::Number(
+      Calc::Number(1.0),
+      Calc::Number(0.0),
+    ))));
+    assert_eq!(calc.to_css_string(PrinterOptions::default()).unwrap(), "atan2(1, 0)");
+    assert_eq!(
+      calc
+        .to_css_string(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap(),
+      "90deg"
+    );
+    assert_eq!(
+      calc
+        .to_css_string(PrinterOptions {
+          targets: Some(Browsers {
+            chrome: Some(100 << 16),
+            ..Browsers::default()
+          }),
+          ..PrinterOptions::default()
+        })
+        .unwrap(),
+      "90deg"
+    );
+    let calc = Calc::<Angle>::Function(Box::new(MathFunction::Asin(Calc::Function(Box::new(
+      MathFunction::Sin(Calc::Value(Box::new(Angle::Deg(30.0)))),
+    )))));
+    assert_eq!(
+      calc
+        .to_css_string(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap(),
+      "30deg"
+    );
   }
 
   #[test]
@@ -7704,26 +8109,227 @@ mod tests {
   }
 
   #[test]
-  fn test_merge_layers() {
-    test(
+  fn test_media_query_evaluation() {
+    use crate::media_query::{Environment, MediaList, MediaType};
+    use cssparser::{Parser, ParserInput};
+
+    fn evaluate(media: &str, environment: &Environment) -> Option<bool> {
+      let mut input = ParserInput::new(media);
+      let mut parser = Parser::new(&mut input);
+      MediaList::parse(&mut parser).unwrap().evaluate(environment)
+    }
+
+    let phone = Environment {
+      media_type: Some(MediaType::Screen),
+      width: Some(390.0),
+      height: Some(844.0),
+      resolution: Some(3.0),
+      color: Some(8),
+      features: HashMap::from([
+        ("prefers-color-scheme".into(), "dark".into()),
+        ("pointer".into(), "coarse".into()),
+        ("hover".into(), "none".into()),
+      ]),
+      ..Environment::default()
+    };
+    assert_eq!(evaluate("", &phone), Some(true));
+    assert_eq!(evaluate("screen", &phone), Some(true));
+    assert_eq!(evaluate("print", &phone), Some(false));
+    assert_eq!(evaluate("not print", &phone), Some(true));
+    assert_eq!(evaluate("print, (max-width: 600px)", &phone), Some(true));
+    assert_eq!(evaluate("screen and (min-width: 768px)", &phone), Some(false));
+    assert_eq!(evaluate("(400px <= width <= 800px)", &phone), Some(false));
+    assert_eq!(evaluate("(width < 30em)", &phone), None);
+    assert_eq!(evaluate("(orientation: portrait)", &phone), Some(true));
+    assert_eq!(evaluate("(aspect-ratio > 1/1)", &phone), Some(false));
+    assert_eq!(evaluate("(min-resolution: 2dppx)", &phone), Some(true));
+    assert_eq!(evaluate("(resolution: 96dpi)", &phone), Some(false));
+    assert_eq!(evaluate("(-webkit-min-device-pixel-ratio: 2)", &phone), Some(true));
+    assert_eq!(evaluate("(color)", &phone), Some(true));
+    assert_eq!(evaluate("(monochrome)", &phone), None);
+    assert_eq!(evaluate("(prefers-color-scheme: dark)", &phone), Some(true));
+    assert_eq!(evaluate("(pointer: fine)", &phone), Some(false));
+    assert_eq!(evaluate("(hover)", &phone), Some(false));
+    assert_eq!(evaluate("not (hover: hover)", &phone), Some(true));
+    assert_eq!(evaluate("(prefers-reduced-motion: reduce)", &phone), None);
+    assert_eq!(
+      evaluate("(prefers-reduced-motion: reduce) or (pointer: coarse)", &phone),
+      Some(true)
+    );
+    assert_eq!(evaluate("(--custom)", &phone), None);
+
+    let unknown = Environment::default();
+    assert_eq!(evaluate("screen", &unknown), None);
+    assert_eq!(evaluate("all and (min-width: 0)", &unknown), Some(true));
+    assert_eq!(evaluate("(width < 0)", &unknown), Some(false));
+
+    let stylesheet = StyleSheet::parse(
       r#"
-      @layer foo {
-        .foo {
-          color: red;
-        }
+      @import "print.css" print;
+      @import "dark.css" (prefers-color-scheme: dark);
+      .foo { color: red }
+      @media (max-width: 600px) {
+        .foo { color: green }
       }
-      @layer foo {
-        .foo {
-          background: #fff;
-        }
-
-        .baz {
-          color: #fff;
+      @media (min-width: 1024px) {
+        .foo { color: blue }
+      }
+      @media (prefers-reduced-motion: reduce) {
+        .foo { transition: none }
+      }
+      @supports (display: grid) {
+        @media (hover: hover) {
+          .foo { display: grid }
         }
       }
       "#,
-      indoc! {r#"
-      @layer foo {
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let res = stylesheet
+      .prune_for_environment(&phone)
+      .to_css(PrinterOptions {
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    assert_eq!(
+      res.code,
+      "@import \"dark.css\";.foo{color:red}.foo{color:green}@media (prefers-reduced-motion:reduce){.foo{transition:none}}"
+    );
+  }
+
+  #[test]
+  fn test_media_simplification() {
+    minify_test(
+      "@media (min-width: 500px) and (min-width: 600px) { .foo { color: red }}",
+      "@media (width>=600px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (max-width: 800px) and (min-width: 500px) and (min-width: 600px) { .foo { color: red }}",
+      "@media (width<=800px) and (width>=600px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (400px <= width <= 800px) and (min-width: 500px) and (max-width: 900px) { .foo { color: red }}",
+      "@media (400px<=width<=800px) and (width>=500px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (width: 500px) and (min-width: 400px) { .foo { color: red }}",
+      "@media (width:500px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (min-width: 1in) and (min-width: 90px) { .foo { color: red }}",
+      "@media (width>=1in){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (min-width: 10em) and (min-width: 100px) { .foo { color: red }}",
+      "@media (width>=10em) and (width>=100px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media screen and (min-width: 0) { .foo { color: red }}",
+      "@media screen{.foo{color:red}}",
+    );
+    minify_test("@media (min-width: 0) { .foo { color: red }}", ".foo{color:red}");
+    minify_test(
+      "@media (min-width: 0) and (hover) { .foo { color: red }}",
+      "@media (hover){.foo{color:red}}",
+    );
+
+    // Contradictions.
+    minify_test("@media (width >= 10em) and (width < 5em) { .foo { color: red }}", "");
+    minify_test("@media (width: 500px) and (width: 600px) { .foo { color: red }}", "");
+    minify_test(
+      "@media (max-width: 500px) and (min-width: 500.5px) { .foo { color: red }}",
+      "",
+    );
+    minify_test("@media (width < 0) { .foo { color: red }}", "");
+    minify_test("@media not all and (min-width: 0) { .foo { color: red }}", "");
+    minify_test(
+      "@media screen and (width < 10em) and (width > 20em) { .foo { color: red }}",
+      "",
+    );
+    minify_test(
+      "@media not screen and (width < 10em) and (width > 20em) { .foo { color: red }}",
+      ".foo{color:red}",
+    );
+    minify_test(
+      "@media (width < 0) or (hover) { .foo { color: red }}",
+      "@media (hover){.foo{color:red}}",
+    );
+    minify_test("@media not ((width < 0) or (min-width: 0)) { .foo { color: red }}", "");
+    minify_test(
+      "@media (max-width: 100px) and (min-width: 200px), print { .foo { color: red }}",
+      "@media print{.foo{color:red}}",
+    );
+    minify_test(
+      "@media (max-width: 500px) and (min-width: 10em) { .foo { color: red }}",
+      "@media (width<=500px) and (width>=10em){.foo{color:red}}",
+    );
+
+    // Subsumed queries.
+    minify_test(
+      "@media (min-width: 500px), (min-width: 600px) { .foo { color: red }}",
+      "@media (width>=500px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media screen and (min-width: 600px), (min-width: 500px) { .foo { color: red }}",
+      "@media (width>=500px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (min-width: 500px), screen and (min-width: 600px) { .foo { color: red }}",
+      "@media (width>=500px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media screen and (min-width: 500px), print and (min-width: 600px) { .foo { color: red }}",
+      "@media screen and (width>=500px),print and (width>=600px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (hover), (hover) and (min-width: 500px) { .foo { color: red }}",
+      "@media (hover){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (min-width: 100px) and (max-width: 200px), (50px < width < 300px) { .foo { color: red }}",
+      "@media (50px<width<300px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (hover), (min-width: 500px) { .foo { color: red }}",
+      "@media (hover),(width>=500px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media print, print { .foo { color: red }}",
+      "@media print{.foo{color:red}}",
+    );
+    minify_test(
+      "@media not print, not print and (min-width: 500px) { .foo { color: red }}",
+      "@media not print,not print and (width>=500px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (min-width: 500px), all { .foo { color: red }}",
+      ".foo{color:red}",
+    );
+  }
+
+  #[test]
+  fn test_merge_layers() {
+    test(
+      r#"
+      @layer foo {
+        .foo {
+          color: red;
+        }
+      }
+      @layer foo {
+        .foo {
+          background: #fff;
+        }
+
+        .baz {
+          color: #fff;
+        }
+      }
+      "#,
+      indoc! {r#"
+      @layer foo {
         .foo {
           color: red;
           background: #fff;
@@ -9674,6 +10280,138 @@ mod tests {
         ..Browsers::default()
       },
     );
+
+    minify_test(".foo { animation-timeline: auto }", ".foo{animation-timeline:auto}");
+    minify_test(".foo { animation-timeline: --foo }", ".foo{animation-timeline:--foo}");
+    minify_test(
+      ".foo { animation-timeline: scroll() }",
+      ".foo{animation-timeline:scroll()}",
+    );
+    minify_test(
+      ".foo { animation-timeline: scroll(nearest block) }",
+      ".foo{animation-timeline:scroll()}",
+    );
+    minify_test(
+      ".foo { animation-timeline: scroll(inline root) }",
+      ".foo{animation-timeline:scroll(root inline)}",
+    );
+    minify_test(
+      ".foo { animation-timeline: scroll(self x) }",
+      ".foo{animation-timeline:scroll(self x)}",
+    );
+    minify_test(".foo { animation-timeline: view() }", ".foo{animation-timeline:view()}");
+    minify_test(
+      ".foo { animation-timeline: view(block auto auto) }",
+      ".foo{animation-timeline:view()}",
+    );
+    minify_test(
+      ".foo { animation-timeline: view(20px 10% inline) }",
+      ".foo{animation-timeline:view(inline 20px 10%)}",
+    );
+    minify_test(
+      ".foo { animation-timeline: view(x 20px 20px) }",
+      ".foo{animation-timeline:view(x 20px)}",
+    );
+    minify_test(
+      ".foo { animation-timeline: --foo, scroll(), none }",
+      ".foo{animation-timeline:--foo,scroll(),none}",
+    );
+    minify_test(
+      ".foo { animation-composition: add, accumulate }",
+      ".foo{animation-composition:add,accumulate}",
+    );
+
+    minify_test(
+      ".foo { animation-range-start: normal }",
+      ".foo{animation-range-start:normal}",
+    );
+    minify_test(
+      ".foo { animation-range-start: entry 0% }",
+      ".foo{animation-range-start:entry}",
+    );
+    minify_test(
+      ".foo { animation-range-start: entry 20% }",
+      ".foo{animation-range-start:entry 20%}",
+    );
+    minify_test(
+      ".foo { animation-range-end: exit 100% }",
+      ".foo{animation-range-end:exit}",
+    );
+    minify_test(".foo { animation-range-end: 50px }", ".foo{animation-range-end:50px}");
+    minify_test(".foo { animation-range: entry }", ".foo{animation-range:entry}");
+    minify_test(
+      ".foo { animation-range: entry 0% entry 100% }",
+      ".foo{animation-range:entry}",
+    );
+    minify_test(
+      ".foo { animation-range: entry 10% exit 90% }",
+      ".foo{animation-range:entry 10% exit 90%}",
+    );
+    minify_test(
+      ".foo { animation-range: normal normal }",
+      ".foo{animation-range:normal}",
+    );
+    minify_test(".foo { animation-range: 10% }", ".foo{animation-range:10%}");
+    minify_test(
+      ".foo { animation-range: entry-crossing 20%, contain }",
+      ".foo{animation-range:entry-crossing 20%,contain}",
+    );
+    minify_test(
+      ".foo { animation-range-start: entry 10%; animation-range-end: exit 90% }",
+      ".foo{animation-range:entry 10% exit 90%}",
+    );
+    minify_test(
+      ".foo { animation-range-start: entry 10%, contain; animation-range-end: exit 90% }",
+      ".foo{animation-range-start:entry 10%,contain;animation-range-end:exit 90%}",
+    );
+
+    minify_test(".foo { scroll-timeline: --foo }", ".foo{scroll-timeline:--foo}");
+    minify_test(".foo { scroll-timeline: --foo block }", ".foo{scroll-timeline:--foo}");
+    minify_test(
+      ".foo { scroll-timeline: --foo inline, none x }",
+      ".foo{scroll-timeline:--foo inline,none x}",
+    );
+    minify_test(
+      ".foo { scroll-timeline-axis: y, block }",
+      ".foo{scroll-timeline-axis:y,block}",
+    );
+    minify_test(".foo { view-timeline: --foo }", ".foo{view-timeline:--foo}");
+    minify_test(
+      ".foo { view-timeline: --foo 10px 20% inline }",
+      ".foo{view-timeline:--foo inline 10px 20%}",
+    );
+    minify_test(
+      ".foo { view-timeline-inset: auto, 10px auto }",
+      ".foo{view-timeline-inset:auto,10px auto}",
+    );
+    minify_test(".foo { timeline-scope: none }", ".foo{timeline-scope:none}");
+    minify_test(".foo { timeline-scope: all }", ".foo{timeline-scope:all}");
+    minify_test(
+      ".foo { timeline-scope: --foo, --bar }",
+      ".foo{timeline-scope:--foo,--bar}",
+    );
+
+    // The animation shorthand resets the scroll-driven longhands, so they must stay after it.
+    minify_test(
+      ".foo { animation: foo 1s linear; animation-timeline: --foo; animation-range: entry exit }",
+      ".foo{animation:1s linear foo;animation-timeline:--foo;animation-range:entry exit}",
+    );
+    minify_test(
+      ".foo { animation-timeline: --foo; animation-range-start: entry; animation: foo 1s linear }",
+      ".foo{animation:1s linear foo}",
+    );
+    minify_test(
+      ".foo { animation-timeline: --foo; animation-name: foo; animation-composition: add }",
+      ".foo{animation-name:foo;animation-composition:add;animation-timeline:--foo}",
+    );
+    minify_test(
+      ".foo { animation-timeline: --foo; -webkit-animation: foo 1s linear }",
+      ".foo{-webkit-animation:1s linear foo;animation-timeline:--foo}",
+    );
+    minify_test(
+      ".foo { animation: foo 1s linear; animation-timeline: var(--foo) }",
+      ".foo{animation:1s linear foo;animation-timeline:var(--foo)}",
+    );
   }
 
   #[test]
@@ -10780,6 +11518,136 @@ mod tests {
         ..Browsers::default()
       },
     );
+
+    minify_test(
+      ".foo { background: linear-gradient(in oklch, red, blue) }",
+      ".foo{background:linear-gradient(in oklch,red,#00f)}",
+    );
+    minify_test(
+      ".foo { background: linear-gradient(to right in oklch longer hue, red, blue) }",
+      ".foo{background:linear-gradient(90deg in oklch longer hue,red,#00f)}",
+    );
+    minify_test(
+      ".foo { background: linear-gradient(in hsl shorter hue to right, red, blue) }",
+      ".foo{background:linear-gradient(90deg in hsl,red,#00f)}",
+    );
+    minify_test(
+      ".foo { background: linear-gradient(in lab 180deg, red, blue) }",
+      ".foo{background:linear-gradient(in lab,red,#00f)}",
+    );
+    minify_test(
+      ".foo { background: linear-gradient(to top in oklab, red 20%, blue) }",
+      ".foo{background:linear-gradient(in oklab,#00f,red 80%)}",
+    );
+    minify_test(
+      ".foo { background: linear-gradient(to top in hsl increasing hue, red 20%, blue) }",
+      ".foo{background:linear-gradient(0deg in hsl increasing hue,red 20%,#00f)}",
+    );
+    minify_test(
+      ".foo { background: radial-gradient(circle at top in srgb-linear, red, blue) }",
+      ".foo{background:radial-gradient(circle at top in srgb-linear,red,#00f)}",
+    );
+    minify_test(
+      ".foo { background: radial-gradient(in lch decreasing hue, red, blue) }",
+      ".foo{background:radial-gradient(in lch decreasing hue,red,#00f)}",
+    );
+    minify_test(
+      ".foo { background: conic-gradient(from 45deg in oklch, red, blue) }",
+      ".foo{background:conic-gradient(from 45deg in oklch,red,#00f)}",
+    );
+    minify_test(
+      ".foo { background: conic-gradient(in hwb, red, blue) }",
+      ".foo{background:conic-gradient(in hwb,red,#00f)}",
+    );
+    minify_test(
+      ".foo { background: -webkit-linear-gradient(in oklch, red, blue) }",
+      ".foo{background:-webkit-linear-gradient(in oklch,red,blue)}",
+    );
+
+    prefix_test(
+      ".foo { background: linear-gradient(in srgb, red, blue) }",
+      indoc! { r#"
+        .foo {
+          background: linear-gradient(red, #00f);
+        }
+      "#},
+      Browsers {
+        chrome: Some(100 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { background: linear-gradient(to right in oklch, red, blue 50%, lime) }",
+      indoc! { r#"
+        .foo {
+          background: linear-gradient(to right, red 0%, #f10054 6.25%, #e3007b 12.5%, #ca009b 18.75%, #b600bd 25%, #9900d7 31.25%, #7800eb 37.5%, #5100fa 43.75%, #00f 50%, #0066b8 56.25%, #007eb5 62.5%, #0092b0 68.75%, #00a6b1 75%, #00bbb1 81.25%, #00d0a9 87.5%, #00e88d 93.75%, #0f0 100%);
+        }
+      "#},
+      Browsers {
+        chrome: Some(100 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { background: conic-gradient(from 90deg in hsl, red 50%, lime) }",
+      indoc! { r#"
+        .foo {
+          background: conic-gradient(from 90deg, red 50%, #ff4000 56.25%, #ff8000 62.5%, #ffbf00 68.75%, #ff0 75%, #bfff00 81.25%, #80ff00 87.5%, #40ff00 93.75%, #0f0 100%);
+        }
+      "#},
+      Browsers {
+        chrome: Some(100 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { background: linear-gradient(in oklch, red 10px, blue) }",
+      indoc! { r#"
+        .foo {
+          background: linear-gradient(red 10px, #00f);
+        }
+      "#},
+      Browsers {
+        chrome: Some(100 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { background: radial-gradient(in oklab, red, 30%, currentColor) }",
+      indoc! { r#"
+        .foo {
+          background: radial-gradient(red, 30%, currentColor);
+        }
+      "#},
+      Browsers {
+        chrome: Some(100 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { background: linear-gradient(in hsl longer hue, red, blue calc(10% + 10px)) }",
+      indoc! { r#"
+        .foo {
+          background: linear-gradient(red, #00f calc(10% + 10px));
+        }
+      "#},
+      Browsers {
+        chrome: Some(100 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { background: linear-gradient(in oklch, red, blue) }",
+      indoc! { r#"
+        .foo {
+          background: linear-gradient(in oklch, red, #00f);
+        }
+      "#},
+      Browsers {
+        chrome: Some(111 << 16),
+        ..Browsers::default()
+      },
+    );
   }
 
   #[test]
@@ -11018,30 +11886,89 @@ mod tests {
   }
 
   #[test]
-  fn test_page_rule() {
-    minify_test("@page {margin: 0.5cm}", "@page{margin:.5cm}");
-    minify_test("@page :left {margin: 0.5cm}", "@page:left{margin:.5cm}");
-    minify_test("@page :right {margin: 0.5cm}", "@page:right{margin:.5cm}");
+  fn test_font_feature_values() {
     minify_test(
-      "@page LandscapeTable {margin: 0.5cm}",
-      "@page LandscapeTable{margin:.5cm}",
+      "@font-feature-values Font One { @styleset { nice-style: 12; } }",
+      "@font-feature-values Font One{@styleset{nice-style:12}}",
     );
     minify_test(
-      "@page CompanyLetterHead:first {margin: 0.5cm}",
-      "@page CompanyLetterHead:first{margin:.5cm}",
+      "@font-feature-values Font One, 'Font Two' { @swash { fancy: 1 } @character-variant { alt-g: 1 2; } }",
+      "@font-feature-values Font One,Font Two{@swash{fancy:1}@character-variant{alt-g:1 2}}",
     );
-    minify_test("@page:first {margin: 0.5cm}", "@page:first{margin:.5cm}");
-    minify_test("@page :blank:first {margin: 0.5cm}", "@page:blank:first{margin:.5cm}");
-    minify_test("@page toc, index {margin: 0.5cm}", "@page toc,index{margin:.5cm}");
     minify_test(
-      r#"
-    @page :right {
-      @bottom-left {
-        margin: 10pt;
-      }
-    }
-    "#,
-      "@page:right{@bottom-left{margin:10pt}}",
+      "@font-feature-values Font One, Font One { @styleset { nice-style: 12 } @styleset { other: 1 2 3; nice-style: 4 } }",
+      "@font-feature-values Font One{@styleset{nice-style:4;other:1 2 3}}",
+    );
+    minify_test(
+      "@font-feature-values Font One { @styleset { a: 1 } } .foo { color: red } @font-feature-values Font One { @swash { b: 2 } @styleset { a: 3 } }",
+      "@font-feature-values Font One{@styleset{a:3}@swash{b:2}}.foo{color:red}",
+    );
+    minify_test(
+      "@font-feature-values Font One { @swash { a: 1 2; b: -1; c: foo } @annotation { d: 1 } @unknown { e: 1 } }",
+      "@font-feature-values Font One{@annotation{d:1}}",
+    );
+    minify_test("@font-feature-values Font One { @swash { a: 1 2 } }", "");
+    minify_test(
+      "@font-feature-values \"Font One\" { @ornaments { a: 1 } @stylistic { b: 2 } }",
+      "@font-feature-values Font One{@ornaments{a:1}@stylistic{b:2}}",
+    );
+    test(
+      r#"
+      @font-feature-values Font One, Font Two {
+        @styleset {
+          nice-style: 12;
+          other: 1 2;
+        }
+        @swash {
+          fancy: 1;
+        }
+      }
+    "#,
+      indoc! {r#"
+      @font-feature-values Font One, Font Two {
+        @styleset {
+          nice-style: 12;
+          other: 1 2;
+        }
+
+        @swash {
+          fancy: 1;
+        }
+      }
+    "#},
+    );
+
+    error_test(
+      "@font-feature-values sans-serif { @swash { a: 1 } }",
+      ParserError::InvalidValue,
+    );
+  }
+
+  #[test]
+  fn test_page_rule() {
+    minify_test("@page {margin: 0.5cm}", "@page{margin:.5cm}");
+    minify_test("@page :left {margin: 0.5cm}", "@page:left{margin:.5cm}");
+    minify_test("@page :right {margin: 0.5cm}", "@page:right{margin:.5cm}");
+    minify_test(
+      "@page LandscapeTable {margin: 0.5cm}",
+      "@page LandscapeTable{margin:.5cm}",
+    );
+    minify_test(
+      "@page CompanyLetterHead:first {margin: 0.5cm}",
+      "@page CompanyLetterHead:first{margin:.5cm}",
+    );
+    minify_test("@page:first {margin: 0.5cm}", "@page:first{margin:.5cm}");
+    minify_test("@page :blank:first {margin: 0.5cm}", "@page:blank:first{margin:.5cm}");
+    minify_test("@page toc, index {margin: 0.5cm}", "@page toc,index{margin:.5cm}");
+    minify_test(
+      r#"
+    @page :right {
+      @bottom-left {
+        margin: 10pt;
+      }
+    }
+    "#,
+      "@page:right{@bottom-left{margin:10pt}}",
     );
     minify_test(
       r#"
@@ -13438,6 +14365,156 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_anchor_positioning() {
+    minify_test(".foo { anchor-name: none }", ".foo{anchor-name:none}");
+    minify_test(".foo { anchor-name: --foo }", ".foo{anchor-name:--foo}");
+    minify_test(".foo { anchor-name: --foo, --bar }", ".foo{anchor-name:--foo,--bar}");
+    minify_test(".foo { anchor-name: foo }", ".foo{anchor-name:foo}");
+    minify_test(".foo { position-anchor: auto }", ".foo{position-anchor:auto}");
+    minify_test(".foo { position-anchor: --foo }", ".foo{position-anchor:--foo}");
+
+    minify_test(".foo { position-area: none }", ".foo{position-area:none}");
+    minify_test(".foo { position-area: top }", ".foo{position-area:top}");
+    minify_test(".foo { position-area: top span-all }", ".foo{position-area:top}");
+    minify_test(".foo { position-area: top left }", ".foo{position-area:top left}");
+    minify_test(
+      ".foo { position-area: span-top span-x-end }",
+      ".foo{position-area:span-top span-x-end}",
+    );
+    minify_test(".foo { position-area: start start }", ".foo{position-area:start}");
+    minify_test(
+      ".foo { position-area: start span-all }",
+      ".foo{position-area:start span-all}",
+    );
+    minify_test(".foo { position-area: center }", ".foo{position-area:center}");
+    minify_test(
+      ".foo { inset-area: block-end span-inline-start }",
+      ".foo{inset-area:block-end span-inline-start}",
+    );
+
+    minify_test(".foo { top: anchor(bottom) }", ".foo{top:anchor(bottom)}");
+    minify_test(".foo { top: anchor(--foo bottom) }", ".foo{top:anchor(--foo bottom)}");
+    minify_test(".foo { top: anchor(bottom --foo) }", ".foo{top:anchor(--foo bottom)}");
+    minify_test(".foo { left: anchor(--foo 50%) }", ".foo{left:anchor(--foo 50%)}");
+    minify_test(
+      ".foo { left: anchor(self-start, 10px) }",
+      ".foo{left:anchor(self-start,10px)}",
+    );
+    minify_test(
+      ".foo { left: anchor(--foo end, calc(10px + 10px)) }",
+      ".foo{left:anchor(--foo end,20px)}",
+    );
+    minify_test(
+      ".foo { top: calc(anchor(bottom) + 10px) }",
+      ".foo{top:calc(anchor(bottom) + 10px)}",
+    );
+    minify_test(
+      ".foo { inset-block-start: anchor(--foo end) }",
+      ".foo{inset-block-start:anchor(--foo end)}",
+    );
+    minify_test(
+      ".foo { top: anchor(top); bottom: anchor(bottom); left: anchor(left); right: anchor(right) }",
+      ".foo{inset:anchor(top) anchor(right) anchor(bottom) anchor(left)}",
+    );
+    minify_test(".foo { width: anchor-size(width) }", ".foo{width:anchor-size(width)}");
+    minify_test(".foo { width: anchor-size() }", ".foo{width:anchor-size()}");
+    minify_test(".foo { width: anchor-size(--foo) }", ".foo{width:anchor-size(--foo)}");
+    minify_test(
+      ".foo { width: anchor-size(self-inline --foo, 50px) }",
+      ".foo{width:anchor-size(--foo self-inline,50px)}",
+    );
+    minify_test(".foo { width: anchor-size(50%) }", ".foo{width:anchor-size(50%)}");
+    minify_test(
+      ".foo { max-height: calc(anchor-size(height) * 2) }",
+      ".foo{max-height:calc(2*anchor-size(height))}",
+    );
+
+    // anchor() resolves to a length, so it is invalid in other types of values.
+    minify_test(".foo { top: anchor(foo) }", ".foo{top:anchor(foo)}");
+    minify_test(".foo { rotate: anchor(top) }", ".foo{rotate:anchor(top)}");
+    minify_test(".foo { opacity: calc(anchor(top)) }", ".foo{opacity:calc(anchor(top))}");
+
+    prefix_test(
+      ".foo { top: anchor(bottom); inset-inline-start: anchor(--foo start) }",
+      indoc! {r#"
+      .foo {
+        top: anchor(bottom);
+        inset-inline-start: anchor(--foo start);
+      }
+    "#},
+      Browsers {
+        chrome: Some(125 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    css_modules_test(
+      r#"
+      .foo {
+        anchor-name: --anchor;
+      }
+
+      .bar {
+        position-anchor: --anchor;
+        top: anchor(--anchor bottom);
+        width: anchor-size(--other width);
+      }
+    "#,
+      indoc! {r#"
+      .EgL3uq_foo {
+        anchor-name: --EgL3uq_anchor;
+      }
+
+      .EgL3uq_bar {
+        position-anchor: --EgL3uq_anchor;
+        width: anchor-size(--EgL3uq_other width);
+        top: anchor(--EgL3uq_anchor bottom);
+      }
+    "#},
+      map! {
+        "foo" => "EgL3uq_foo",
+        "bar" => "EgL3uq_bar",
+        "--anchor" => "--EgL3uq_anchor" referenced: true,
+        "--other" => "--EgL3uq_other" referenced: true
+      },
+      HashMap::new(),
+      crate::css_modules::Config {
+        dashed_idents: true,
+        ..Default::default()
+      },
+    );
+
+    css_modules_test(
+      r#"
+      .foo {
+        top: anchor(--anchor from "./a.css" bottom);
+        width: anchor-size(--other from global width);
+      }
+    "#,
+      indoc! {r#"
+      .EgL3uq_foo {
+        width: anchor-size(--other width);
+        top: anchor(--sN6i3q bottom);
+      }
+    "#},
+      map! {
+        "foo" => "EgL3uq_foo"
+      },
+      HashMap::from([(
+        "--sN6i3q".into(),
+        CssModuleReference::Dependency {
+          name: "--anchor".into(),
+          specifier: "./a.css".into(),
+        },
+      )]),
+      crate::css_modules::Config {
+        dashed_idents: true,
+        ..Default::default()
+      },
+    );
+  }
+
   #[test]
   fn test_overflow() {
     minify_test(".foo { overflow: hidden }", ".foo{overflow:hidden}");
@@ -13575,6 +14652,141 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_scroll() {
+    minify_test(".foo { scroll-snap-type: none }", ".foo{scroll-snap-type:none}");
+    minify_test(".foo { scroll-snap-type: x }", ".foo{scroll-snap-type:x}");
+    minify_test(".foo { scroll-snap-type: y proximity }", ".foo{scroll-snap-type:y}");
+    minify_test(
+      ".foo { scroll-snap-type: both mandatory }",
+      ".foo{scroll-snap-type:both mandatory}",
+    );
+    minify_test(
+      ".foo { scroll-snap-type: mandatory }",
+      ".foo{scroll-snap-type:mandatory}",
+    );
+    minify_test(".foo { scroll-snap-align: start }", ".foo{scroll-snap-align:start}");
+    minify_test(
+      ".foo { scroll-snap-align: center center }",
+      ".foo{scroll-snap-align:center}",
+    );
+    minify_test(
+      ".foo { scroll-snap-align: none end }",
+      ".foo{scroll-snap-align:none end}",
+    );
+    minify_test(".foo { scroll-snap-stop: always }", ".foo{scroll-snap-stop:always}");
+    minify_test(".foo { scroll-behavior: smooth }", ".foo{scroll-behavior:smooth}");
+    minify_test(".foo { scrollbar-gutter: auto }", ".foo{scrollbar-gutter:auto}");
+    minify_test(
+      ".foo { scrollbar-gutter: both-edges stable }",
+      ".foo{scrollbar-gutter:stable both-edges}",
+    );
+    minify_test(".foo { scrollbar-gutter: stable }", ".foo{scrollbar-gutter:stable}");
+
+    minify_test(
+      ".foo { overscroll-behavior: contain }",
+      ".foo{overscroll-behavior:contain}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior: none auto }",
+      ".foo{overscroll-behavior:none auto}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior: none none }",
+      ".foo{overscroll-behavior:none}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior-x: contain; overscroll-behavior-y: none }",
+      ".foo{overscroll-behavior:contain none}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior: contain; overscroll-behavior-y: auto }",
+      ".foo{overscroll-behavior:contain auto}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior-block: contain; overscroll-behavior-inline: none }",
+      ".foo{overscroll-behavior-block:contain;overscroll-behavior-inline:none}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior: none; overscroll-behavior-block: contain }",
+      ".foo{overscroll-behavior:none;overscroll-behavior-block:contain}",
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        overscroll-behavior-block: contain;
+        overscroll-behavior-inline: none;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        overscroll-behavior: none contain;
+        overscroll-behavior-block: contain;
+        overscroll-behavior-inline: none;
+      }
+    "#},
+      Browsers {
+        safari: Some(15 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        overscroll-behavior: none;
+        overscroll-behavior-inline: contain;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        overscroll-behavior: contain none;
+        overscroll-behavior-inline: contain;
+      }
+    "#},
+      Browsers {
+        safari: Some(15 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        overscroll-behavior-block: var(--value);
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        overscroll-behavior-y: var(--value);
+        overscroll-behavior-block: var(--value);
+      }
+    "#},
+      Browsers {
+        safari: Some(15 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        overscroll-behavior-block: contain;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        overscroll-behavior-block: contain;
+      }
+    "#},
+      Browsers {
+        safari: Some(16 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
   #[test]
   fn test_ui() {
     minify_test(".foo { resize: both }", ".foo{resize:both}");
@@ -13926,6 +15138,81 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_content() {
+    minify_test(".foo { content: normal }", ".foo{content:normal}");
+    minify_test(".foo { content: none }", ".foo{content:none}");
+    minify_test(".foo { content: \"foo\" }", ".foo{content:\"foo\"}");
+    minify_test(".foo { content: 'foo' \"bar\" }", ".foo{content:\"foo\" \"bar\"}");
+    minify_test(
+      ".foo { content: open-quote \"foo\" close-quote }",
+      ".foo{content:open-quote \"foo\" close-quote}",
+    );
+    minify_test(".foo { content: no-open-quote }", ".foo{content:no-open-quote}");
+    minify_test(".foo { content: counter(foo) }", ".foo{content:counter(foo)}");
+    minify_test(".foo { content: counter(foo, decimal) }", ".foo{content:counter(foo)}");
+    minify_test(
+      ".foo { content: counter(foo, upper-roman) }",
+      ".foo{content:counter(foo,upper-roman)}",
+    );
+    minify_test(
+      ".foo { content: counters(foo, \".\") }",
+      ".foo{content:counters(foo,\".\")}",
+    );
+    minify_test(
+      ".foo { content: counters(foo, \".\", lower-alpha) \") \" }",
+      ".foo{content:counters(foo,\".\",lower-alpha) \") \"}",
+    );
+    minify_test(".foo { content: attr(data-foo) }", ".foo{content:attr(data-foo)}");
+    minify_test(
+      ".foo { content: \"(\" attr(title) \")\" }",
+      ".foo{content:\"(\" attr(title) \")\"}",
+    );
+    minify_test(".foo { content: url(foo.png) }", ".foo{content:url(foo.png)}");
+    minify_test(
+      ".foo { content: url(foo.png) / \"Foo\" }",
+      ".foo{content:url(foo.png)/\"Foo\"}",
+    );
+    minify_test(
+      ".foo { content: linear-gradient(red, blue) / \"Item \" counter(foo) }",
+      ".foo{content:linear-gradient(red,#00f)/\"Item \" counter(foo)}",
+    );
+    minify_test(
+      ".foo { content: \"foo\" / url(foo.png) }",
+      ".foo{content:\"foo\"/url(foo.png)}",
+    );
+    minify_test(
+      ".foo { content: attr(data-foo type(<color>)) }",
+      ".foo{content:attr(data-foo type(<color>))}",
+    );
+
+    minify_test(".foo { quotes: auto }", ".foo{quotes:auto}");
+    minify_test(".foo { quotes: none }", ".foo{quotes:none}");
+    minify_test(
+      ".foo { quotes: \"«\" \"»\" \"‹\" \"›\" }",
+      ".foo{quotes:\"«\"\"»\"\"‹\"\"›\"}",
+    );
+    minify_test(".foo { quotes: \"«\" }", ".foo{quotes:\"«\"}");
+
+    minify_test(".foo { counter-reset: none }", ".foo{counter-reset:none}");
+    minify_test(".foo { counter-reset: foo }", ".foo{counter-reset:foo}");
+    minify_test(".foo { counter-reset: foo 2 bar }", ".foo{counter-reset:foo 2 bar}");
+    minify_test(
+      ".foo { counter-reset: reversed(foo) -1 }",
+      ".foo{counter-reset:reversed(foo) -1}",
+    );
+    minify_test(".foo { counter-reset: list-item 5 }", ".foo{counter-reset:list-item 5}");
+    minify_test(
+      ".foo { counter-increment: foo 2 bar -1 }",
+      ".foo{counter-increment:foo 2 bar -1}",
+    );
+    minify_test(".foo { counter-set: foo 0 }", ".foo{counter-set:foo 0}");
+    minify_test(
+      ".foo { counter-increment: reversed(foo) }",
+      ".foo{counter-increment:reversed(foo)}",
+    );
+  }
+
   #[test]
   fn test_image_set() {
     // Spec: https://drafts.csswg.org/css-images-4/#image-set-notation
@@ -18125,11 +19412,147 @@ mod tests {
     }
   }
 
-  #[cfg(feature = "grid")]
   #[test]
-  fn test_grid() {
+  fn test_light_dark() {
     minify_test(
-      ".foo { grid-template-columns: [first nav-start]  150px [main-start] 1fr [last]; }",
+      ".foo { color: light-dark(black, white); }",
+      ".foo{color:light-dark(#000,#fff)}",
+    );
+    minify_test(
+      ".foo { color: light-dark(rgb(0 0 0), rgba(255 255 255 / .5)); }",
+      ".foo{color:light-dark(#000,#ffffff80)}",
+    );
+    minify_test(
+      ".foo { color: color-mix(in srgb, light-dark(black, white), red); }",
+      ".foo{color:light-dark(maroon,#ff8080)}",
+    );
+    minify_test(
+      ".foo { color: rgb(from light-dark(black, white) r g b); }",
+      ".foo{color:rgb(from light-dark(#000,#fff) r g b)}",
+    );
+    minify_test(".foo { color-scheme: normal; }", ".foo{color-scheme:normal}");
+    minify_test(".foo { color-scheme: dark light; }", ".foo{color-scheme:light dark}");
+    minify_test(".foo { color-scheme: only light; }", ".foo{color-scheme:light only}");
+    minify_test(".foo { color-scheme: light only; }", ".foo{color-scheme:light only}");
+    minify_test(".foo { color-scheme: light foo; }", ".foo{color-scheme:light foo}");
+
+    prefix_test(
+      r#"
+      .foo {
+        color-scheme: light dark;
+        color: light-dark(black, white);
+      }
+    "#,
+      indoc! { r#"
+      .foo {
+        --lightningcss-light: initial;
+        --lightningcss-dark: ;
+        color-scheme: light dark;
+        color: var(--lightningcss-light, #000) var(--lightningcss-dark, #fff);
+      }
+
+      @media (prefers-color-scheme: dark) {
+        .foo {
+          --lightningcss-light: ;
+          --lightningcss-dark: initial;
+        }
+      }
+    "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      r#"
+      .foo {
+        color-scheme: dark;
+        background: light-dark(lab(40% 56.6 39), var(--bg));
+      }
+    "#,
+      indoc! { r#"
+      .foo {
+        --lightningcss-light: ;
+        --lightningcss-dark: initial;
+        color-scheme: dark;
+        background: var(--lightningcss-light, #b32323) var(--lightningcss-dark, var(--bg));
+      }
+
+      @supports (color: lab(0% 0 0)) {
+        .foo {
+          background: var(--lightningcss-light, lab(40% 56.6 39)) var(--lightningcss-dark, var(--bg));
+        }
+      }
+    "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      r#"
+      .foo {
+        color-scheme: light dark;
+        color: light-dark(lab(40% 56.6 39), #fff);
+      }
+    "#,
+      indoc! { r#"
+      .foo {
+        --lightningcss-light: initial;
+        --lightningcss-dark: ;
+        color-scheme: light dark;
+        color: var(--lightningcss-light, color(display-p3 .643308 .192455 .167712)) var(--lightningcss-dark, #fff);
+        color: var(--lightningcss-light, lab(40% 56.6 39)) var(--lightningcss-dark, #fff);
+      }
+
+      @media (prefers-color-scheme: dark) {
+        .foo {
+          --lightningcss-light: ;
+          --lightningcss-dark: initial;
+        }
+      }
+    "#},
+      Browsers {
+        safari: Some(14 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      r#"
+      .foo {
+        color-scheme: light dark;
+        color: light-dark(black, white);
+      }
+    "#,
+      indoc! { r#"
+      .foo {
+        color-scheme: light dark;
+        color: light-dark(#000, #fff);
+      }
+    "#},
+      Browsers {
+        chrome: Some(123 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    // Style attributes have no rules to define the custom properties that light-dark() is lowered to.
+    attr_test(
+      "color-scheme: light dark; color: light-dark(black, white); background: light-dark(red, var(--bg))",
+      "color-scheme:light dark;color:light-dark(#000,#fff);background:light-dark(red,var(--bg))",
+      true,
+      Some(Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      }),
+    );
+  }
+
+  #[cfg(feature = "grid")]
+  #[test]
+  fn test_grid() {
+    minify_test(
+      ".foo { grid-template-columns: [first nav-start]  150px [main-start] 1fr [last]; }",
       ".foo{grid-template-columns:[first nav-start]150px[main-start]1fr[last]}",
     );
     minify_test(
@@ -18905,6 +20328,113 @@ mod tests {
         }
       "#},
     );
+
+    minify_test(
+      ".foo { grid-template-columns: subgrid; }",
+      ".foo{grid-template-columns:subgrid}",
+    );
+    minify_test(
+      ".foo { grid-template-columns: subgrid [a] [b c]; }",
+      ".foo{grid-template-columns:subgrid[a][b c]}",
+    );
+    minify_test(
+      ".foo { grid-template-rows: subgrid repeat(2, [a] [b]) [c]; }",
+      ".foo{grid-template-rows:subgrid repeat(2,[a][b])[c]}",
+    );
+    minify_test(
+      ".foo { grid-template-rows: subgrid repeat(auto-fill, [a]); }",
+      ".foo{grid-template-rows:subgrid repeat(auto-fill,[a])}",
+    );
+    minify_test(
+      ".foo { grid-template-rows: masonry; }",
+      ".foo{grid-template-rows:masonry}",
+    );
+    minify_test(
+      ".foo { grid-template: subgrid / masonry; }",
+      ".foo{grid-template:subgrid/masonry}",
+    );
+    minify_test(".foo { grid: subgrid [a] / 1fr 1fr; }", ".foo{grid:subgrid[a]/1fr 1fr}");
+    minify_test(
+      ".foo { grid-template: \"a a\" 1fr / subgrid; }",
+      ".foo{grid-template:\"a a\" 1fr/subgrid}",
+    );
+
+    prefix_test(
+      r#"
+        .foo {
+          grid-template-columns: 1fr 1fr;
+          grid-template-columns: subgrid;
+        }
+      "#,
+      indoc! {r#"
+        .foo {
+          grid-template-columns: 1fr 1fr;
+          grid-template-columns: subgrid;
+        }
+      "#},
+      Browsers {
+        chrome: Some(100 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+        .foo {
+          grid-template-columns: 1fr 1fr;
+          grid-template-columns: subgrid;
+        }
+      "#,
+      indoc! {r#"
+        .foo {
+          grid-template-columns: subgrid;
+        }
+      "#},
+      Browsers {
+        chrome: Some(120 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+        .foo {
+          grid-template-rows: subgrid;
+          grid-template-columns: 1fr 1fr;
+          grid-template-areas: none;
+        }
+      "#,
+      indoc! {r#"
+        .foo {
+          grid-template-rows: subgrid;
+          grid-template-columns: 1fr 1fr;
+          grid-template-areas: none;
+        }
+      "#},
+      Browsers {
+        safari: Some(15 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+        .foo {
+          grid-template-rows: subgrid;
+          grid-template-columns: 1fr 1fr;
+          grid-template-areas: none;
+        }
+      "#,
+      indoc! {r#"
+        .foo {
+          grid-template: subgrid / 1fr 1fr;
+        }
+      "#},
+      Browsers {
+        safari: Some(16 << 16),
+        ..Browsers::default()
+      },
+    );
   }
 
   #[test]
@@ -20969,6 +22499,49 @@ mod tests {
       Default::default(),
     );
 
+    css_modules_test(
+      r#"
+      @counter-style circles {
+        symbols: Ⓐ Ⓑ Ⓒ;
+      }
+
+      ol {
+        counter-reset: section list-item;
+      }
+
+      li {
+        counter-increment: section;
+      }
+
+      li::before {
+        content: counters(section, ".", circles) " " counter(list-item);
+      }
+    "#,
+      indoc! {r#"
+      @counter-style EgL3uq_circles {
+        symbols: Ⓐ Ⓑ Ⓒ;
+      }
+
+      ol {
+        counter-reset: EgL3uq_section list-item;
+      }
+
+      li {
+        counter-increment: EgL3uq_section;
+      }
+
+      li:before {
+        content: counters(EgL3uq_section, ".", EgL3uq_circles) " " counter(list-item);
+      }
+    "#},
+      map! {
+        "circles" => "EgL3uq_circles" referenced: true,
+        "section" => "EgL3uq_section" referenced: true
+      },
+      HashMap::new(),
+      Default::default(),
+    );
+
     #[cfg(feature = "grid")]
     css_modules_test(
       r#"
@@ -21007,6 +22580,30 @@ mod tests {
       Default::default(),
     );
 
+    css_modules_test(
+      r#"
+      @scope (.card) to (.content) {
+        .title {
+          color: red;
+        }
+      }
+    "#,
+      indoc! {r#"
+      @scope (.EgL3uq_card) to (.EgL3uq_content) {
+        .EgL3uq_title {
+          color: red;
+        }
+      }
+    "#},
+      map! {
+        "card" => "EgL3uq_card",
+        "content" => "EgL3uq_content",
+        "title" => "EgL3uq_title"
+      },
+      HashMap::new(),
+      Default::default(),
+    );
+
     css_modules_test(
       r#"
       test {
@@ -21752,37 +23349,431 @@ mod tests {
   }
 
   #[test]
-  fn test_svg() {
-    minify_test(".foo { fill: yellow; }", ".foo{fill:#ff0}");
-    minify_test(".foo { fill: url(#foo); }", ".foo{fill:url(#foo)}");
-    minify_test(".foo { fill: url(#foo) none; }", ".foo{fill:url(#foo) none}");
-    minify_test(".foo { fill: url(#foo) yellow; }", ".foo{fill:url(#foo) #ff0}");
-    minify_test(".foo { fill: none; }", ".foo{fill:none}");
-    minify_test(".foo { fill: context-fill; }", ".foo{fill:context-fill}");
-    minify_test(".foo { fill: context-stroke; }", ".foo{fill:context-stroke}");
+  fn test_unreferenced_at_rules() {
+    fn unreferenced_test(source: &str, expected: &str, removed: Vec<UnreferencedAtRule>) {
+      let mut stylesheet = StyleSheet::parse(&source, ParserOptions::default()).unwrap();
+      let result = stylesheet
+        .minify(MinifyOptions {
+          remove_unreferenced_at_rules: true,
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet.to_css(PrinterOptions::default()).unwrap();
+      assert_eq!(res.code, expected);
+      assert_eq!(result.unreferenced_at_rules, removed);
+    }
 
-    minify_test(".foo { stroke: yellow; }", ".foo{stroke:#ff0}");
-    minify_test(".foo { stroke: url(#foo); }", ".foo{stroke:url(#foo)}");
-    minify_test(".foo { stroke: url(#foo) none; }", ".foo{stroke:url(#foo) none}");
-    minify_test(".foo { stroke: url(#foo) yellow; }", ".foo{stroke:url(#foo) #ff0}");
-    minify_test(".foo { stroke: none; }", ".foo{stroke:none}");
-    minify_test(".foo { stroke: context-fill; }", ".foo{stroke:context-fill}");
-    minify_test(".foo { stroke: context-stroke; }", ".foo{stroke:context-stroke}");
+    unreferenced_test(
+      r#"
+      .foo {
+        animation: 2s used;
+      }
 
-    minify_test(".foo { marker-start: url(#foo); }", ".foo{marker-start:url(#foo)}");
+      @keyframes used {
+        from { opacity: 0 }
+      }
 
-    minify_test(".foo { stroke-dasharray: 4 1 2; }", ".foo{stroke-dasharray:4 1 2}");
-    minify_test(".foo { stroke-dasharray: 4,1,2; }", ".foo{stroke-dasharray:4 1 2}");
-    minify_test(".foo { stroke-dasharray: 4, 1, 2; }", ".foo{stroke-dasharray:4 1 2}");
-    minify_test(
-      ".foo { stroke-dasharray: 4px, 1px, 2px; }",
-      ".foo{stroke-dasharray:4 1 2}",
-    );
+      @keyframes unused {
+        from { opacity: 0 }
+      }
 
-    minify_test(".foo { mask: url('foo.svg'); }", ".foo{mask:url(foo.svg)}");
-    minify_test(
-      ".foo { mask: url(masks.svg#star) luminance }",
-      ".foo{mask:url(masks.svg#star) luminance}",
+      @-webkit-keyframes unused {
+        from { opacity: 0 }
+      }
+
+      @media (width > 100px) {
+        @keyframes also-unused {
+          from { opacity: 0 }
+        }
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        animation: 2s used;
+      }
+
+      @keyframes used {
+        from {
+          opacity: 0;
+        }
+      }
+    "#},
+      vec![
+        UnreferencedAtRule::Keyframes("unused".into()),
+        UnreferencedAtRule::Keyframes("also-unused".into()),
+      ],
+    );
+
+    unreferenced_test(
+      r#"
+      @import "other.css";
+
+      @keyframes unused {
+        from { opacity: 0 }
+      }
+    "#,
+      indoc! {r#"
+      @import "other.css";
+
+      @keyframes unused {
+        from {
+          opacity: 0;
+        }
+      }
+    "#},
+      vec![],
+    );
+
+    unreferenced_test(
+      r#"
+      .foo {
+        animation-name: "quoted", var(--name);
+        --name: from-var;
+      }
+
+      @keyframes "quoted" {
+        from { opacity: 0 }
+      }
+
+      @keyframes from-var {
+        from { opacity: 0 }
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        animation-name: "quoted", var(--name);
+        --name: from-var;
+      }
+
+      @keyframes quoted {
+        from {
+          opacity: 0;
+        }
+      }
+
+      @keyframes from-var {
+        from {
+          opacity: 0;
+        }
+      }
+    "#},
+      vec![],
+    );
+
+    unreferenced_test(
+      r#"
+      ul {
+        list-style: circles;
+      }
+
+      li::before {
+        content: counter(item, stars);
+      }
+
+      @counter-style circles {
+        system: extends base;
+        symbols: Ⓐ Ⓑ Ⓒ;
+      }
+
+      @counter-style base {
+        system: cyclic;
+        symbols: a;
+      }
+
+      @counter-style stars {
+        system: cyclic;
+        symbols: "*";
+      }
+
+      @counter-style unused {
+        system: extends unused-base;
+        symbols: a;
+      }
+
+      @counter-style unused-base {
+        system: cyclic;
+        symbols: a;
+      }
+    "#,
+      indoc! {r#"
+      ul {
+        list-style: circles;
+      }
+
+      li:before {
+        content: counter(item, stars);
+      }
+
+      @counter-style circles {
+        system: extends base;
+        symbols: Ⓐ Ⓑ Ⓒ;
+      }
+
+      @counter-style base {
+        system: cyclic;
+        symbols: a;
+      }
+
+      @counter-style stars {
+        system: cyclic;
+        symbols: "*";
+      }
+    "#},
+      vec![
+        UnreferencedAtRule::CounterStyle("unused".into()),
+        UnreferencedAtRule::CounterStyle("unused-base".into()),
+      ],
+    );
+
+    unreferenced_test(
+      r#"
+      .foo {
+        font-palette: --used;
+      }
+
+      @font-palette-values --used {
+        font-family: Bixa;
+      }
+
+      @font-palette-values --unused {
+        font-family: Bixa;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        font-palette: --used;
+      }
+
+      @font-palette-values --used {
+        font-family: Bixa;
+      }
+    "#},
+      vec![UnreferencedAtRule::FontPaletteValues("--unused".into())],
+    );
+
+    unreferenced_test(
+      r#"
+      .foo {
+        --set: 10px;
+        width: var(--read);
+      }
+
+      @property --set {
+        syntax: "<length>";
+        inherits: false;
+        initial-value: 0px;
+      }
+
+      @property --read {
+        syntax: "<length>";
+        inherits: false;
+        initial-value: 0px;
+      }
+
+      @property --unused {
+        syntax: "<length>";
+        inherits: false;
+        initial-value: 0px;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        --set: 10px;
+        width: var(--read);
+      }
+
+      @property --set {
+        syntax: "<length>";
+        inherits: false;
+        initial-value: 0;
+      }
+
+      @property --read {
+        syntax: "<length>";
+        inherits: false;
+        initial-value: 0;
+      }
+    "#},
+      vec![UnreferencedAtRule::Property("--unused".into())],
+    );
+
+    unreferenced_test(
+      r#"
+      .foo {
+        transition: --shorthand 1s;
+      }
+
+      .bar {
+        transition-property: --longhand;
+      }
+
+      .baz {
+        will-change: --hint;
+      }
+
+      @property --shorthand {
+        syntax: "<length>";
+        inherits: false;
+        initial-value: 0px;
+      }
+
+      @property --longhand {
+        syntax: "<color>";
+        inherits: false;
+        initial-value: red;
+      }
+
+      @property --hint {
+        syntax: "<number>";
+        inherits: false;
+        initial-value: 0;
+      }
+
+      @property --unused {
+        syntax: "<length>";
+        inherits: false;
+        initial-value: 0px;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        transition: --shorthand 1s;
+      }
+
+      .bar {
+        transition-property: --longhand;
+      }
+
+      .baz {
+        will-change: --hint;
+      }
+
+      @property --shorthand {
+        syntax: "<length>";
+        inherits: false;
+        initial-value: 0;
+      }
+
+      @property --longhand {
+        syntax: "<color>";
+        inherits: false;
+        initial-value: red;
+      }
+
+      @property --hint {
+        syntax: "<number>";
+        inherits: false;
+        initial-value: 0;
+      }
+    "#},
+      vec![UnreferencedAtRule::Property("--unused".into())],
+    );
+  }
+
+  #[test]
+  fn test_used_symbols() {
+    fn used_symbols_test(source: &str, expected: &str) {
+      let mut stylesheet = StyleSheet::parse(
+        &source,
+        ParserOptions {
+          nesting: true,
+          ..ParserOptions::default()
+        },
+      )
+      .unwrap();
+      let set = |s: &[&str]| s.iter().map(|s| String::from(*s)).collect();
+      stylesheet
+        .minify(MinifyOptions {
+          used_symbols: Some(UsedSymbols {
+            classes: set(&["foo", "bar"]),
+            ids: set(&["main"]),
+            elements: set(&["div", "span", "a"]),
+            attributes: set(&["href", "data-state"]),
+          }),
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    used_symbols_test(".foo { color: red } .baz { color: red }", ".foo{color:red}");
+    used_symbols_test(".foo, .baz { color: red }", ".foo,.baz{color:red}");
+    used_symbols_test(".foo .baz { color: red }", "");
+    used_symbols_test(".foo.baz { color: red }", "");
+    used_symbols_test("#main { color: red } #other { color: red }", "#main{color:red}");
+    used_symbols_test(
+      "div > span { color: red } section { color: red }",
+      "div>span{color:red}",
+    );
+    used_symbols_test("DIV { color: red }", "DIV{color:red}");
+    used_symbols_test("* { color: red } :root { color: red }", "*{color:red}:root{color:red}");
+    used_symbols_test(
+      "a[href^=http] { color: red } [data-state=open] { color: red } [disabled] { color: red }",
+      "a[href^=http]{color:red}[data-state=open]{color:red}",
+    );
+    used_symbols_test(":is(.foo, .baz) { color: red }", ":is(.foo,.baz){color:red}");
+    used_symbols_test(":is(.baz, .qux) { color: red }", "");
+    used_symbols_test(":where(.baz) .foo { color: red }", "");
+    used_symbols_test(":not(.baz) { color: red }", ":not(.baz){color:red}");
+    used_symbols_test(".foo:not(.baz) { color: red }", ".foo:not(.baz){color:red}");
+    used_symbols_test(".foo:has(.bar) { color: red }", ".foo:has(.bar){color:red}");
+    used_symbols_test(".foo:has(> img) { color: red }", "");
+    used_symbols_test(":nth-child(2 of .baz) { color: red }", "");
+    used_symbols_test(".foo:hover::before { color: red }", ".foo:hover:before{color:red}");
+    used_symbols_test(
+      "@media (width > 100px) { .baz { color: red } .foo { color: red } }",
+      "@media (width>100px){.foo{color:red}}",
+    );
+    used_symbols_test("@media (width > 100px) { .baz { color: red } }", "");
+    used_symbols_test("@supports (display: grid) { .baz { color: red } }", "");
+    used_symbols_test("@container (width > 100px) { .baz { color: red } }", "");
+    used_symbols_test(
+      "@layer base { .baz { color: red } .bar { color: red } }",
+      "@layer base{.bar{color:red}}",
+    );
+    used_symbols_test(".baz { color: red; & .foo { color: green } }", "");
+    used_symbols_test(".baz { color: red; .foo & { color: green } }", "");
+    used_symbols_test(".foo { color: red; & .baz { color: green } }", ".foo{color:red}");
+  }
+
+  #[test]
+  fn test_svg() {
+    minify_test(".foo { fill: yellow; }", ".foo{fill:#ff0}");
+    minify_test(".foo { fill: url(#foo); }", ".foo{fill:url(#foo)}");
+    minify_test(".foo { fill: url(#foo) none; }", ".foo{fill:url(#foo) none}");
+    minify_test(".foo { fill: url(#foo) yellow; }", ".foo{fill:url(#foo) #ff0}");
+    minify_test(".foo { fill: none; }", ".foo{fill:none}");
+    minify_test(".foo { fill: context-fill; }", ".foo{fill:context-fill}");
+    minify_test(".foo { fill: context-stroke; }", ".foo{fill:context-stroke}");
+
+    minify_test(".foo { stroke: yellow; }", ".foo{stroke:#ff0}");
+    minify_test(".foo { stroke: url(#foo); }", ".foo{stroke:url(#foo)}");
+    minify_test(".foo { stroke: url(#foo) none; }", ".foo{stroke:url(#foo) none}");
+    minify_test(".foo { stroke: url(#foo) yellow; }", ".foo{stroke:url(#foo) #ff0}");
+    minify_test(".foo { stroke: none; }", ".foo{stroke:none}");
+    minify_test(".foo { stroke: context-fill; }", ".foo{stroke:context-fill}");
+    minify_test(".foo { stroke: context-stroke; }", ".foo{stroke:context-stroke}");
+
+    minify_test(".foo { marker-start: url(#foo); }", ".foo{marker-start:url(#foo)}");
+
+    minify_test(".foo { stroke-dasharray: 4 1 2; }", ".foo{stroke-dasharray:4 1 2}");
+    minify_test(".foo { stroke-dasharray: 4,1,2; }", ".foo{stroke-dasharray:4 1 2}");
+    minify_test(".foo { stroke-dasharray: 4, 1, 2; }", ".foo{stroke-dasharray:4 1 2}");
+    minify_test(
+      ".foo { stroke-dasharray: 4px, 1px, 2px; }",
+      ".foo{stroke-dasharray:4 1 2}",
+    );
+
+    minify_test(".foo { mask: url('foo.svg'); }", ".foo{mask:url(foo.svg)}");
+    minify_test(
+      ".foo { mask: url(masks.svg#star) luminance }",
+      ".foo{mask:url(masks.svg#star) luminance}",
     );
     minify_test(
       ".foo { mask: url(masks.svg#star) 40px 20px }",
@@ -23711,23 +25702,499 @@ mod tests {
       "#,
       "@layer one{body{background:#ff0}}body{background:red}@layer two{body{background:green}}",
     );
-  }
-
-  #[test]
-  fn test_property() {
     minify_test(
       r#"
-      @property --property-name {
-        syntax: '<color>';
-        inherits: false;
-        initial-value: yellow;
+      @layer a {
+        @layer b {
+          .foo {
+            color: red;
+          }
+        }
       }
-    "#,
-      "@property --property-name{syntax:\"<color>\";inherits:false;initial-value:#ff0}",
-    );
-
-    minify_test(
-      r#"
+      .bar {
+        color: blue;
+      }
+      @layer a.b {
+        .foo {
+          background: green;
+        }
+      }
+      "#,
+      "@layer a.b{.foo{color:red;background:green}}.bar{color:#00f}",
+    );
+    minify_test(
+      r#"
+      @layer a {
+        .foo {
+          color: red;
+        }
+      }
+      .bar {
+        color: blue;
+      }
+      @layer a.b {
+        .foo {
+          color: green;
+        }
+      }
+      "#,
+      "@layer a{.foo{color:red}@layer b{.foo{color:green}}}.bar{color:#00f}",
+    );
+    minify_test(
+      r#"
+      @layer a {
+        .foo {
+          color: red;
+        }
+      }
+      @layer b;
+      @layer a.c;
+      @layer a {
+        @layer d {
+          .bar {
+            color: green;
+          }
+        }
+      }
+      "#,
+      "@layer a,b,a.c,a.d;@layer a{.foo{color:red}@layer d{.bar{color:green}}}",
+    );
+    minify_test(
+      r#"
+      .foo {
+        color: red;
+      }
+      @layer a;
+      @layer b {
+        .foo {
+          color: green;
+        }
+      }
+      @layer b.c, a.d;
+      "#,
+      ".foo{color:red}@layer a,b.c,a.d;@layer b{.foo{color:green}}",
+    );
+    minify_test(
+      r#"
+      @layer a {
+        .foo {
+          color: red;
+        }
+      }
+      @media print {
+        @layer a.c {
+          .baz {
+            color: blue;
+          }
+        }
+      }
+      @layer a {
+        @layer d {
+          .bar {
+            color: green;
+          }
+        }
+      }
+      "#,
+      "@layer a{.foo{color:red}}@media print{@layer a.c{.baz{color:#00f}}}@layer a.d{.bar{color:green}}",
+    );
+    minify_test(
+      r#"
+      @layer a;
+      @layer {
+        .foo {
+          color: red;
+        }
+      }
+      @layer b;
+      "#,
+      "@layer a;@layer{.foo{color:red}}@layer b;",
+    );
+    minify_test(
+      r#"
+      @layer a {}
+      @layer b {
+        .foo {
+          color: red;
+        }
+      }
+      @layer a {
+        .foo {
+          color: green;
+        }
+      }
+      @layer b {}
+      "#,
+      "@layer a{.foo{color:green}}@layer b{.foo{color:red}}",
+    );
+
+    prefix_test(
+      r#"
+      @layer b, a;
+      @layer a {
+        .foo {
+          color: red;
+        }
+      }
+      @layer b {
+        #bar .foo {
+          color: green;
+        }
+      }
+      .foo {
+        color: blue;
+      }
+      "#,
+      indoc! {r#"
+        .foo:not(#\#):not(#\#) {
+          color: red;
+        }
+
+        #bar .foo {
+          color: green;
+        }
+
+        .foo:not(#\#):not(#\#):not(#\#):not(#\#) {
+          color: #00f;
+        }
+      "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      @layer a {
+        @layer b {
+          .foo::before {
+            color: red;
+          }
+        }
+        .foo:hover {
+          color: green;
+        }
+      }
+      "#,
+      indoc! {r#"
+        .foo:before {
+          color: red;
+        }
+
+        .foo:hover:not(#\#) {
+          color: green;
+        }
+      "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      @layer a {
+        .foo {
+          color: red !important;
+          background: green;
+        }
+      }
+      @layer b {
+        .foo::after {
+          color: yellow;
+        }
+      }
+      .foo {
+        color: blue !important;
+      }
+      "#,
+      indoc! {r#"
+        .foo {
+          background: green;
+        }
+
+        .foo:not(#\#):not(#\#) {
+          color: red !important;
+        }
+
+        .foo:not(#\#):after {
+          color: #ff0;
+        }
+
+        .foo {
+          color: #00f !important;
+        }
+      "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      @layer a {
+        @media (width > 100px) {
+          .foo {
+            color: red;
+          }
+        }
+      }
+      .bar {
+        color: green;
+      }
+      "#,
+      indoc! {r#"
+        @media (min-width: 100.001px) {
+          .foo {
+            color: red;
+          }
+        }
+
+        .bar:not(#\#) {
+          color: green;
+        }
+      "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      @layer a {
+        .foo {
+          color: red;
+        }
+      }
+      "#,
+      indoc! {r#"
+        @layer a {
+          .foo {
+            color: red;
+          }
+        }
+      "#},
+      Browsers {
+        chrome: Some(99 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    let mut stylesheet = StyleSheet::parse(
+      r#"
+      @import "foo.css" layer(foo);
+      @layer bar {
+        .foo {
+          color: red;
+        }
+      }
+      "#,
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let res = stylesheet
+      .minify(MinifyOptions {
+        targets: Some(Browsers {
+          chrome: Some(90 << 16),
+          ..Browsers::default()
+        }),
+        ..MinifyOptions::default()
+      })
+      .unwrap();
+    assert_eq!(
+      res.warnings,
+      vec![Error {
+        kind: MinifyErrorKind::UnsupportedLayerImport,
+        loc: Some(ErrorLocation {
+          filename: "".into(),
+          line: 1,
+          column: 7
+        })
+      }]
+    );
+
+    let mut stylesheet = StyleSheet::parse(
+      r#"
+      @layer foo {
+        .foo {
+          color: red;
+        }
+      }
+      .foo {
+        @layer bar {
+          color: green;
+        }
+      }
+      "#,
+      ParserOptions {
+        nesting: true,
+        ..ParserOptions::default()
+      },
+    )
+    .unwrap();
+    let res = stylesheet
+      .minify(MinifyOptions {
+        targets: Some(Browsers {
+          chrome: Some(90 << 16),
+          ..Browsers::default()
+        }),
+        ..MinifyOptions::default()
+      })
+      .unwrap();
+    assert_eq!(
+      res.warnings.iter().map(|w| &w.kind).collect::<Vec<_>>(),
+      vec![&MinifyErrorKind::UnsupportedNestedLayer]
+    );
+    assert_eq!(
+      stylesheet
+        .to_css(PrinterOptions {
+          targets: Some(Browsers {
+            chrome: Some(90 << 16),
+            ..Browsers::default()
+          }),
+          ..PrinterOptions::default()
+        })
+        .unwrap()
+        .code,
+      indoc! {r#"
+        @layer foo {
+          .foo {
+            color: red;
+          }
+        }
+
+        @layer bar {
+          .foo {
+            color: green;
+          }
+        }
+      "#}
+    );
+
+    let mut stylesheet = StyleSheet::parse(
+      r#"
+      @layer foo {
+        .foo {
+          @starting-style {
+            color: red !important;
+            opacity: 0;
+          }
+        }
+      }
+      .foo {
+        color: green;
+      }
+      "#,
+      ParserOptions {
+        nesting: true,
+        ..ParserOptions::default()
+      },
+    )
+    .unwrap();
+    let targets = Some(Browsers {
+      chrome: Some(90 << 16),
+      ..Browsers::default()
+    });
+    stylesheet
+      .minify(MinifyOptions {
+        targets,
+        ..MinifyOptions::default()
+      })
+      .unwrap();
+    assert_eq!(
+      stylesheet
+        .to_css(PrinterOptions {
+          targets,
+          ..PrinterOptions::default()
+        })
+        .unwrap()
+        .code,
+      indoc! {r#"
+        @starting-style {
+          .foo {
+            opacity: 0;
+          }
+        }
+
+        @starting-style {
+          .foo:not(#\#) {
+            color: red !important;
+          }
+        }
+
+        .foo:not(#\#) {
+          color: green;
+        }
+      "#}
+    );
+
+    // The added ids are not renamed by CSS modules.
+    let mut stylesheet = StyleSheet::parse(
+      r#"
+      @layer foo {
+        #foo {
+          color: red;
+        }
+      }
+      .foo {
+        color: green;
+      }
+      "#,
+      ParserOptions {
+        filename: "test.css".into(),
+        css_modules: Some(Default::default()),
+        ..ParserOptions::default()
+      },
+    )
+    .unwrap();
+    stylesheet
+      .minify(MinifyOptions {
+        targets,
+        ..MinifyOptions::default()
+      })
+      .unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        targets,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    assert_eq!(
+      res.code,
+      indoc! {r#"
+        #EgL3uq_foo {
+          color: red;
+        }
+
+        .EgL3uq_foo:not(#\#):not(#\#) {
+          color: green;
+        }
+      "#}
+    );
+    assert_eq!(
+      res.exports.unwrap().keys().collect::<Vec<_>>(),
+      vec!["foo"]
+    );
+  }
+
+  #[test]
+  fn test_property() {
+    minify_test(
+      r#"
+      @property --property-name {
+        syntax: '<color>';
+        inherits: false;
+        initial-value: yellow;
+      }
+    "#,
+      "@property --property-name{syntax:\"<color>\";inherits:false;initial-value:#ff0}",
+    );
+
+    minify_test(
+      r#"
       @property --property-name {
         syntax: '<length>';
         inherits: true;
@@ -24454,6 +26921,385 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_container_query_evaluation() {
+    use crate::rules::container::{ContainerCondition, ContainerInfo};
+
+    fn evaluate(condition: &str, container: &ContainerInfo) -> Option<bool> {
+      ContainerCondition::parse_string(condition).unwrap().evaluate(container)
+    }
+
+    let container = ContainerInfo {
+      width: Some(400.0),
+      height: Some(300.0),
+      properties: HashMap::from([("--theme".into(), "dark".into())]),
+      ..ContainerInfo::default()
+    };
+    assert_eq!(evaluate("(width > 300px)", &container), Some(true));
+    assert_eq!(evaluate("(min-width: 500px)", &container), Some(false));
+    assert_eq!(evaluate("(300px < width <= 400px)", &container), Some(true));
+    assert_eq!(evaluate("(400px < width < 500px)", &container), Some(false));
+    assert_eq!(evaluate("(width = 4in)", &container), Some(false));
+    assert_eq!(evaluate("(width = 100px) or (height = 300px)", &container), Some(true));
+    assert_eq!(evaluate("(inline-size > 350px)", &container), Some(true));
+    assert_eq!(evaluate("(block-size > 350px)", &container), Some(false));
+    assert_eq!(evaluate("(aspect-ratio > 1)", &container), Some(true));
+    assert_eq!(evaluate("(orientation: landscape)", &container), Some(true));
+    assert_eq!(evaluate("not (orientation: portrait)", &container), Some(true));
+    assert_eq!(evaluate("(width > 30em)", &container), None);
+    assert_eq!(evaluate("(width > 30em) or (width > 300px)", &container), Some(true));
+    assert_eq!(evaluate("(width > 30em) and (width > 500px)", &container), Some(false));
+    assert_eq!(evaluate("(width > 30em) and (width > 300px)", &container), None);
+    assert_eq!(evaluate("style(--theme: dark)", &container), Some(true));
+    assert_eq!(evaluate("style(--theme: light)", &container), Some(false));
+    assert_eq!(evaluate("style(not (--theme: light))", &container), Some(true));
+    assert_eq!(evaluate("style(--other: dark)", &container), None);
+    assert_eq!(
+      evaluate(
+        "(inline-size > 350px)",
+        &ContainerInfo {
+          vertical_writing_mode: true,
+          ..container.clone()
+        }
+      ),
+      Some(false)
+    );
+
+    let unknown = ContainerInfo::default();
+    assert_eq!(evaluate("(width > 300px)", &unknown), None);
+    assert_eq!(evaluate("(width < 0px)", &unknown), Some(false));
+    assert_eq!(evaluate("(width >= 0px)", &unknown), Some(true));
+    assert_eq!(evaluate("(200px < width < 100px)", &unknown), Some(false));
+    assert_eq!(evaluate("(orientation: portrait)", &unknown), None);
+
+    fn container_test(source: &str, expected: &str) {
+      let mut stylesheet = StyleSheet::parse(&source, ParserOptions::default()).unwrap();
+      stylesheet
+        .minify(MinifyOptions {
+          remove_unmatched_container_queries: true,
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    container_test("@container (width < 0px) { .foo { color: red } }", "");
+    container_test("@container sidebar (height < -10px) { .foo { color: red } }", "");
+    container_test(
+      "@container (width < 0px) or (width > 100px) { .foo { color: red } }",
+      "@container (width<0) or (width>100px){.foo{color:red}}",
+    );
+    container_test(
+      "@container not (width < 0px) { .foo { color: red } }",
+      "@container not (width<0){.foo{color:red}}",
+    );
+    container_test(
+      "@container (width < 0px) and style(--foo: bar) { .foo { color: red } }",
+      "",
+    );
+    container_test(
+      r#"
+      @container sidebar (width > 100px) { .foo { color: red } }
+      @container (200px < width < 100px) { .bar { color: red } }
+      @container sidebar (100px < width) { .baz { color: red } }
+      "#,
+      "@container sidebar (width>100px){.foo,.baz{color:red}}",
+    );
+  }
+
+  #[test]
+  fn test_scope() {
+    minify_test(
+      "@scope (.card) to (.content) { img { border: 1px solid black } }",
+      "@scope(.card) to (.content){img{border:1px solid #000}}",
+    );
+    minify_test(
+      "@scope (.card) { :scope { color: red } }",
+      "@scope(.card){:scope{color:red}}",
+    );
+    minify_test(
+      "@scope to (.content) { img { color: red } }",
+      "@scope to (.content){img{color:red}}",
+    );
+    minify_test("@scope { img { color: red } }", "@scope{img{color:red}}");
+    minify_test(
+      "@scope (.a, .b) to (.c > .d) { img { color: red } }",
+      "@scope(.a,.b) to (.c>.d){img{color:red}}",
+    );
+    minify_test("@scope (.card) { }", "");
+    minify_test(
+      "@scope (.card) { img { color: red } img { background: green } }",
+      "@scope(.card){img{color:red;background:green}}",
+    );
+    minify_test(
+      "@scope (.card) { @media (min-width: 100px) { img { color: red } } }",
+      "@scope(.card){@media (width>=100px){img{color:red}}}",
+    );
+    test(
+      "@scope (.card) to (.content) { img { color: red } }",
+      indoc! {r#"
+      @scope (.card) to (.content) {
+        img {
+          color: red;
+        }
+      }
+    "#},
+    );
+
+    prefix_test(
+      r#"
+      @scope (.card) {
+        img {
+          border: 1px solid black;
+        }
+      }
+    "#,
+      indoc! {r#"
+      :where(.card) img {
+        border: 1px solid #000;
+      }
+    "#},
+      Browsers {
+        chrome: Some(110 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    // :where() is not supported, so @scope cannot be lowered.
+    prefix_test(
+      r#"
+      @scope (.card) {
+        img {
+          border: 1px solid black;
+        }
+      }
+    "#,
+      indoc! {r#"
+      @scope (.card) {
+        img {
+          border: 1px solid #000;
+        }
+      }
+    "#},
+      Browsers {
+        chrome: Some(80 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      @scope (.card) to (.content) {
+        img {
+          border: 1px solid black;
+        }
+
+        :scope > .title {
+          color: red;
+        }
+      }
+    "#,
+      indoc! {r#"
+      :where(.card) img:not(:where(:where(.card) .content, :where(.card) .content *)) {
+        border: 1px solid #000;
+      }
+
+      :where(.card) > .title:not(:where(:where(.card) .content, :where(.card) .content *)) {
+        color: red;
+      }
+    "#},
+      Browsers {
+        chrome: Some(110 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      @scope (.a, .b) {
+        @media (min-width: 100px) {
+          .foo, :is(:scope .bar) {
+            color: red;
+          }
+        }
+      }
+    "#,
+      indoc! {r#"
+      @media (width >= 100px) {
+        :where(.a, .b) .foo, :is(:where(.a, .b) .bar) {
+          color: red;
+        }
+      }
+    "#},
+      Browsers {
+        chrome: Some(110 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    // Without a prelude, the scoping root is the parent of the owner node, not :root.
+    prefix_test(
+      r#"
+      @scope {
+        img {
+          color: green;
+        }
+      }
+    "#,
+      indoc! {r#"
+      @scope {
+        img {
+          color: green;
+        }
+      }
+    "#},
+      Browsers {
+        chrome: Some(110 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      @scope (.card) {
+        img {
+          border: 1px solid black;
+        }
+      }
+    "#,
+      indoc! {r#"
+      @scope (.card) {
+        img {
+          border: 1px solid #000;
+        }
+      }
+    "#},
+      Browsers {
+        chrome: Some(118 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    error_test("@scope (.a) to {}", ParserError::EndOfInput);
+  }
+
+  #[test]
+  fn test_starting_style() {
+    minify_test(
+      "@starting-style { .foo { opacity: 0 } }",
+      "@starting-style{.foo{opacity:0}}",
+    );
+    minify_test(
+      "@starting-style { .foo { opacity: 0 } } @starting-style { .bar { opacity: 0 } }",
+      "@starting-style{.foo,.bar{opacity:0}}",
+    );
+    minify_test(
+      "@starting-style { .foo { opacity: 0 } } .foo { opacity: 1 } @starting-style { .bar { opacity: 0 } }",
+      "@starting-style{.foo{opacity:0}}.foo{opacity:1}@starting-style{.bar{opacity:0}}",
+    );
+    minify_test("@starting-style {}", "");
+    test(
+      r#"
+      @starting-style {
+        .foo {
+          opacity: 0;
+        }
+      }
+    "#,
+      indoc! {r#"
+      @starting-style {
+        .foo {
+          opacity: 0;
+        }
+      }
+    "#},
+    );
+
+    nesting_test(
+      r#"
+      .foo {
+        opacity: 1;
+        transition: opacity 1s;
+
+        @starting-style {
+          opacity: 0;
+        }
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        opacity: 1;
+        transition: opacity 1s;
+      }
+
+      @starting-style {
+        .foo {
+          opacity: 0;
+        }
+      }
+    "#},
+    );
+
+    nesting_test_no_targets(
+      r#"
+      .foo {
+        opacity: 1;
+
+        @starting-style {
+          opacity: 0;
+        }
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        opacity: 1;
+
+        @starting-style {
+          & {
+            opacity: 0;
+          }
+        }
+      }
+    "#},
+    );
+  }
+
+  #[test]
+  fn test_position_try() {
+    minify_test(
+      "@position-try --top { top: anchor(bottom); left: anchor(center) }",
+      "@position-try --top{top:anchor(bottom);left:anchor(center)}",
+    );
+    minify_test(
+      "@position-try --foo { margin-top: 10px; margin-bottom: 10px; margin-left: 10px; margin-right: 10px }",
+      "@position-try --foo{margin:10px}",
+    );
+    test(
+      r#"
+      @position-try --bottom {
+        top: anchor(--tooltip bottom);
+        position-area: bottom;
+      }
+    "#,
+      indoc! {r#"
+      @position-try --bottom {
+        position-area: bottom;
+        top: anchor(--tooltip bottom);
+      }
+    "#},
+    );
+    error_test(
+      "@position-try foo { top: 0 }",
+      ParserError::UnexpectedToken(Token::Ident("foo".into())),
+    );
+  }
+
   #[test]
   fn test_unknown_at_rules() {
     minify_test("@foo;", "@foo;");
@@ -20,6 +20,7 @@ pub enum LogicalGroup {
   ScrollMargin,
   Padding,
   ScrollPadding,
+  OverscrollBehavior,
   Inset,
   Size,
   MinSize,
@@ -1022,6 +1022,663 @@ where
   }
 }
 
+impl<'i, FeatureId> QueryFeature<'i, FeatureId> {
+  /// Evaluates a range feature, given the range of values the feature may have in the environment
+  /// and a function to convert feature values into numbers comparable with it.
+  /// Returns `None` if the result depends on information that is not known.
+  pub(crate) fn evaluate_range<F>(&self, actual: FeatureRange, to_number: F) -> Option<bool>
+  where
+    F: Fn(&MediaFeatureValue<'i>) -> Option<f32>,
+  {
+    if let QueryFeature::Boolean { .. } = self {
+      // In a boolean context, a range feature matches if it is non-zero.
+      return if actual.min > 0.0 {
+        Some(true)
+      } else if actual.max <= 0.0 {
+        Some(false)
+      } else {
+        None
+      };
+    }
+
+    actual.evaluate(&self.value_range(to_number)?)
+  }
+
+  /// Returns the range of values matched by a plain, range or interval feature, given a function
+  /// to convert feature values into numbers. Returns `None` for boolean features.
+  fn value_range<F>(&self, to_number: F) -> Option<FeatureRange>
+  where
+    F: Fn(&MediaFeatureValue<'i>) -> Option<f32>,
+  {
+    let range = match self {
+      QueryFeature::Plain { value, .. } => FeatureRange::exact(to_number(value)?),
+      QueryFeature::Boolean { .. } => return None,
+      QueryFeature::Range { operator, value, .. } => FeatureRange::from_comparison(*operator, to_number(value)?),
+      QueryFeature::Interval {
+        start,
+        start_operator,
+        end,
+        end_operator,
+        ..
+      } => FeatureRange::from_comparison(start_operator.opposite(), to_number(start)?)
+        .intersect(&FeatureRange::from_comparison(*end_operator, to_number(end)?)),
+    };
+
+    Some(range)
+  }
+}
+
+/// A range of numeric values that a query feature may have, with inclusive or exclusive bounds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct FeatureRange {
+  min: f32,
+  min_inclusive: bool,
+  max: f32,
+  max_inclusive: bool,
+}
+
+impl FeatureRange {
+  /// A range containing only the given value.
+  pub fn exact(value: f32) -> FeatureRange {
+    FeatureRange::from_comparison(MediaFeatureComparison::Equal, value)
+  }
+
+  /// A range containing all values greater than or equal to the given value.
+  pub fn at_least(value: f32) -> FeatureRange {
+    FeatureRange::from_comparison(MediaFeatureComparison::GreaterThanEqual, value)
+  }
+
+  /// A range containing all values that match the comparison `x <operator> value`.
+  pub fn from_comparison(operator: MediaFeatureComparison, value: f32) -> FeatureRange {
+    let (min, min_inclusive, max, max_inclusive) = match operator {
+      MediaFeatureComparison::Equal => (value, true, value, true),
+      MediaFeatureComparison::GreaterThan => (value, false, f32::INFINITY, true),
+      MediaFeatureComparison::GreaterThanEqual => (value, true, f32::INFINITY, true),
+      MediaFeatureComparison::LessThan => (f32::NEG_INFINITY, true, value, false),
+      MediaFeatureComparison::LessThanEqual => (f32::NEG_INFINITY, true, value, true),
+    };
+
+    FeatureRange {
+      min,
+      min_inclusive,
+      max,
+      max_inclusive,
+    }
+  }
+
+  /// Returns the range of values contained in both ranges.
+  pub fn intersect(&self, other: &FeatureRange) -> FeatureRange {
+    let (min, min_inclusive) = if self.min > other.min {
+      (self.min, self.min_inclusive)
+    } else if self.min < other.min {
+      (other.min, other.min_inclusive)
+    } else {
+      (self.min, self.min_inclusive && other.min_inclusive)
+    };
+
+    let (max, max_inclusive) = if self.max < other.max {
+      (self.max, self.max_inclusive)
+    } else if self.max > other.max {
+      (other.max, other.max_inclusive)
+    } else {
+      (self.max, self.max_inclusive && other.max_inclusive)
+    };
+
+    FeatureRange {
+      min,
+      min_inclusive,
+      max,
+      max_inclusive,
+    }
+  }
+
+  /// Returns whether the range contains no values.
+  pub fn is_empty(&self) -> bool {
+    self.min > self.max || (self.min == self.max && !(self.min_inclusive && self.max_inclusive))
+  }
+
+  /// Returns whether all values in the given range are also contained in this range.
+  pub fn contains(&self, other: &FeatureRange) -> bool {
+    (self.min < other.min || (self.min == other.min && (self.min_inclusive || !other.min_inclusive)))
+      && (self.max > other.max || (self.max == other.max && (self.max_inclusive || !other.max_inclusive)))
+  }
+
+  /// Evaluates whether a value within this range matches the expected range.
+  /// Returns `None` if only some of the values match.
+  pub fn evaluate(&self, expected: &FeatureRange) -> Option<bool> {
+    if expected.contains(self) {
+      Some(true)
+    } else if self.intersect(expected).is_empty() {
+      Some(false)
+    } else {
+      None
+    }
+  }
+}
+
+/// Combines the results of evaluating a list of conditions with the given operator.
+/// A result of `None` means it is unknown whether the condition matches.
+pub(crate) fn evaluate_operation<I: IntoIterator<Item = Option<bool>>>(
+  operator: Operator,
+  results: I,
+) -> Option<bool> {
+  // If any result determines the outcome, the unknown results do not matter.
+  let decisive = operator == Operator::Or;
+  let mut unknown = false;
+  for result in results {
+    match result {
+      Some(result) if result == decisive => return Some(decisive),
+      Some(_) => {}
+      None => unknown = true,
+    }
+  }
+
+  if unknown {
+    None
+  } else {
+    Some(!decisive)
+  }
+}
+
+/// A description of a device and its user preferences, used to evaluate media queries.
+///
+/// Features that depend on values that are not provided evaluate to unknown. Unknown lengths
+/// and resolutions are assumed to be any non-negative value, so conditions that cannot match
+/// any device (e.g. `(width < 0px)`) still evaluate to false.
+#[derive(Debug, Clone, Default)]
+pub struct Environment {
+  /// The media type of the device.
+  pub media_type: Option<MediaType<'static>>,
+  /// The width of the viewport, in pixels.
+  pub width: Option<f32>,
+  /// The height of the viewport, in pixels.
+  pub height: Option<f32>,
+  /// The width of the device screen, in pixels.
+  pub device_width: Option<f32>,
+  /// The height of the device screen, in pixels.
+  pub device_height: Option<f32>,
+  /// The resolution of the device, in dots per px.
+  pub resolution: Option<f32>,
+  /// The number of bits per color component of the device, or zero for non-color devices.
+  pub color: Option<i32>,
+  /// The number of bits per pixel of a monochrome device, or zero for non-monochrome devices.
+  pub monochrome: Option<i32>,
+  /// The values of discrete features, keyed by feature name, e.g. `prefers-color-scheme` → `dark`,
+  /// `pointer` → `coarse`, or `hover` → `none`.
+  pub features: HashMap<String, String>,
+}
+
+impl Environment {
+  fn range(value: Option<f32>) -> FeatureRange {
+    match value {
+      Some(value) => FeatureRange::exact(value),
+      None => FeatureRange::at_least(0.0),
+    }
+  }
+
+  fn aspect_ratio(width: Option<f32>, height: Option<f32>) -> FeatureRange {
+    match (width, height) {
+      (Some(width), Some(height)) => FeatureRange::exact(width / height),
+      _ => FeatureRange::at_least(0.0),
+    }
+  }
+}
+
+impl<'i> MediaList<'i> {
+  /// Evaluates the media query list against the given environment. Returns `None` if the
+  /// result depends on information that is not known.
+  pub fn evaluate(&self, environment: &Environment) -> Option<bool> {
+    if self.media_queries.is_empty() {
+      return Some(true);
+    }
+
+    evaluate_operation(
+      Operator::Or,
+      self.media_queries.iter().map(|query| query.evaluate(environment)),
+    )
+  }
+}
+
+impl<'i> MediaQuery<'i> {
+  /// Evaluates the media query against the given environment. Returns `None` if the
+  /// result depends on information that is not known.
+  pub fn evaluate(&self, environment: &Environment) -> Option<bool> {
+    let media_type = match &self.media_type {
+      MediaType::All => Some(true),
+      media_type => environment.media_type.as_ref().map(|m| m == media_type),
+    };
+    let condition = match &self.condition {
+      Some(condition) => condition.evaluate(environment),
+      None => Some(true),
+    };
+
+    let result = evaluate_operation(Operator::And, [media_type, condition]);
+    if self.qualifier == Some(Qualifier::Not) {
+      result.map(|result| !result)
+    } else {
+      result
+    }
+  }
+}
+
+impl<'i> MediaCondition<'i> {
+  /// Evaluates the media condition against the given environment. Returns `None` if the
+  /// result depends on information that is not known.
+  pub fn evaluate(&self, environment: &Environment) -> Option<bool> {
+    match self {
+      MediaCondition::Feature(feature) => feature.evaluate(environment),
+      MediaCondition::Not(condition) => condition.evaluate(environment).map(|result| !result),
+      MediaCondition::Operation { operator, conditions } => evaluate_operation(
+        *operator,
+        conditions.iter().map(|condition| condition.evaluate(environment)),
+      ),
+    }
+  }
+}
+
+impl<'i> MediaFeature<'i> {
+  fn evaluate(&self, environment: &Environment) -> Option<bool> {
+    let name = match self {
+      QueryFeature::Plain { name, .. }
+      | QueryFeature::Boolean { name }
+      | QueryFeature::Range { name, .. }
+      | QueryFeature::Interval { name, .. } => match name {
+        MediaFeatureName::Standard(name) => name,
+        _ => return None,
+      },
+    };
+
+    let to_px = |value: &MediaFeatureValue| match value {
+      MediaFeatureValue::Length(length) => length.to_px(),
+      _ => None,
+    };
+    let to_ratio = |value: &MediaFeatureValue| match value {
+      MediaFeatureValue::Ratio(ratio) => Some(ratio.0 / ratio.1),
+      _ => None,
+    };
+    let to_integer = |value: &MediaFeatureValue| match value {
+      MediaFeatureValue::Integer(integer) => Some(*integer as f32),
+      _ => None,
+    };
+
+    match name {
+      MediaFeatureId::Width => self.evaluate_range(Environment::range(environment.width), to_px),
+      MediaFeatureId::Height => self.evaluate_range(Environment::range(environment.height), to_px),
+      MediaFeatureId::DeviceWidth => self.evaluate_range(Environment::range(environment.device_width), to_px),
+      MediaFeatureId::DeviceHeight => self.evaluate_range(Environment::range(environment.device_height), to_px),
+      MediaFeatureId::AspectRatio => self.evaluate_range(
+        Environment::aspect_ratio(environment.width, environment.height),
+        to_ratio,
+      ),
+      MediaFeatureId::DeviceAspectRatio => self.evaluate_range(
+        Environment::aspect_ratio(environment.device_width, environment.device_height),
+        to_ratio,
+      ),
+      MediaFeatureId::Resolution => {
+        self.evaluate_range(Environment::range(environment.resolution), |value| match value {
+          MediaFeatureValue::Resolution(resolution) => Some(resolution.to_dppx()),
+          _ => None,
+        })
+      }
+      MediaFeatureId::WebKitDevicePixelRatio | MediaFeatureId::MozDevicePixelRatio => {
+        self.evaluate_range(Environment::range(environment.resolution), |value| match value {
+          MediaFeatureValue::Number(number) => Some(*number),
+          _ => None,
+        })
+      }
+      MediaFeatureId::Color => self.evaluate_range(
+        Environment::range(environment.color.map(|color| color as f32)),
+        to_integer,
+      ),
+      MediaFeatureId::Monochrome => self.evaluate_range(
+        Environment::range(environment.monochrome.map(|monochrome| monochrome as f32)),
+        to_integer,
+      ),
+      MediaFeatureId::Orientation => {
+        let is_portrait = environment.height? >= environment.width?;
+        self.evaluate_discrete(if is_portrait { "portrait" } else { "landscape" })
+      }
+      name => match name.value_type() {
+        MediaFeatureType::Ident => self.evaluate_discrete(environment.features.get(name.as_str())?),
+        _ => None,
+      },
+    }
+  }
+
+  /// Evaluates a discrete feature, given its value in the environment.
+  fn evaluate_discrete(&self, value: &str) -> Option<bool> {
+    match self {
+      QueryFeature::Plain {
+        value: MediaFeatureValue::Ident(ident),
+        ..
+      } => Some(ident.0.eq_ignore_ascii_case(value)),
+      // In a boolean context, discrete features match unless they have their "empty" value.
+      QueryFeature::Boolean { .. } => {
+        Some(!value.eq_ignore_ascii_case("none") && !value.eq_ignore_ascii_case("no-preference"))
+      }
+      _ => None,
+    }
+  }
+}
+
+/// The range of values matched by a numeric media feature. Ranges can only be compared
+/// when they constrain the same feature and their values are expressed in the same unit,
+/// e.g. `em` values cannot be compared with `px` values.
+struct FeatureBounds {
+  id: MediaFeatureId,
+  unit: String,
+  range: FeatureRange,
+}
+
+impl FeatureBounds {
+  fn is_comparable(&self, other: &FeatureBounds) -> bool {
+    self.id == other.id && self.unit == other.unit
+  }
+}
+
+/// Converts a media feature value into a number, along with the unit it is expressed in.
+/// Absolute lengths are converted to pixels so they can be compared with each other.
+fn numeric_value(value: &MediaFeatureValue) -> Option<(f32, String)> {
+  match value {
+    MediaFeatureValue::Length(length) => match (length.to_px(), length) {
+      (Some(px), _) => Some((px, "px".into())),
+      (None, Length::Value(value)) => {
+        let (value, unit) = value.to_unit_value();
+        Some((value, unit.into()))
+      }
+      _ => None,
+    },
+    MediaFeatureValue::Number(number) => Some((*number, String::new())),
+    MediaFeatureValue::Integer(integer) => Some((*integer as f32, String::new())),
+    // Resolutions in different units are often used as fallbacks for each other,
+    // so they are not considered comparable.
+    MediaFeatureValue::Resolution(resolution) => Some(match resolution {
+      Resolution::Dpi(dpi) => (*dpi, "dpi".into()),
+      Resolution::Dpcm(dpcm) => (*dpcm, "dpcm".into()),
+      Resolution::Dppx(dppx) => (*dppx, "dppx".into()),
+    }),
+    MediaFeatureValue::Ratio(ratio) => Some((ratio.0 / ratio.1, String::new())),
+    _ => None,
+  }
+}
+
+impl<'i> MediaFeature<'i> {
+  /// Returns the range of values matched by a numeric feature. All numeric media features
+  /// are non-negative, so the range is limited to values greater than or equal to zero.
+  fn bounds(&self) -> Option<FeatureBounds> {
+    let (name, value) = match self {
+      QueryFeature::Plain { name, value } | QueryFeature::Range { name, value, .. } => (name, value),
+      QueryFeature::Interval { name, start, .. } => (name, start),
+      QueryFeature::Boolean { .. } => return None,
+    };
+
+    let id = match name {
+      MediaFeatureName::Standard(id) => *id,
+      _ => return None,
+    };
+
+    let (_, unit) = numeric_value(value)?;
+    let range = self.value_range(|value| match numeric_value(value) {
+      Some((value, u)) if u == unit => Some(value),
+      _ => None,
+    })?;
+
+    Some(FeatureBounds {
+      id,
+      unit,
+      range: range.intersect(&FeatureRange::at_least(0.0)),
+    })
+  }
+}
+
+impl<'i> MediaList<'i> {
+  /// Simplifies each media query in the list, and removes queries that never match or
+  /// that are subsumed by another query in the list.
+  pub(crate) fn simplify(&mut self) {
+    for query in &mut self.media_queries {
+      query.simplify();
+    }
+
+    let mut i = 0;
+    while i < self.media_queries.len() {
+      let query = &self.media_queries[i];
+      let is_redundant = self.media_queries.iter().enumerate().any(|(j, other)| {
+        // When two queries subsume each other, keep the first one.
+        j != i && other.subsumes(query) && (j < i || !query.subsumes(other))
+      });
+
+      if is_redundant {
+        self.media_queries.remove(i);
+      } else {
+        i += 1;
+      }
+    }
+  }
+}
+
+impl<'i> MediaQuery<'i> {
+  /// Simplifies the condition of the media query. Queries with a condition that always matches
+  /// are reduced to their media type, and queries that can never match become `not all`.
+  pub(crate) fn simplify(&mut self) {
+    let result = match &mut self.condition {
+      Some(condition) => condition.simplify(),
+      None => return,
+    };
+
+    match result {
+      Some(true) => self.condition = None,
+      Some(false) => {
+        // `not (screen and <false>)` always matches.
+        self.qualifier = if self.qualifier == Some(Qualifier::Not) {
+          None
+        } else {
+          Some(Qualifier::Not)
+        };
+        self.media_type = MediaType::All;
+        self.condition = None;
+      }
+      None => {}
+    }
+  }
+
+  /// Returns whether every environment matched by the given query is also matched by this query.
+  fn subsumes(&self, other: &MediaQuery<'i>) -> bool {
+    if self.always_matches() || other.never_matches() {
+      return true;
+    }
+
+    if self.qualifier != other.qualifier || self.qualifier == Some(Qualifier::Not) {
+      return self == other;
+    }
+
+    if self.media_type != MediaType::All && self.media_type != other.media_type {
+      return false;
+    }
+
+    match (&self.condition, &other.condition) {
+      (None, _) => true,
+      (Some(_), None) => false,
+      (Some(a), Some(b)) => b.implies(a),
+    }
+  }
+}
+
+impl<'i> MediaCondition<'i> {
+  /// Simplifies the condition by removing features that always match and range features that
+  /// are redundant with other features in the same conjunction. Returns whether the condition
+  /// always or never matches, in which case the condition itself should be discarded.
+  fn simplify(&mut self) -> Option<bool> {
+    let (operator, conditions) = match self {
+      MediaCondition::Feature(feature) => {
+        let bounds = feature.bounds()?;
+        return FeatureRange::at_least(0.0).evaluate(&bounds.range);
+      }
+      MediaCondition::Not(condition) => return condition.simplify().map(|result| !result),
+      MediaCondition::Operation { operator, conditions } => (*operator, std::mem::take(conditions)),
+    };
+
+    // A result that decides the outcome of the operation, e.g. `false` for `and`.
+    let decisive = operator == Operator::Or;
+    let mut simplified = Vec::with_capacity(conditions.len());
+    for mut condition in conditions {
+      match condition.simplify() {
+        Some(result) if result == decisive => return Some(decisive),
+        Some(_) => {}
+        None => match condition {
+          MediaCondition::Operation {
+            operator: op,
+            conditions,
+          } if op == operator => simplified.extend(conditions),
+          condition => simplified.push(condition),
+        },
+      }
+    }
+
+    if operator == Operator::And && !merge_ranges(&mut simplified) {
+      return Some(false);
+    }
+
+    match simplified.len() {
+      0 => return Some(!decisive),
+      1 => *self = simplified.pop().unwrap(),
+      _ => {
+        *self = MediaCondition::Operation {
+          operator,
+          conditions: simplified,
+        }
+      }
+    }
+
+    None
+  }
+
+  /// Returns whether this condition matching guarantees that the given condition matches.
+  /// This may return false negatives for conditions that are too complex to compare.
+  fn implies(&self, other: &MediaCondition<'i>) -> bool {
+    if self == other {
+      return true;
+    }
+
+    match other {
+      MediaCondition::Operation {
+        operator: Operator::And,
+        conditions,
+      } => return conditions.iter().all(|condition| self.implies(condition)),
+      MediaCondition::Operation {
+        operator: Operator::Or,
+        conditions,
+      } if conditions.iter().any(|condition| self.implies(condition)) => return true,
+      _ => {}
+    }
+
+    match self {
+      MediaCondition::Operation {
+        operator: Operator::Or,
+        conditions,
+      } => conditions.iter().all(|condition| condition.implies(other)),
+      MediaCondition::Operation {
+        operator: Operator::And,
+        conditions,
+      } => {
+        if conditions.iter().any(|condition| condition.implies(other)) {
+          return true;
+        }
+
+        // The features of a conjunction may only imply a range together, e.g.
+        // `(width >= 100px) and (width <= 200px)` implies `(50px < width < 300px)`.
+        let expected = match other {
+          MediaCondition::Feature(feature) => feature.bounds(),
+          _ => None,
+        };
+        let expected = match expected {
+          Some(expected) => expected,
+          None => return false,
+        };
+
+        let range = conditions
+          .iter()
+          .filter_map(|condition| match condition {
+            MediaCondition::Feature(feature) => feature.bounds(),
+            _ => None,
+          })
+          .filter(|bounds| bounds.is_comparable(&expected))
+          .fold(FeatureRange::at_least(0.0), |range, bounds| {
+            range.intersect(&bounds.range)
+          });
+        expected.range.contains(&range)
+      }
+      MediaCondition::Feature(feature) => match (feature.bounds(), other) {
+        (Some(actual), MediaCondition::Feature(other)) => match other.bounds() {
+          Some(expected) => expected.is_comparable(&actual) && expected.range.contains(&actual.range),
+          None => false,
+        },
+        _ => false,
+      },
+      MediaCondition::Not(..) => false,
+    }
+  }
+}
+
+/// Merges the range features of a conjunction that constrain the same feature, keeping only the
+/// features that determine the bounds of their combined range, e.g. `(min-width: 500px) and
+/// (min-width: 600px)` becomes `(min-width: 600px)`. Returns false if the ranges do not intersect,
+/// in which case the conjunction never matches.
+fn merge_ranges(conditions: &mut Vec<MediaCondition>) -> bool {
+  let bounds: Vec<_> = conditions
+    .iter()
+    .map(|condition| match condition {
+      MediaCondition::Feature(feature) => feature.bounds(),
+      _ => None,
+    })
+    .collect();
+
+  let mut keep = vec![true; conditions.len()];
+  for (i, first) in bounds.iter().enumerate() {
+    let first = match first {
+      Some(first) => first,
+      None => continue,
+    };
+
+    let group: Vec<usize> = (0..bounds.len())
+      .filter(|j| matches!(&bounds[*j], Some(b) if b.is_comparable(first)))
+      .collect();
+    if group[0] != i || group.len() == 1 {
+      continue;
+    }
+
+    let range = group.iter().fold(first.range, |range, j| {
+      range.intersect(&bounds[*j].as_ref().unwrap().range)
+    });
+    if range.is_empty() {
+      return false;
+    }
+
+    // Prefer a single feature that covers the whole range, otherwise keep the features
+    // that provide the lower and upper bounds.
+    let range_of = |j: &usize| bounds[*j].as_ref().unwrap().range;
+    let needed = match group.iter().find(|j| range_of(j) == range) {
+      Some(j) => vec![*j],
+      None => {
+        let lower = group
+          .iter()
+          .find(|j| range_of(j).min == range.min && range_of(j).min_inclusive == range.min_inclusive);
+        let upper = group
+          .iter()
+          .find(|j| range_of(j).max == range.max && range_of(j).max_inclusive == range.max_inclusive);
+        lower.into_iter().chain(upper).copied().collect()
+      }
+    };
+
+    for j in group {
+      keep[j] = needed.contains(&j);
+    }
+  }
+
+  let mut keep = keep.into_iter();
+  conditions.retain(|_| keep.next().unwrap());
+  true
+}
+
 impl<'i, FeatureId: FeatureToCss> ToCss for QueryFeature<'i, FeatureId> {
   fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
   where
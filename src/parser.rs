@@ -3,10 +3,15 @@ use crate::error::{Error, ParserError, PrinterError};
 use crate::media_query::*;
 use crate::printer::Printer;
 use crate::properties::custom::TokenList;
+use crate::properties::font::FontFamily;
 use crate::rules::container::{ContainerCondition, ContainerName, ContainerRule};
+use crate::rules::font_feature_values::FontFeatureValuesRule;
 use crate::rules::font_palette_values::FontPaletteValuesRule;
 use crate::rules::layer::{LayerBlockRule, LayerStatementRule};
+use crate::rules::position_try::PositionTryRule;
 use crate::rules::property::PropertyRule;
+use crate::rules::scope::ScopeRule;
+use crate::rules::starting_style::StartingStyleRule;
 use crate::rules::viewport::ViewportRule;
 use crate::rules::{
   counter_style::CounterStyleRule,
@@ -138,7 +143,7 @@ pub enum AtRulePrelude<'i, T> {
   /// A @font-face rule prelude.
   FontFace,
   /// A @font-feature-values rule prelude, with its FamilyName list.
-  FontFeatureValues, //(Vec<FamilyName>),
+  FontFeatureValues(Vec<FontFamily<'i>>),
   /// A @font-palette-values rule prelude, with its name.
   FontPaletteValues(DashedIdent<'i>),
   /// A @counter-style rule prelude, with its counter style name.
@@ -178,6 +183,12 @@ pub enum AtRulePrelude<'i, T> {
   Property(DashedIdent<'i>),
   /// A @container prelude.
   Container(Option<ContainerName<'i>>, ContainerCondition<'i>),
+  /// A @scope prelude.
+  Scope(Option<SelectorList<'i>>, Option<SelectorList<'i>>),
+  /// A @starting-style prelude.
+  StartingStyle,
+  /// A @position-try prelude.
+  PositionTry(DashedIdent<'i>),
   /// An unknown prelude.
   Unknown(CowArcStr<'i>, TokenList<'i>),
   /// A custom prelude.
@@ -423,14 +434,10 @@ impl<'a, 'o, 'b, 'i, T: crate::traits::AtRuleParser<'i>> AtRuleParser<'i> for Ne
       "font-face" => {
         Ok(AtRulePrelude::FontFace)
       },
-      // "font-feature-values" => {
-      //     if !cfg!(feature = "gecko") {
-      //         // Support for this rule is not fully implemented in Servo yet.
-      //         return Err(input.new_custom_error(StyleParseErrorKind::UnsupportedAtRule(name.clone())))
-      //     }
-      //     let family_names = parse_family_name_list(self.context, input)?;
-      //     Ok(AtRuleType::WithBlock(AtRuleBlockPrelude::FontFeatureValues(family_names)))
-      // },
+      "font-feature-values" => {
+        let names = FontFeatureValuesRule::parse_prelude(input)?;
+        Ok(AtRulePrelude::FontFeatureValues(names))
+      },
       "font-palette-values" => {
         let name = DashedIdent::parse(input)?;
         return Ok(AtRulePrelude::FontPaletteValues(name))
@@ -500,6 +507,38 @@ impl<'a, 'o, 'b, 'i, T: crate::traits::AtRuleParser<'i>> AtRuleParser<'i> for Ne
         let condition = ContainerCondition::parse(input)?;
         Ok(AtRulePrelude::Container(name, condition))
       },
+      "scope" => {
+        let selector_parser = SelectorParser {
+          is_nesting_allowed: self.options.nesting,
+          options: &self.options,
+        };
+
+        let scope_start = if input.try_parse(|input| input.expect_parenthesis_block()).is_ok() {
+          Some(input.parse_nested_block(|input| {
+            SelectorList::parse(&selector_parser, input, NestingRequirement::None)
+          })?)
+        } else {
+          None
+        };
+
+        let scope_end = if input.try_parse(|input| input.expect_ident_matching("to")).is_ok() {
+          input.expect_parenthesis_block()?;
+          Some(input.parse_nested_block(|input| {
+            SelectorList::parse(&selector_parser, input, NestingRequirement::None)
+          })?)
+        } else {
+          None
+        };
+
+        Ok(AtRulePrelude::Scope(scope_start, scope_end))
+      },
+      "starting-style" => {
+        Ok(AtRulePrelude::StartingStyle)
+      },
+      "position-try" => {
+        let name = DashedIdent::parse(input)?;
+        Ok(AtRulePrelude::PositionTry(name))
+      },
       _ => parse_custom_at_rule_prelude(&name, input, self.options, self.at_rule_parser)
     }
   }
@@ -522,22 +561,10 @@ impl<'a, 'o, 'b, 'i, T: crate::traits::AtRuleParser<'i>> AtRuleParser<'i> for Ne
         }
         Ok(CssRule::FontFace(FontFaceRule { properties, loc }))
       }
-      // AtRuleBlockPrelude::FontFeatureValues(family_names) => {
-      //     let context = ParserContext::new_with_rule_type(
-      //         self.context,
-      //         CssRuleType::FontFeatureValues,
-      //         self.namespaces,
-      //     );
-
-      //     Ok(CssRule::FontFeatureValues(Arc::new(self.shared_lock.wrap(
-      //         FontFeatureValuesRule::parse(
-      //             &context,
-      //             input,
-      //             family_names,
-      //             start.source_location(),
-      //         ),
-      //     ))))
-      // },
+      AtRulePrelude::FontFeatureValues(names) => {
+        let rule = FontFeatureValuesRule::parse(names, input, loc, self.options.source_index)?;
+        Ok(CssRule::FontFeatureValues(rule))
+      }
       AtRulePrelude::FontPaletteValues(name) => {
         let rule = FontPaletteValuesRule::parse(name, input, loc)?;
         Ok(CssRule::FontPaletteValues(rule))
@@ -547,6 +574,11 @@ impl<'a, 'o, 'b, 'i, T: crate::traits::AtRuleParser<'i>> AtRuleParser<'i> for Ne
         declarations: DeclarationBlock::parse(input, self.options)?,
         loc,
       })),
+      AtRulePrelude::PositionTry(name) => Ok(CssRule::PositionTry(PositionTryRule {
+        name,
+        declarations: DeclarationBlock::parse(input, self.options)?,
+        loc,
+      })),
       AtRulePrelude::Media(query) => Ok(CssRule::Media(MediaRule {
         query,
         rules: self.parse_nested_rules(input)?,
@@ -563,6 +595,16 @@ impl<'a, 'o, 'b, 'i, T: crate::traits::AtRuleParser<'i>> AtRuleParser<'i> for Ne
         rules: self.parse_nested_rules(input)?,
         loc,
       })),
+      AtRulePrelude::Scope(scope_start, scope_end) => Ok(CssRule::Scope(ScopeRule {
+        scope_start,
+        scope_end,
+        rules: self.parse_nested_rules(input)?,
+        loc,
+      })),
+      AtRulePrelude::StartingStyle => Ok(CssRule::StartingStyle(StartingStyleRule {
+        rules: self.parse_nested_rules(input)?,
+        loc,
+      })),
       AtRulePrelude::Viewport(vendor_prefix) => {
         Ok(CssRule::Viewport(ViewportRule {
           vendor_prefix,
@@ -613,7 +655,7 @@ impl<'a, 'o, 'b, 'i, T: crate::traits::AtRuleParser<'i>> AtRuleParser<'i> for Ne
         // These rules don't have blocks.
         Err(input.new_unexpected_token_error(Token::CurlyBracketBlock))
       }
-      AtRulePrelude::FontFeatureValues | AtRulePrelude::Nest(..) => unreachable!(),
+      AtRulePrelude::Nest(..) => unreachable!(),
       AtRulePrelude::Unknown(name, prelude) => Ok(CssRule::Unknown(UnknownAtRule {
         name,
         prelude,
@@ -866,6 +908,9 @@ impl<'a, 'o, 'i, T: crate::traits::AtRuleParser<'i>> AtRuleParser<'i> for StyleR
         let condition = ContainerCondition::parse(input)?;
         Ok(AtRulePrelude::Container(name, condition))
       },
+      "starting-style" => {
+        Ok(AtRulePrelude::StartingStyle)
+      },
       "layer" => {
         // Only layer block rules are supported within style rules.
         let name = input.try_parse(LayerName::parse).ok();
@@ -922,6 +967,13 @@ impl<'a, 'o, 'i, T: crate::traits::AtRuleParser<'i>> AtRuleParser<'i> for StyleR
         }));
         Ok(())
       }
+      AtRulePrelude::StartingStyle => {
+        self.rules.0.push(CssRule::StartingStyle(StartingStyleRule {
+          rules: parse_nested_at_rule(input, self.options, self.at_rule_parser)?,
+          loc,
+        }));
+        Ok(())
+      }
       AtRulePrelude::LayerBlock(name) => {
         self.rules.0.push(CssRule::LayerBlock(LayerBlockRule {
           name,
@@ -1,15 +1,18 @@
 //! CSS serialization and source map generation.
 
+use crate::compat::Feature;
 use crate::css_modules::CssModule;
-use crate::dependencies::{Dependency, DependencyOptions};
+use crate::dependencies::{Dependency, DependencyOptions, InlinedAsset, UrlDependency};
 use crate::error::{Error, ErrorLocation, PrinterError, PrinterErrorKind};
 use crate::rules::{Location, StyleContext};
 use crate::selector::SelectorList;
 use crate::targets::Browsers;
+use crate::values::url::Url;
 use crate::vendor_prefix::VendorPrefix;
 use cssparser::{serialize_identifier, serialize_name};
 #[cfg(feature = "sourcemap")]
 use parcel_sourcemap::{OriginalLocation, SourceMap};
+use std::collections::HashMap;
 
 /// Options that control how CSS is serialized to a string.
 #[derive(Default)]
@@ -65,6 +68,8 @@ pub struct PseudoClasses<'a> {
 /// that respects options such as `minify`, and `css_modules`.
 pub struct Printer<'a, 'b, 'c, W> {
   pub(crate) sources: Option<&'c Vec<String>>,
+  pub(crate) original_urls: Option<&'c HashMap<(u32, u32, u32), String>>,
+  pub(crate) inlined_urls: Option<&'c HashMap<(u32, u32, u32), InlinedAsset>>,
   dest: &'a mut W,
   #[cfg(feature = "sourcemap")]
   #[cfg_attr(docsrs, doc(cfg(feature = "sourcemap")))]
@@ -82,8 +87,12 @@ pub struct Printer<'a, 'b, 'c, W> {
   /// the vendor prefix of whatever is being printed.
   pub(crate) vendor_prefix: VendorPrefix,
   pub(crate) in_calc: bool,
+  /// Whether `light-dark()` is lowered to the `--lightningcss-light` and `--lightningcss-dark`
+  /// custom properties. These are only defined by style rules that set `color-scheme`.
+  pub(crate) light_dark_vars: bool,
   pub(crate) css_module: Option<CssModule<'a, 'b, 'c>>,
   pub(crate) dependencies: Option<Vec<Dependency>>,
+  pub(crate) inlined_assets: Option<Vec<InlinedAsset>>,
   pub(crate) remove_imports: bool,
   pub(crate) pseudo_classes: Option<PseudoClasses<'a>>,
   context: Option<&'a StyleContext<'a, 'b>>,
@@ -94,6 +103,8 @@ impl<'a, 'b, 'c, W: std::fmt::Write + Sized> Printer<'a, 'b, 'c, W> {
   pub fn new(dest: &'a mut W, options: PrinterOptions<'a>) -> Self {
     Printer {
       sources: None,
+      original_urls: None,
+      inlined_urls: None,
       dest,
       #[cfg(feature = "sourcemap")]
       source_map: options.source_map,
@@ -111,12 +122,18 @@ impl<'a, 'b, 'c, W: std::fmt::Write + Sized> Printer<'a, 'b, 'c, W> {
       targets: options.targets,
       vendor_prefix: VendorPrefix::empty(),
       in_calc: false,
+      light_dark_vars: matches!(options.targets, Some(targets) if !Feature::LightDark.is_compatible(targets)),
       css_module: None,
       dependencies: if options.analyze_dependencies.is_some() {
         Some(Vec::new())
       } else {
         None
       },
+      inlined_assets: if options.analyze_dependencies.is_some() {
+        Some(Vec::new())
+      } else {
+        None
+      },
       remove_imports: matches!(&options.analyze_dependencies, Some(d) if d.remove_imports),
       pseudo_classes: options.pseudo_classes,
       context: None,
@@ -136,6 +153,44 @@ impl<'a, 'b, 'c, W: std::fmt::Write + Sized> Printer<'a, 'b, 'c, W> {
     }
   }
 
+  /// Creates a dependency for the given url. If the url was rewritten by the bundler,
+  /// the dependency refers to the original url instead.
+  pub(crate) fn url_dependency(&self, url: &Url) -> UrlDependency {
+    let key = (self.loc.source_index, url.loc.line, url.loc.column);
+    match self.original_urls.and_then(|original_urls| original_urls.get(&key)) {
+      Some(original) => UrlDependency::new(
+        &Url {
+          url: original.as_str().into(),
+          loc: url.loc,
+        },
+        self.filename(),
+      ),
+      None => UrlDependency::new(url, self.filename()),
+    }
+  }
+
+  /// If the given url was inlined as a `data:` URI by the bundler, records it in the list of
+  /// inlined assets and returns true. Inlined urls are not reported as dependencies.
+  pub(crate) fn record_inlined_asset(&mut self, url: &Url) -> bool {
+    let key = (self.loc.source_index, url.loc.line, url.loc.column);
+    let asset = match self.inlined_urls.and_then(|inlined_urls| inlined_urls.get(&key)) {
+      Some(asset) => asset,
+      None => return false,
+    };
+
+    if let Some(inlined_assets) = &mut self.inlined_assets {
+      // The same url may be printed more than once, e.g. when adding fallbacks.
+      if !inlined_assets
+        .iter()
+        .any(|a| a.loc.file_path == asset.loc.file_path && a.loc.start == asset.loc.start)
+      {
+        inlined_assets.push(asset.clone());
+      }
+    }
+
+    true
+  }
+
   /// Writes a raw string to the underlying destination.
   ///
   /// NOTE: Is is assumed that the string does not contain any newline characters.
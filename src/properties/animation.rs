@@ -9,9 +9,16 @@ use crate::printer::Printer;
 use crate::properties::{Property, PropertyId, VendorPrefix};
 use crate::targets::Browsers;
 use crate::traits::{Parse, PropertyHandler, Shorthand, ToCss, Zero};
+use crate::values::length::{LengthPercentage, LengthPercentageOrAuto};
 use crate::values::number::CSSNumber;
+use crate::values::percentage::Percentage;
+use crate::values::size::Size2D;
 use crate::values::string::CowArcStr;
-use crate::values::{easing::EasingFunction, ident::CustomIdent, time::Time};
+use crate::values::{
+  easing::EasingFunction,
+  ident::{CustomIdent, DashedIdent},
+  time::Time,
+};
 #[cfg(feature = "visitor")]
 use crate::visitor::Visit;
 use cssparser::*;
@@ -191,6 +198,650 @@ impl Default for AnimationFillMode {
   }
 }
 
+enum_property! {
+  /// A value for the [animation-composition](https://drafts.csswg.org/css-animations-2/#animation-composition) property.
+  pub enum AnimationComposition {
+    /// The result of compositing the effect value with the underlying value is simply the effect value.
+    Replace,
+    /// The effect value is added to the underlying value.
+    Add,
+    /// The effect value is accumulated onto the underlying value.
+    Accumulate,
+  }
+}
+
+impl Default for AnimationComposition {
+  fn default() -> Self {
+    AnimationComposition::Replace
+  }
+}
+
+enum_property! {
+  /// A scroll axis, as used in the `scroll()` and `view()` functions and the
+  /// `scroll-timeline-axis` and `view-timeline-axis` properties.
+  pub enum ScrollAxis {
+    /// The block axis of the scroll container.
+    Block,
+    /// The inline axis of the scroll container.
+    Inline,
+    /// The horizontal axis of the scroll container.
+    X,
+    /// The vertical axis of the scroll container.
+    Y,
+  }
+}
+
+impl Default for ScrollAxis {
+  fn default() -> Self {
+    ScrollAxis::Block
+  }
+}
+
+enum_property! {
+  /// A scroller, as used in the `scroll()` function.
+  pub enum Scroller {
+    /// Specifies to use the document viewport as the scroll container.
+    "root": Root,
+    /// Specifies to use the nearest ancestor scroll container.
+    "nearest": Nearest,
+    /// Specifies to use the element's own principal box as the scroll container.
+    "self": SelfElement,
+  }
+}
+
+impl Default for Scroller {
+  fn default() -> Self {
+    Scroller::Nearest
+  }
+}
+
+/// The [scroll()](https://drafts.csswg.org/scroll-animations-1/#scroll-notation) function.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct ScrollFunction {
+  /// Specifies which element to use as the scroll container.
+  pub scroller: Scroller,
+  /// Specifies which axis of the scroll container to use as the progress for the timeline.
+  pub axis: ScrollAxis,
+}
+
+impl<'i> Parse<'i> for ScrollFunction {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    input.expect_function_matching("scroll")?;
+    input.parse_nested_block(|input| {
+      let mut scroller = None;
+      let mut axis = None;
+      loop {
+        if scroller.is_none() {
+          scroller = input.try_parse(Scroller::parse).ok();
+        }
+
+        if axis.is_none() {
+          axis = input.try_parse(ScrollAxis::parse).ok();
+          if axis.is_some() {
+            continue;
+          }
+        }
+        break;
+      }
+
+      Ok(ScrollFunction {
+        scroller: scroller.unwrap_or_default(),
+        axis: axis.unwrap_or_default(),
+      })
+    })
+  }
+}
+
+impl ToCss for ScrollFunction {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    dest.write_str("scroll(")?;
+
+    let mut needs_space = false;
+    if self.scroller != Scroller::default() {
+      self.scroller.to_css(dest)?;
+      needs_space = true;
+    }
+
+    if self.axis != ScrollAxis::default() {
+      if needs_space {
+        dest.write_char(' ')?;
+      }
+      self.axis.to_css(dest)?;
+    }
+
+    dest.write_char(')')
+  }
+}
+
+/// The [view()](https://drafts.csswg.org/scroll-animations-1/#view-notation) function.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct ViewFunction {
+  /// Specifies which axis of the scroll container to use as the progress for the timeline.
+  pub axis: ScrollAxis,
+  /// Provides an adjustment of the view progress visibility range.
+  pub inset: Size2D<LengthPercentageOrAuto>,
+}
+
+impl Default for ViewFunction {
+  fn default() -> Self {
+    ViewFunction {
+      axis: ScrollAxis::default(),
+      inset: Size2D(LengthPercentageOrAuto::Auto, LengthPercentageOrAuto::Auto),
+    }
+  }
+}
+
+impl<'i> Parse<'i> for ViewFunction {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    input.expect_function_matching("view")?;
+    input.parse_nested_block(|input| {
+      let (axis, inset) = parse_axis_and_inset(input);
+      Ok(ViewFunction {
+        axis: axis.unwrap_or_default(),
+        inset: inset.unwrap_or_else(|| ViewFunction::default().inset),
+      })
+    })
+  }
+}
+
+/// Parses `[ <axis> || <'view-timeline-inset'> ]?`.
+fn parse_axis_and_inset<'i, 't>(
+  input: &mut Parser<'i, 't>,
+) -> (Option<ScrollAxis>, Option<Size2D<LengthPercentageOrAuto>>) {
+  let mut axis = None;
+  let mut inset = None;
+  loop {
+    if axis.is_none() {
+      axis = input.try_parse(ScrollAxis::parse).ok();
+    }
+
+    if inset.is_none() {
+      inset = input.try_parse(Size2D::parse).ok();
+      if inset.is_some() {
+        continue;
+      }
+    }
+    break;
+  }
+  (axis, inset)
+}
+
+impl ToCss for ViewFunction {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    dest.write_str("view(")?;
+    self.write_axis_and_inset(dest, false)?;
+    dest.write_char(')')
+  }
+}
+
+impl ViewFunction {
+  fn write_axis_and_inset<W>(&self, dest: &mut Printer<W>, mut needs_space: bool) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if self.axis != ScrollAxis::default() {
+      if needs_space {
+        dest.write_char(' ')?;
+      }
+      self.axis.to_css(dest)?;
+      needs_space = true;
+    }
+
+    if self.inset != ViewFunction::default().inset {
+      if needs_space {
+        dest.write_char(' ')?;
+      }
+      self.inset.to_css(dest)?;
+    }
+
+    Ok(())
+  }
+}
+
+/// A value for the [animation-timeline](https://drafts.csswg.org/css-animations-2/#animation-timeline) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum AnimationTimeline<'i> {
+  /// The animation's timeline is the document's default timeline.
+  Auto,
+  /// The animation is not associated with a timeline.
+  None,
+  /// A named scroll or view progress timeline.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  DashedIdent(DashedIdent<'i>),
+  /// An anonymous scroll progress timeline.
+  Scroll(ScrollFunction),
+  /// An anonymous view progress timeline.
+  View(ViewFunction),
+}
+
+impl<'i> Default for AnimationTimeline<'i> {
+  fn default() -> Self {
+    AnimationTimeline::Auto
+  }
+}
+
+impl<'i> Parse<'i> for AnimationTimeline<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      return Ok(AnimationTimeline::Auto);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(AnimationTimeline::None);
+    }
+
+    if let Ok(name) = input.try_parse(DashedIdent::parse) {
+      return Ok(AnimationTimeline::DashedIdent(name));
+    }
+
+    if let Ok(scroll) = input.try_parse(ScrollFunction::parse) {
+      return Ok(AnimationTimeline::Scroll(scroll));
+    }
+
+    let view = ViewFunction::parse(input)?;
+    Ok(AnimationTimeline::View(view))
+  }
+}
+
+impl<'i> ToCss for AnimationTimeline<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      AnimationTimeline::Auto => dest.write_str("auto"),
+      AnimationTimeline::None => dest.write_str("none"),
+      AnimationTimeline::DashedIdent(name) => name.to_css(dest),
+      AnimationTimeline::Scroll(scroll) => scroll.to_css(dest),
+      AnimationTimeline::View(view) => view.to_css(dest),
+    }
+  }
+}
+
+enum_property! {
+  /// A [named timeline range](https://drafts.csswg.org/scroll-animations-1/#named-timeline-range).
+  pub enum TimelineRangeName {
+    /// Represents the full range of the view progress timeline.
+    "cover": Cover,
+    /// Represents the range during which the principal box is either fully contained by,
+    /// or fully covers, its view progress visibility range within the scrollport.
+    "contain": Contain,
+    /// Represents the range during which the principal box is entering the view progress visibility range.
+    "entry": Entry,
+    /// Represents the range during which the principal box is exiting the view progress visibility range.
+    "exit": Exit,
+    /// Represents the range during which the principal box crosses the end border edge.
+    "entry-crossing": EntryCrossing,
+    /// Represents the range during which the principal box crosses the start border edge.
+    "exit-crossing": ExitCrossing,
+  }
+}
+
+/// A value for the [animation-range-start](https://drafts.csswg.org/scroll-animations-1/#animation-range-start)
+/// and [animation-range-end](https://drafts.csswg.org/scroll-animations-1/#animation-range-end) properties.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum AnimationAttachmentRange {
+  /// The start or end of the animation's attachment range is the start or end of its associated timeline.
+  Normal,
+  /// The animation attachment range starts or ends at the specified point on the timeline,
+  /// measured from the start of the timeline.
+  #[cfg_attr(feature = "serde", serde(rename = "length-percentage"))]
+  LengthPercentage {
+    /// The offset from the start of the timeline.
+    value: LengthPercentage,
+  },
+  /// The animation attachment range starts or ends at the specified point,
+  /// measured from the start of the specified named timeline range.
+  TimelineRange {
+    /// The name of the timeline range.
+    name: TimelineRangeName,
+    /// The offset from the start of the named timeline range.
+    offset: LengthPercentage,
+  },
+}
+
+impl AnimationAttachmentRange {
+  fn parse<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    default_offset: f32,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(AnimationAttachmentRange::Normal);
+    }
+
+    if let Ok(value) = input.try_parse(LengthPercentage::parse) {
+      return Ok(AnimationAttachmentRange::LengthPercentage { value });
+    }
+
+    let name = TimelineRangeName::parse(input)?;
+    let offset = input
+      .try_parse(LengthPercentage::parse)
+      .unwrap_or(LengthPercentage::Percentage(Percentage(default_offset)));
+    Ok(AnimationAttachmentRange::TimelineRange { name, offset })
+  }
+
+  fn to_css<W>(&self, dest: &mut Printer<W>, default_offset: f32) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      AnimationAttachmentRange::Normal => dest.write_str("normal"),
+      AnimationAttachmentRange::LengthPercentage { value } => value.to_css(dest),
+      AnimationAttachmentRange::TimelineRange { name, offset } => {
+        name.to_css(dest)?;
+        if *offset != LengthPercentage::Percentage(Percentage(default_offset)) {
+          dest.write_char(' ')?;
+          offset.to_css(dest)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+impl Default for AnimationAttachmentRange {
+  fn default() -> Self {
+    AnimationAttachmentRange::Normal
+  }
+}
+
+/// A value for the [animation-range-start](https://drafts.csswg.org/scroll-animations-1/#animation-range-start) property.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct AnimationRangeStart(pub AnimationAttachmentRange);
+
+impl<'i> Parse<'i> for AnimationRangeStart {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let range = AnimationAttachmentRange::parse(input, 0.0)?;
+    Ok(AnimationRangeStart(range))
+  }
+}
+
+impl ToCss for AnimationRangeStart {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    self.0.to_css(dest, 0.0)
+  }
+}
+
+/// A value for the [animation-range-end](https://drafts.csswg.org/scroll-animations-1/#animation-range-end) property.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct AnimationRangeEnd(pub AnimationAttachmentRange);
+
+impl<'i> Parse<'i> for AnimationRangeEnd {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let range = AnimationAttachmentRange::parse(input, 1.0)?;
+    Ok(AnimationRangeEnd(range))
+  }
+}
+
+impl ToCss for AnimationRangeEnd {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    self.0.to_css(dest, 1.0)
+  }
+}
+
+define_list_shorthand! {
+  /// A value for the [animation-range](https://drafts.csswg.org/scroll-animations-1/#animation-range) shorthand property.
+  pub struct AnimationRange {
+    /// The start of the animation's attachment range.
+    start: AnimationRangeStart(AnimationRangeStart),
+    /// The end of the animation's attachment range.
+    end: AnimationRangeEnd(AnimationRangeEnd),
+  }
+}
+
+impl AnimationRange {
+  /// Returns the end value implied when only a start value is specified.
+  fn implied_end(start: &AnimationRangeStart) -> AnimationRangeEnd {
+    match &start.0 {
+      AnimationAttachmentRange::TimelineRange { name, .. } => {
+        AnimationRangeEnd(AnimationAttachmentRange::TimelineRange {
+          name: *name,
+          offset: LengthPercentage::Percentage(Percentage(1.0)),
+        })
+      }
+      _ => AnimationRangeEnd(AnimationAttachmentRange::Normal),
+    }
+  }
+}
+
+impl<'i> Parse<'i> for AnimationRange {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let start = AnimationRangeStart::parse(input)?;
+    let end = input
+      .try_parse(AnimationRangeEnd::parse)
+      .unwrap_or_else(|_| AnimationRange::implied_end(&start));
+    Ok(AnimationRange { start, end })
+  }
+}
+
+impl ToCss for AnimationRange {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    self.start.to_css(dest)?;
+    if self.end != AnimationRange::implied_end(&self.start) {
+      dest.write_char(' ')?;
+      self.end.to_css(dest)?;
+    }
+    Ok(())
+  }
+}
+
+/// A timeline name, as used in the [scroll-timeline-name](https://drafts.csswg.org/scroll-animations-1/#scroll-timeline-name)
+/// and [view-timeline-name](https://drafts.csswg.org/scroll-animations-1/#view-timeline-name) properties.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum TimelineName<'i> {
+  /// The timeline has no name.
+  None,
+  /// A named timeline.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  DashedIdent(DashedIdent<'i>),
+}
+
+impl<'i> Parse<'i> for TimelineName<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(TimelineName::None);
+    }
+
+    let name = DashedIdent::parse(input)?;
+    Ok(TimelineName::DashedIdent(name))
+  }
+}
+
+impl<'i> ToCss for TimelineName<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      TimelineName::None => dest.write_str("none"),
+      TimelineName::DashedIdent(name) => name.to_css(dest),
+    }
+  }
+}
+
+define_list_shorthand! {
+  /// A value for the [scroll-timeline](https://drafts.csswg.org/scroll-animations-1/#scroll-timeline-shorthand) shorthand property.
+  #[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+  pub struct ScrollTimeline<'i> {
+    /// The name of the scroll progress timeline.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    name: ScrollTimelineName(TimelineName<'i>),
+    /// The axis of the scroll progress timeline.
+    axis: ScrollTimelineAxis(ScrollAxis),
+  }
+}
+
+impl<'i> Parse<'i> for ScrollTimeline<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let name = TimelineName::parse(input)?;
+    let axis = input.try_parse(ScrollAxis::parse).unwrap_or_default();
+    Ok(ScrollTimeline { name, axis })
+  }
+}
+
+impl<'i> ToCss for ScrollTimeline<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    self.name.to_css(dest)?;
+    if self.axis != ScrollAxis::default() {
+      dest.write_char(' ')?;
+      self.axis.to_css(dest)?;
+    }
+    Ok(())
+  }
+}
+
+define_list_shorthand! {
+  /// A value for the [view-timeline](https://drafts.csswg.org/scroll-animations-1/#view-timeline-shorthand) shorthand property.
+  #[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+  pub struct ViewTimeline<'i> {
+    /// The name of the view progress timeline.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    name: ViewTimelineName(TimelineName<'i>),
+    /// The axis of the view progress timeline.
+    axis: ViewTimelineAxis(ScrollAxis),
+    /// The inset adjustment of the view progress visibility range.
+    inset: ViewTimelineInset(Size2D<LengthPercentageOrAuto>),
+  }
+}
+
+impl<'i> Parse<'i> for ViewTimeline<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let name = TimelineName::parse(input)?;
+    let (axis, inset) = parse_axis_and_inset(input);
+    Ok(ViewTimeline {
+      name,
+      axis: axis.unwrap_or_default(),
+      inset: inset.unwrap_or_else(|| ViewFunction::default().inset),
+    })
+  }
+}
+
+impl<'i> ToCss for ViewTimeline<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    self.name.to_css(dest)?;
+    let view = ViewFunction {
+      axis: self.axis,
+      inset: self.inset.clone(),
+    };
+    view.write_axis_and_inset(dest, true)
+  }
+}
+
+/// A value for the [timeline-scope](https://drafts.csswg.org/scroll-animations-1/#timeline-scope) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum TimelineScope<'i> {
+  /// No timeline names are in scope.
+  None,
+  /// All named timelines in the subtree are in scope.
+  All,
+  /// The named timelines are in scope.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  DashedIdents(SmallVec<[DashedIdent<'i>; 1]>),
+}
+
+impl<'i> Parse<'i> for TimelineScope<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(TimelineScope::None);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("all")).is_ok() {
+      return Ok(TimelineScope::All);
+    }
+
+    let names = input.parse_comma_separated(DashedIdent::parse)?;
+    Ok(TimelineScope::DashedIdents(names.into()))
+  }
+}
+
+impl<'i> ToCss for TimelineScope<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      TimelineScope::None => dest.write_str("none"),
+      TimelineScope::All => dest.write_str("all"),
+      TimelineScope::DashedIdents(names) => {
+        let mut first = true;
+        for name in names {
+          if first {
+            first = false;
+          } else {
+            dest.delim(',', false)?;
+          }
+          name.to_css(dest)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
 define_list_shorthand! {
   /// A value for the [animation](https://drafts.csswg.org/css-animations/#animation) shorthand property.
   #[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
@@ -331,6 +982,10 @@ pub(crate) struct AnimationHandler<'i> {
   play_states: Option<(SmallVec<[AnimationPlayState; 1]>, VendorPrefix)>,
   delays: Option<(SmallVec<[Time; 1]>, VendorPrefix)>,
   fill_modes: Option<(SmallVec<[AnimationFillMode; 1]>, VendorPrefix)>,
+  compositions: Option<SmallVec<[AnimationComposition; 1]>>,
+  timelines: Option<SmallVec<[AnimationTimeline<'i>; 1]>>,
+  range_starts: Option<SmallVec<[AnimationRangeStart; 1]>>,
+  range_ends: Option<SmallVec<[AnimationRangeEnd; 1]>>,
   has_any: bool,
 }
 
@@ -388,6 +1043,27 @@ impl<'i> PropertyHandler<'i> for AnimationHandler<'i> {
       AnimationPlayState(val, vp) => property!(play_states, val, vp),
       AnimationDelay(val, vp) => property!(delays, val, vp),
       AnimationFillMode(val, vp) => property!(fill_modes, val, vp),
+      AnimationComposition(val) => {
+        self.compositions = Some(val.clone());
+        self.has_any = true;
+      }
+      AnimationTimeline(val) => {
+        self.timelines = Some(val.clone());
+        self.has_any = true;
+      }
+      AnimationRangeStart(val) => {
+        self.range_starts = Some(val.clone());
+        self.has_any = true;
+      }
+      AnimationRangeEnd(val) => {
+        self.range_ends = Some(val.clone());
+        self.has_any = true;
+      }
+      AnimationRange(val) => {
+        self.range_starts = Some(val.iter().map(|r| r.start.clone()).collect());
+        self.range_ends = Some(val.iter().map(|r| r.end.clone()).collect());
+        self.has_any = true;
+      }
       Animation(val, vp) => {
         let names = val.iter().map(|b| b.name.clone()).collect();
         maybe_flush!(names, &names, vp);
@@ -421,6 +1097,15 @@ impl<'i> PropertyHandler<'i> for AnimationHandler<'i> {
         property!(play_states, &play_states, vp);
         property!(delays, &delays, vp);
         property!(fill_modes, &fill_modes, vp);
+
+        // The unprefixed animation shorthand resets these longhands to their initial values,
+        // so any earlier declarations are overridden.
+        if vp.contains(VendorPrefix::None) {
+          self.compositions = None;
+          self.timelines = None;
+          self.range_starts = None;
+          self.range_ends = None;
+        }
       }
       Unparsed(val) if is_animation_property(&val.property_id) => {
         self.flush(dest);
@@ -453,6 +1138,10 @@ impl<'i> AnimationHandler<'i> {
     let mut play_states = std::mem::take(&mut self.play_states);
     let mut delays = std::mem::take(&mut self.delays);
     let mut fill_modes = std::mem::take(&mut self.fill_modes);
+    let compositions = std::mem::take(&mut self.compositions);
+    let timelines = std::mem::take(&mut self.timelines);
+    let mut range_starts = std::mem::take(&mut self.range_starts);
+    let mut range_ends = std::mem::take(&mut self.range_ends);
 
     if let (
       Some((names, names_vp)),
@@ -559,6 +1248,36 @@ impl<'i> AnimationHandler<'i> {
     prop!(play_states, AnimationPlayState);
     prop!(delays, AnimationDelay);
     prop!(fill_modes, AnimationFillMode);
+
+    // These longhands are reset by the animation shorthand, so they must come after it.
+    if let Some(compositions) = compositions {
+      dest.push(Property::AnimationComposition(compositions));
+    }
+
+    if let Some(timelines) = timelines {
+      dest.push(Property::AnimationTimeline(timelines));
+    }
+
+    if let (Some(starts), Some(ends)) = (&mut range_starts, &mut range_ends) {
+      if starts.len() == ends.len() {
+        let ranges = starts
+          .drain(..)
+          .zip(ends.drain(..))
+          .map(|(start, end)| AnimationRange { start, end })
+          .collect();
+        dest.push(Property::AnimationRange(ranges));
+        range_starts = None;
+        range_ends = None;
+      }
+    }
+
+    if let Some(starts) = range_starts {
+      dest.push(Property::AnimationRangeStart(starts));
+    }
+
+    if let Some(ends) = range_ends {
+      dest.push(Property::AnimationRangeEnd(ends));
+    }
   }
 }
 
@@ -573,7 +1292,12 @@ fn is_animation_property(property_id: &PropertyId) -> bool {
     | PropertyId::AnimationPlayState(_)
     | PropertyId::AnimationDelay(_)
     | PropertyId::AnimationFillMode(_)
-    | PropertyId::Animation(_) => true,
+    | PropertyId::Animation(_)
+    | PropertyId::AnimationComposition
+    | PropertyId::AnimationTimeline
+    | PropertyId::AnimationRangeStart
+    | PropertyId::AnimationRangeEnd
+    | PropertyId::AnimationRange => true,
     _ => false,
   }
 }
@@ -0,0 +1,437 @@
+//! CSS properties related to multi-column layout.
+
+use super::border::{BorderSideWidth, GenericBorder, LineStyle};
+use super::{Property, PropertyId};
+use crate::context::PropertyHandlerContext;
+use crate::declaration::{DeclarationBlock, DeclarationList};
+use crate::error::{ParserError, PrinterError};
+use crate::macros::{define_shorthand, enum_property, impl_shorthand};
+use crate::prefixes::Feature;
+use crate::printer::Printer;
+use crate::targets::Browsers;
+use crate::traits::{FallbackValues, Parse, PropertyHandler, Shorthand, ToCss, TrySign};
+use crate::values::color::CssColor;
+use crate::values::length::Length;
+use crate::values::number::CSSInteger;
+use crate::vendor_prefix::VendorPrefix;
+#[cfg(feature = "visitor")]
+use crate::visitor::Visit;
+use cssparser::*;
+
+/// A value for the [column-width](https://www.w3.org/TR/css-multicol-1/#cw) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum ColumnWidth {
+  /// The column width is determined by other properties.
+  Auto,
+  /// An explicit column width.
+  Length(Length),
+}
+
+impl Default for ColumnWidth {
+  fn default() -> ColumnWidth {
+    ColumnWidth::Auto
+  }
+}
+
+impl<'i> Parse<'i> for ColumnWidth {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      return Ok(ColumnWidth::Auto);
+    }
+
+    let location = input.current_source_location();
+    let length = Length::parse(input)?;
+    if length.is_sign_negative() {
+      return Err(location.new_custom_error(ParserError::InvalidValue));
+    }
+    Ok(ColumnWidth::Length(length))
+  }
+}
+
+impl ToCss for ColumnWidth {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      ColumnWidth::Auto => dest.write_str("auto"),
+      ColumnWidth::Length(length) => length.to_css(dest),
+    }
+  }
+}
+
+/// A value for the [column-count](https://www.w3.org/TR/css-multicol-1/#cc) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum ColumnCount {
+  /// The number of columns is determined by other properties.
+  Auto,
+  /// An explicit number of columns.
+  Integer(CSSInteger),
+}
+
+impl Default for ColumnCount {
+  fn default() -> ColumnCount {
+    ColumnCount::Auto
+  }
+}
+
+impl<'i> Parse<'i> for ColumnCount {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      return Ok(ColumnCount::Auto);
+    }
+
+    let location = input.current_source_location();
+    let count = CSSInteger::parse(input)?;
+    if count <= 0 {
+      return Err(location.new_custom_error(ParserError::InvalidValue));
+    }
+    Ok(ColumnCount::Integer(count))
+  }
+}
+
+impl ToCss for ColumnCount {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      ColumnCount::Auto => dest.write_str("auto"),
+      ColumnCount::Integer(count) => count.to_css(dest),
+    }
+  }
+}
+
+define_shorthand! {
+  /// A value for the [columns](https://www.w3.org/TR/css-multicol-1/#columns) shorthand property.
+  pub struct Columns(VendorPrefix) {
+    /// The column width.
+    width: ColumnWidth(ColumnWidth, VendorPrefix),
+    /// The column count.
+    count: ColumnCount(ColumnCount, VendorPrefix),
+  }
+}
+
+impl<'i> Parse<'i> for Columns {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut width = None;
+    let mut count = None;
+    let mut autos = 0;
+    for _ in 0..2 {
+      // `auto` is valid for both properties, so we don't know which one it applies to yet.
+      if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+        autos += 1;
+        continue;
+      }
+
+      if count.is_none() {
+        if let Ok(value) = input.try_parse(ColumnCount::parse) {
+          count = Some(value);
+          continue;
+        }
+      }
+
+      if width.is_none() {
+        if let Ok(value) = input.try_parse(ColumnWidth::parse) {
+          width = Some(value);
+          continue;
+        }
+      }
+
+      break;
+    }
+
+    if autos == 0 && width.is_none() && count.is_none() {
+      return Err(input.new_custom_error(ParserError::InvalidDeclaration));
+    }
+
+    Ok(Columns {
+      width: width.unwrap_or_default(),
+      count: count.unwrap_or_default(),
+    })
+  }
+}
+
+impl ToCss for Columns {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if self.width == ColumnWidth::Auto && self.count == ColumnCount::Auto {
+      return dest.write_str("auto");
+    }
+
+    let mut needs_space = false;
+    if self.width != ColumnWidth::Auto {
+      self.width.to_css(dest)?;
+      needs_space = true;
+    }
+
+    if self.count != ColumnCount::Auto {
+      if needs_space {
+        dest.write_char(' ')?;
+      }
+      self.count.to_css(dest)?;
+    }
+
+    Ok(())
+  }
+}
+
+/// A value for the [column-rule](https://www.w3.org/TR/css-multicol-1/#column-rule) shorthand property.
+pub type ColumnRule = GenericBorder<LineStyle, 12>;
+
+impl_shorthand! {
+  ColumnRule(ColumnRule, VendorPrefix) {
+    width: [ColumnRuleWidth(VendorPrefix)],
+    style: [ColumnRuleStyle(VendorPrefix)],
+    color: [ColumnRuleColor(VendorPrefix)],
+  }
+}
+
+enum_property! {
+  /// A value for the [column-span](https://www.w3.org/TR/css-multicol-1/#column-span) property.
+  pub enum ColumnSpan {
+    /// The element does not span multiple columns.
+    None,
+    /// The element spans across all columns.
+    All,
+  }
+}
+
+enum_property! {
+  /// A value for the [column-fill](https://www.w3.org/TR/css-multicol-1/#cf) property.
+  pub enum ColumnFill {
+    /// Columns are filled sequentially.
+    "auto": Auto,
+    /// Content is balanced across columns, except in the last fragment.
+    "balance": Balance,
+    /// Content is balanced across columns in all fragments.
+    "balance-all": BalanceAll,
+  }
+}
+
+#[derive(Default, Debug)]
+pub(crate) struct ColumnHandler {
+  targets: Option<Browsers>,
+  width: Option<(ColumnWidth, VendorPrefix)>,
+  count: Option<(ColumnCount, VendorPrefix)>,
+  rule_width: Option<(BorderSideWidth, VendorPrefix)>,
+  rule_style: Option<(LineStyle, VendorPrefix)>,
+  rule_color: Option<(CssColor, VendorPrefix)>,
+  span: Option<(ColumnSpan, VendorPrefix)>,
+  fill: Option<(ColumnFill, VendorPrefix)>,
+  has_any: bool,
+}
+
+impl ColumnHandler {
+  pub fn new(targets: Option<Browsers>) -> ColumnHandler {
+    ColumnHandler {
+      targets,
+      ..ColumnHandler::default()
+    }
+  }
+}
+
+impl<'i> PropertyHandler<'i> for ColumnHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    _: &mut PropertyHandlerContext<'i, '_>,
+  ) -> bool {
+    use Property::*;
+
+    macro_rules! maybe_flush {
+      ($prop: ident, $val: expr, $vp: ident) => {{
+        // If two vendor prefixes for the same property have different
+        // values, we need to flush what we have immediately to preserve order.
+        if let Some((val, prefixes)) = &self.$prop {
+          if val != $val && !prefixes.contains(*$vp) {
+            self.flush(dest);
+          }
+        }
+      }};
+    }
+
+    macro_rules! property {
+      ($prop: ident, $val: expr, $vp: ident) => {{
+        maybe_flush!($prop, $val, $vp);
+
+        // Otherwise, update the value and add the prefix.
+        if let Some((val, prefixes)) = &mut self.$prop {
+          *val = $val.clone();
+          *prefixes |= *$vp;
+        } else {
+          self.$prop = Some(($val.clone(), *$vp));
+          self.has_any = true;
+        }
+      }};
+    }
+
+    match property {
+      ColumnWidth(val, vp) => property!(width, val, vp),
+      ColumnCount(val, vp) => property!(count, val, vp),
+      Columns(val, vp) => {
+        maybe_flush!(width, &val.width, vp);
+        maybe_flush!(count, &val.count, vp);
+        property!(width, &val.width, vp);
+        property!(count, &val.count, vp);
+      }
+      ColumnRuleWidth(val, vp) => property!(rule_width, val, vp),
+      ColumnRuleStyle(val, vp) => property!(rule_style, val, vp),
+      ColumnRuleColor(val, vp) => property!(rule_color, val, vp),
+      ColumnRule(val, vp) => {
+        maybe_flush!(rule_width, &val.width, vp);
+        maybe_flush!(rule_style, &val.style, vp);
+        maybe_flush!(rule_color, &val.color, vp);
+        property!(rule_width, &val.width, vp);
+        property!(rule_style, &val.style, vp);
+        property!(rule_color, &val.color, vp);
+      }
+      ColumnSpan(val, vp) => property!(span, val, vp),
+      ColumnFill(val, vp) => property!(fill, val, vp),
+      Unparsed(val) if is_column_property(&val.property_id) => {
+        self.flush(dest);
+        dest.push(property.clone())
+      }
+      _ => return false,
+    }
+
+    true
+  }
+
+  fn finalize(&mut self, dest: &mut DeclarationList, _: &mut PropertyHandlerContext<'i, '_>) {
+    self.flush(dest);
+  }
+}
+
+impl ColumnHandler {
+  fn flush(&mut self, dest: &mut DeclarationList) {
+    if !self.has_any {
+      return;
+    }
+
+    self.has_any = false;
+
+    let mut width = std::mem::take(&mut self.width);
+    let mut count = std::mem::take(&mut self.count);
+    let mut rule_width = std::mem::take(&mut self.rule_width);
+    let mut rule_style = std::mem::take(&mut self.rule_style);
+    let mut rule_color = std::mem::take(&mut self.rule_color);
+    let span = std::mem::take(&mut self.span);
+    let fill = std::mem::take(&mut self.fill);
+
+    // Gets prefixes for standard properties.
+    macro_rules! prefixes {
+      ($prop: ident, $prefix: expr) => {{
+        let mut prefix = $prefix;
+        if prefix.contains(VendorPrefix::None) {
+          if let Some(targets) = self.targets {
+            prefix = Feature::$prop.prefixes_for(targets);
+          }
+        }
+        prefix
+      }};
+    }
+
+    macro_rules! single_property {
+      ($prop: ident, $key: ident) => {
+        if let Some((val, prefix)) = $key {
+          if !prefix.is_empty() {
+            dest.push(Property::$prop(val, prefixes!($prop, prefix)))
+          }
+        }
+      };
+    }
+
+    if let (Some((width, width_prefix)), Some((count, count_prefix))) = (&mut width, &mut count) {
+      let intersection = *width_prefix & *count_prefix;
+      if !intersection.is_empty() {
+        dest.push(Property::Columns(
+          Columns {
+            width: width.clone(),
+            count: count.clone(),
+          },
+          prefixes!(Columns, intersection),
+        ));
+        width_prefix.remove(intersection);
+        count_prefix.remove(intersection);
+      }
+    }
+
+    single_property!(ColumnWidth, width);
+    single_property!(ColumnCount, count);
+
+    if let (Some((width, width_prefix)), Some((style, style_prefix)), Some((color, color_prefix))) =
+      (&mut rule_width, &mut rule_style, &mut rule_color)
+    {
+      let intersection = *width_prefix & *style_prefix & *color_prefix;
+      if !intersection.is_empty() {
+        let prefix = prefixes!(ColumnRule, intersection);
+        let mut rule = ColumnRule {
+          width: width.clone(),
+          style: *style,
+          color: color.clone(),
+        };
+        if let Some(targets) = self.targets {
+          for fallback in rule.get_fallbacks(targets) {
+            dest.push(Property::ColumnRule(fallback, prefix));
+          }
+        }
+        dest.push(Property::ColumnRule(rule, prefix));
+        width_prefix.remove(intersection);
+        style_prefix.remove(intersection);
+        color_prefix.remove(intersection);
+      }
+    }
+
+    single_property!(ColumnRuleWidth, rule_width);
+    single_property!(ColumnRuleStyle, rule_style);
+
+    if let Some((mut val, prefix)) = rule_color {
+      if !prefix.is_empty() {
+        let prefix = prefixes!(ColumnRuleColor, prefix);
+        if let Some(targets) = self.targets {
+          for fallback in val.get_fallbacks(targets) {
+            dest.push(Property::ColumnRuleColor(fallback, prefix));
+          }
+        }
+        dest.push(Property::ColumnRuleColor(val, prefix))
+      }
+    }
+
+    single_property!(ColumnSpan, span);
+    single_property!(ColumnFill, fill);
+  }
+}
+
+#[inline]
+fn is_column_property(property_id: &PropertyId) -> bool {
+  match property_id {
+    PropertyId::ColumnWidth(_)
+    | PropertyId::ColumnCount(_)
+    | PropertyId::Columns(_)
+    | PropertyId::ColumnRuleWidth(_)
+    | PropertyId::ColumnRuleStyle(_)
+    | PropertyId::ColumnRuleColor(_)
+    | PropertyId::ColumnRule(_)
+    | PropertyId::ColumnSpan(_)
+    | PropertyId::ColumnFill(_) => true,
+    _ => false,
+  }
+}
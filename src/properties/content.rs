@@ -0,0 +1,355 @@
+//! CSS properties related to generated content.
+
+use super::list::{serialize_counter_name, CounterStyle, PredefinedCounterStyle};
+use crate::error::{ParserError, PrinterError};
+use crate::macros::enum_property;
+use crate::printer::Printer;
+use crate::traits::{Parse, ToCss};
+use crate::values::ident::{CustomIdent, Ident};
+use crate::values::image::Image;
+use crate::values::string::CSSString;
+#[cfg(feature = "visitor")]
+use crate::visitor::Visit;
+use cssparser::*;
+
+/// A value for the [content](https://drafts.csswg.org/css-content-3/#content-property) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum Content<'i> {
+  /// The `normal` keyword.
+  Normal,
+  /// The `none` keyword.
+  None,
+  /// A list of content items, with optional alternative text.
+  Items {
+    /// The content items to render.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    items: Vec<ContentItem<'i>>,
+    /// Alternative text for accessibility. Only strings, counters, and `attr()` are allowed.
+    alt: Vec<ContentItem<'i>>,
+  },
+}
+
+impl<'i> Parse<'i> for Content<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(Content::Normal);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(Content::None);
+    }
+
+    let mut items = Vec::new();
+    while let Ok(item) = input.try_parse(ContentItem::parse) {
+      items.push(item);
+    }
+
+    if items.is_empty() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    let mut alt = Vec::new();
+    if input.try_parse(|input| input.expect_delim('/')).is_ok() {
+      while let Ok(item) = input.try_parse(ContentItem::parse_alt) {
+        alt.push(item);
+      }
+
+      if alt.is_empty() {
+        return Err(input.new_custom_error(ParserError::InvalidValue));
+      }
+    }
+
+    Ok(Content::Items { items, alt })
+  }
+}
+
+impl<'i> ToCss for Content<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      Content::Normal => dest.write_str("normal"),
+      Content::None => dest.write_str("none"),
+      Content::Items { items, alt } => {
+        serialize_items(items, dest)?;
+        if !alt.is_empty() {
+          dest.delim('/', true)?;
+          serialize_items(alt, dest)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+fn serialize_items<W>(items: &[ContentItem], dest: &mut Printer<W>) -> Result<(), PrinterError>
+where
+  W: std::fmt::Write,
+{
+  let mut first = true;
+  for item in items {
+    if first {
+      first = false;
+    } else {
+      dest.write_char(' ')?;
+    }
+    item.to_css(dest)?;
+  }
+  Ok(())
+}
+
+/// An item within the [content](https://drafts.csswg.org/css-content-3/#content-property) property.
+///
+/// See [Content](Content).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum ContentItem<'i> {
+  /// A string.
+  #[cfg_attr(
+    feature = "serde",
+    serde(borrow, with = "crate::serialization::ValueWrapper::<CSSString>")
+  )]
+  String(CSSString<'i>),
+  /// An image, e.g. `url()` or a gradient.
+  #[cfg_attr(feature = "serde", serde(with = "crate::serialization::ValueWrapper::<Image>"))]
+  Image(Image<'i>),
+  /// A [`counter()`](https://drafts.csswg.org/css-lists-3/#funcdef-counter) function.
+  Counter {
+    /// The name of the counter.
+    name: CustomIdent<'i>,
+    /// The counter style used to format the value.
+    style: CounterStyle<'i>,
+  },
+  /// A [`counters()`](https://drafts.csswg.org/css-lists-3/#funcdef-counters) function.
+  Counters {
+    /// The name of the counter.
+    name: CustomIdent<'i>,
+    /// The string used to join the values of nested counters.
+    separator: CSSString<'i>,
+    /// The counter style used to format the values.
+    style: CounterStyle<'i>,
+  },
+  /// An [`attr()`](https://drafts.csswg.org/css-values-5/#attr-notation) function.
+  #[cfg_attr(feature = "serde", serde(with = "crate::serialization::ValueWrapper::<Ident>"))]
+  Attr(Ident<'i>),
+  /// A quote keyword.
+  #[cfg_attr(feature = "serde", serde(with = "crate::serialization::ValueWrapper::<Quote>"))]
+  Quote(Quote),
+}
+
+impl<'i> Parse<'i> for ContentItem<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if let Ok(quote) = input.try_parse(Quote::parse) {
+      return Ok(ContentItem::Quote(quote));
+    }
+
+    if let Ok(image) = input.try_parse(Image::parse) {
+      return Ok(ContentItem::Image(image));
+    }
+
+    Self::parse_alt(input)
+  }
+}
+
+impl<'i> ContentItem<'i> {
+  /// Parses an item that is valid within alternative text, i.e. a string, counter, or `attr()`.
+  fn parse_alt<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if let Ok(s) = input.try_parse(CSSString::parse) {
+      return Ok(ContentItem::String(s));
+    }
+
+    let location = input.current_source_location();
+    let f = input.expect_function()?.clone();
+    match_ignore_ascii_case! { &f,
+      "counter" => input.parse_nested_block(|input| {
+        let name = CustomIdent::parse(input)?;
+        let style = parse_counter_style(input)?;
+        Ok(ContentItem::Counter { name, style })
+      }),
+      "counters" => input.parse_nested_block(|input| {
+        let name = CustomIdent::parse(input)?;
+        input.expect_comma()?;
+        let separator = CSSString::parse(input)?;
+        let style = parse_counter_style(input)?;
+        Ok(ContentItem::Counters { name, separator, style })
+      }),
+      "attr" => input.parse_nested_block(|input| {
+        let name = Ident::parse(input)?;
+        Ok(ContentItem::Attr(name))
+      }),
+      _ => Err(location.new_unexpected_token_error(Token::Function(f.clone())))
+    }
+  }
+}
+
+fn parse_counter_style<'i, 't>(
+  input: &mut Parser<'i, 't>,
+) -> Result<CounterStyle<'i>, ParseError<'i, ParserError<'i>>> {
+  if input.try_parse(|input| input.expect_comma()).is_ok() {
+    CounterStyle::parse(input)
+  } else {
+    Ok(CounterStyle::Predefined(PredefinedCounterStyle::Decimal))
+  }
+}
+
+impl<'i> ToCss for ContentItem<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      ContentItem::String(s) => s.to_css(dest),
+      ContentItem::Image(image) => image.to_css(dest),
+      ContentItem::Counter { name, style } => {
+        dest.write_str("counter(")?;
+        serialize_counter_name(name, dest)?;
+        serialize_counter_style(style, dest)?;
+        dest.write_char(')')
+      }
+      ContentItem::Counters { name, separator, style } => {
+        dest.write_str("counters(")?;
+        serialize_counter_name(name, dest)?;
+        dest.delim(',', false)?;
+        separator.to_css(dest)?;
+        serialize_counter_style(style, dest)?;
+        dest.write_char(')')
+      }
+      ContentItem::Attr(name) => {
+        dest.write_str("attr(")?;
+        name.to_css(dest)?;
+        dest.write_char(')')
+      }
+      ContentItem::Quote(quote) => quote.to_css(dest),
+    }
+  }
+}
+
+fn serialize_counter_style<W>(style: &CounterStyle, dest: &mut Printer<W>) -> Result<(), PrinterError>
+where
+  W: std::fmt::Write,
+{
+  // The decimal style is the default, so it can be omitted.
+  if *style != CounterStyle::Predefined(PredefinedCounterStyle::Decimal) {
+    dest.delim(',', false)?;
+    style.to_css(dest)?;
+  }
+  Ok(())
+}
+
+enum_property! {
+  /// A [`<quote>`](https://drafts.csswg.org/css-content-3/#typedef-quote) keyword,
+  /// as used in the `content` property.
+  pub enum Quote {
+    /// Inserts the appropriate opening quote.
+    "open-quote": OpenQuote,
+    /// Inserts the appropriate closing quote.
+    "close-quote": CloseQuote,
+    /// Increments the quote nesting level without inserting a quote.
+    "no-open-quote": NoOpenQuote,
+    /// Decrements the quote nesting level without inserting a quote.
+    "no-close-quote": NoCloseQuote,
+  }
+}
+
+/// A value for the [quotes](https://drafts.csswg.org/css-content-3/#quotes-property) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum Quotes<'i> {
+  /// The `auto` keyword. Quotes appropriate for the content language are used.
+  Auto,
+  /// The `none` keyword. Quote keywords in the `content` property produce no quotes.
+  None,
+  /// A list of open and close quote pairs, for each nesting level.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  Pairs(Vec<QuotePair<'i>>),
+}
+
+/// A pair of open and close quotes, as used in the [quotes](https://drafts.csswg.org/css-content-3/#quotes-property)
+/// property.
+///
+/// See [Quotes](Quotes).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct QuotePair<'i> {
+  /// The opening quote.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  pub open: CSSString<'i>,
+  /// The closing quote.
+  pub close: CSSString<'i>,
+}
+
+impl<'i> Parse<'i> for Quotes<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      return Ok(Quotes::Auto);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(Quotes::None);
+    }
+
+    let mut pairs = Vec::new();
+    while let Ok(open) = input.try_parse(CSSString::parse) {
+      let close = CSSString::parse(input)?;
+      pairs.push(QuotePair { open, close });
+    }
+
+    if pairs.is_empty() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(Quotes::Pairs(pairs))
+  }
+}
+
+impl<'i> ToCss for Quotes<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      Quotes::Auto => dest.write_str("auto"),
+      Quotes::None => dest.write_str("none"),
+      Quotes::Pairs(pairs) => {
+        let mut first = true;
+        for pair in pairs {
+          if first {
+            first = false;
+          } else {
+            dest.whitespace()?;
+          }
+          pair.open.to_css(dest)?;
+          dest.whitespace()?;
+          pair.close.to_css(dest)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
@@ -465,7 +465,7 @@ fn try_parse_color_token<'i, 't>(
   input: &mut Parser<'i, 't>,
 ) -> Option<CssColor> {
   match_ignore_ascii_case! { &*f,
-    "rgb" | "rgba" | "hsl" | "hsla" | "hwb" | "lab" | "lch" | "oklab" | "oklch" | "color" | "color-mix" => {
+    "rgb" | "rgba" | "hsl" | "hsla" | "hwb" | "lab" | "lch" | "oklab" | "oklch" | "color" | "color-mix" | "light-dark" => {
       let s = input.state();
       input.reset(&state);
       if let Ok(color) = CssColor::parse(input) {
@@ -1029,6 +1029,9 @@ impl<'i> TokenList<'i> {
         TokenOrValue::Color(color) => {
           fallbacks |= color.get_possible_fallbacks(targets);
         }
+        TokenOrValue::UnresolvedColor(UnresolvedColor::LightDark { light, dark }) => {
+          fallbacks |= light.get_necessary_fallbacks(targets) | dark.get_necessary_fallbacks(targets);
+        }
         TokenOrValue::Function(f) => {
           fallbacks |= f.arguments.get_necessary_fallbacks(targets);
         }
@@ -1055,6 +1058,12 @@ impl<'i> TokenList<'i> {
       .iter()
       .map(|token| match token {
         TokenOrValue::Color(color) => TokenOrValue::Color(color.get_fallback(kind)),
+        TokenOrValue::UnresolvedColor(UnresolvedColor::LightDark { light, dark }) => {
+          TokenOrValue::UnresolvedColor(UnresolvedColor::LightDark {
+            light: light.get_fallback(kind),
+            dark: dark.get_fallback(kind),
+          })
+        }
         TokenOrValue::Function(f) => TokenOrValue::Function(f.get_fallback(kind)),
         TokenOrValue::Var(v) => TokenOrValue::Var(v.get_fallback(kind)),
         TokenOrValue::Env(e) => TokenOrValue::Env(e.get_fallback(kind)),
@@ -1092,6 +1101,10 @@ impl<'i> TokenList<'i> {
           TokenOrValue::Color(color) => {
             *color = color.get_fallback(lowest_fallback);
           }
+          TokenOrValue::UnresolvedColor(UnresolvedColor::LightDark { light, dark }) => {
+            *light = light.get_fallback(lowest_fallback);
+            *dark = dark.get_fallback(lowest_fallback);
+          }
           TokenOrValue::Function(f) => *f = f.get_fallback(lowest_fallback),
           TokenOrValue::Var(v) if v.fallback.is_some() => *v = v.get_fallback(lowest_fallback),
           TokenOrValue::Env(v) if v.fallback.is_some() => *v = v.get_fallback(lowest_fallback),
@@ -1451,6 +1464,14 @@ pub enum UnresolvedColor<'i> {
     #[cfg_attr(feature = "serde", serde(borrow))]
     alpha: TokenList<'i>,
   },
+  /// The light-dark() function.
+  #[cfg_attr(feature = "serde", serde(rename = "light-dark"))]
+  LightDark {
+    /// The light value.
+    light: TokenList<'i>,
+    /// The dark value.
+    dark: TokenList<'i>,
+  },
 }
 
 impl<'i> UnresolvedColor<'i> {
@@ -1477,6 +1498,14 @@ impl<'i> UnresolvedColor<'i> {
           Ok(UnresolvedColor::HSL { h, s, l, alpha })
         })
       },
+      "light-dark" => {
+        input.parse_nested_block(|input| {
+          let light = input.parse_until_before(Delimiter::Comma, |input| TokenList::parse(input, options, 1))?;
+          input.expect_comma()?;
+          let dark = TokenList::parse(input, options, 1)?;
+          Ok(UnresolvedColor::LightDark { light, dark })
+        })
+      },
       _ => Err(input.new_custom_error(ParserError::InvalidValue))
     }
   }
@@ -1543,6 +1572,25 @@ impl<'i> UnresolvedColor<'i> {
         alpha.to_css(dest, is_custom_property)?;
         dest.write_char(')')
       }
+      UnresolvedColor::LightDark { light, dark } => {
+        if dest.light_dark_vars {
+          dest.write_str("var(--lightningcss-light")?;
+          dest.delim(',', false)?;
+          light.to_css(dest, is_custom_property)?;
+          dest.write_char(')')?;
+          dest.whitespace()?;
+          dest.write_str("var(--lightningcss-dark")?;
+          dest.delim(',', false)?;
+          dark.to_css(dest, is_custom_property)?;
+          return dest.write_char(')');
+        }
+
+        dest.write_str("light-dark(")?;
+        light.to_css(dest, is_custom_property)?;
+        dest.delim(',', false)?;
+        dark.to_css(dest, is_custom_property)?;
+        dest.write_char(')')
+      }
     }
   }
 }
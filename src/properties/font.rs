@@ -8,11 +8,14 @@ use crate::context::PropertyHandlerContext;
 use crate::declaration::{DeclarationBlock, DeclarationList};
 use crate::error::{ParserError, PrinterError};
 use crate::macros::*;
+use crate::prefixes::Feature as PrefixFeature;
 use crate::printer::Printer;
 use crate::traits::{Parse, PropertyHandler, Shorthand, ToCss};
-use crate::values::number::CSSNumber;
-use crate::values::string::CowArcStr;
+use crate::values::ident::Ident;
+use crate::values::number::{CSSInteger, CSSNumber};
+use crate::values::string::{CSSString, CowArcStr};
 use crate::values::{angle::Angle, length::LengthPercentage, percentage::Percentage};
+use crate::vendor_prefix::VendorPrefix;
 #[cfg(feature = "visitor")]
 use crate::visitor::Visit;
 use cssparser::*;
@@ -546,6 +549,1119 @@ impl FontVariantCaps {
   }
 }
 
+/// Parses an OpenType feature or axis tag, which must be a string of exactly four printable ASCII characters.
+fn parse_tag<'i, 't>(input: &mut Parser<'i, 't>) -> Result<CSSString<'i>, ParseError<'i, ParserError<'i>>> {
+  let location = input.current_source_location();
+  let tag = CSSString::parse(input)?;
+  if tag.0.len() != 4 || !tag.0.chars().all(|c| c >= ' ' && c <= '~') {
+    return Err(location.new_custom_error(ParserError::InvalidValue));
+  }
+  Ok(tag)
+}
+
+/// A value for the [font-feature-settings](https://www.w3.org/TR/css-fonts-4/#font-feature-settings-prop) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum FontFeatureSettings<'i> {
+  /// No feature settings are applied.
+  Normal,
+  /// A list of OpenType feature settings.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  FeatureTags(Vec<FeatureTagValue<'i>>),
+}
+
+/// An OpenType feature setting within the [font-feature-settings](https://www.w3.org/TR/css-fonts-4/#font-feature-settings-prop) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct FeatureTagValue<'i> {
+  /// The four character OpenType feature tag.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  pub tag: CSSString<'i>,
+  /// The feature value. `on` is 1, and `off` is 0.
+  pub value: CSSInteger,
+}
+
+impl Default for FontFeatureSettings<'_> {
+  fn default() -> Self {
+    FontFeatureSettings::Normal
+  }
+}
+
+impl<'i> Parse<'i> for FontFeatureSettings<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(FontFeatureSettings::Normal);
+    }
+
+    let tags = input.parse_comma_separated(FeatureTagValue::parse)?;
+    Ok(FontFeatureSettings::FeatureTags(tags))
+  }
+}
+
+impl<'i> Parse<'i> for FeatureTagValue<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let tag = parse_tag(input)?;
+    let value = if let Ok(value) = input.try_parse(CSSInteger::parse) {
+      if value.is_negative() {
+        return Err(input.new_custom_error(ParserError::InvalidValue));
+      }
+      value
+    } else if input.try_parse(|input| input.expect_ident_matching("on")).is_ok() {
+      1
+    } else if input.try_parse(|input| input.expect_ident_matching("off")).is_ok() {
+      0
+    } else {
+      1
+    };
+
+    Ok(FeatureTagValue { tag, value })
+  }
+}
+
+impl<'i> ToCss for FontFeatureSettings<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      FontFeatureSettings::Normal => dest.write_str("normal"),
+      FontFeatureSettings::FeatureTags(tags) => {
+        let mut first = true;
+        for tag in tags {
+          if first {
+            first = false;
+          } else {
+            dest.delim(',', false)?;
+          }
+          tag.to_css(dest)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+impl<'i> ToCss for FeatureTagValue<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    self.tag.to_css(dest)?;
+    if self.value != 1 {
+      dest.write_char(' ')?;
+      self.value.to_css(dest)?;
+    }
+    Ok(())
+  }
+}
+
+impl<'i> FontFeatureSettings<'i> {
+  /// Removes duplicate feature tags. When a tag occurs more than once, the last value wins.
+  fn dedupe(&mut self) {
+    if let FontFeatureSettings::FeatureTags(tags) = self {
+      dedupe_tags(tags, |t| &t.tag);
+    }
+  }
+}
+
+/// A value for the [font-variation-settings](https://www.w3.org/TR/css-fonts-4/#font-variation-settings-def) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum FontVariationSettings<'i> {
+  /// No variation settings are applied.
+  Normal,
+  /// A list of font variation axis values.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  Axes(Vec<VariationAxisValue<'i>>),
+}
+
+/// A variation axis value within the [font-variation-settings](https://www.w3.org/TR/css-fonts-4/#font-variation-settings-def) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct VariationAxisValue<'i> {
+  /// The four character axis tag.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  pub tag: CSSString<'i>,
+  /// The axis value.
+  pub value: CSSNumber,
+}
+
+impl Default for FontVariationSettings<'_> {
+  fn default() -> Self {
+    FontVariationSettings::Normal
+  }
+}
+
+impl<'i> Parse<'i> for FontVariationSettings<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(FontVariationSettings::Normal);
+    }
+
+    let axes = input.parse_comma_separated(|input| {
+      let tag = parse_tag(input)?;
+      let value = CSSNumber::parse(input)?;
+      Ok(VariationAxisValue { tag, value })
+    })?;
+    Ok(FontVariationSettings::Axes(axes))
+  }
+}
+
+impl<'i> ToCss for FontVariationSettings<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      FontVariationSettings::Normal => dest.write_str("normal"),
+      FontVariationSettings::Axes(axes) => {
+        let mut first = true;
+        for axis in axes {
+          if first {
+            first = false;
+          } else {
+            dest.delim(',', false)?;
+          }
+          axis.tag.to_css(dest)?;
+          dest.write_char(' ')?;
+          axis.value.to_css(dest)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+impl<'i> FontVariationSettings<'i> {
+  /// Removes duplicate axis tags. When a tag occurs more than once, the last value wins.
+  fn dedupe(&mut self) {
+    if let FontVariationSettings::Axes(axes) = self {
+      dedupe_tags(axes, |a| &a.tag);
+    }
+  }
+}
+
+fn dedupe_tags<'i, T, F: Fn(&T) -> &CSSString<'i>>(values: &mut Vec<T>, tag: F) {
+  if values.len() > 1 {
+    let mut seen = HashSet::new();
+    let mut i = values.len();
+    while i > 0 {
+      i -= 1;
+      if !seen.insert(tag(&values[i]).0.clone()) {
+        values.remove(i);
+      }
+    }
+  }
+}
+
+enum_property! {
+  /// A value for the [font-kerning](https://www.w3.org/TR/css-fonts-4/#font-kerning-prop) property.
+  pub enum FontKerning {
+    /// The browser determines whether kerning should be used.
+    Auto,
+    /// Kerning is applied.
+    Normal,
+    /// Kerning is not applied.
+    None,
+  }
+}
+
+impl Default for FontKerning {
+  fn default() -> FontKerning {
+    FontKerning::Auto
+  }
+}
+
+enum_property! {
+  /// A value for the [font-optical-sizing](https://www.w3.org/TR/css-fonts-4/#font-optical-sizing-def) property.
+  pub enum FontOpticalSizing {
+    /// The browser may modify the glyph shapes for the font size.
+    Auto,
+    /// Optical sizing is disabled.
+    None,
+  }
+}
+
+impl Default for FontOpticalSizing {
+  fn default() -> FontOpticalSizing {
+    FontOpticalSizing::Auto
+  }
+}
+
+/// A value for the [font-synthesis](https://www.w3.org/TR/css-fonts-4/#font-synthesis) property.
+///
+/// Each field indicates whether the browser may synthesize the corresponding typeface.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct FontSynthesis {
+  /// Whether bold faces may be synthesized.
+  pub weight: bool,
+  /// Whether italic and oblique faces may be synthesized.
+  pub style: bool,
+  /// Whether small capitals may be synthesized.
+  pub small_caps: bool,
+  /// Whether subscript and superscript faces may be synthesized.
+  pub position: bool,
+}
+
+impl Default for FontSynthesis {
+  fn default() -> FontSynthesis {
+    FontSynthesis {
+      weight: true,
+      style: true,
+      small_caps: true,
+      position: true,
+    }
+  }
+}
+
+impl<'i> Parse<'i> for FontSynthesis {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut value = FontSynthesis {
+      weight: false,
+      style: false,
+      small_caps: false,
+      position: false,
+    };
+
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(value);
+    }
+
+    let mut any = false;
+    loop {
+      let location = input.current_source_location();
+      let ident = match input.try_parse(|input| input.expect_ident_cloned()) {
+        Ok(ident) => ident,
+        Err(_) => break,
+      };
+      let flag = match_ignore_ascii_case! { &ident,
+        "weight" => &mut value.weight,
+        "style" => &mut value.style,
+        "small-caps" => &mut value.small_caps,
+        "position" => &mut value.position,
+        _ => return Err(location.new_unexpected_token_error(Token::Ident(ident.clone())))
+      };
+      if *flag {
+        return Err(location.new_unexpected_token_error(Token::Ident(ident.clone())));
+      }
+      *flag = true;
+      any = true;
+    }
+
+    if !any {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(value)
+  }
+}
+
+impl ToCss for FontSynthesis {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    let mut needs_space = false;
+    macro_rules! flag {
+      ($field: ident, $str: literal) => {
+        if self.$field {
+          if needs_space {
+            dest.write_char(' ')?;
+          }
+          dest.write_str($str)?;
+          needs_space = true;
+        }
+      };
+    }
+
+    flag!(weight, "weight");
+    flag!(style, "style");
+    flag!(small_caps, "small-caps");
+    flag!(position, "position");
+
+    if !needs_space {
+      dest.write_str("none")?;
+    }
+
+    Ok(())
+  }
+}
+
+enum_property! {
+  /// A value for the [font-variant-position](https://www.w3.org/TR/css-fonts-4/#font-variant-position-prop) property.
+  pub enum FontVariantPosition {
+    /// No subscript or superscript glyphs are used.
+    "normal": Normal,
+    /// Subscript glyphs are used.
+    "sub": Sub,
+    /// Superscript glyphs are used.
+    "super": Super,
+  }
+}
+
+impl Default for FontVariantPosition {
+  fn default() -> FontVariantPosition {
+    FontVariantPosition::Normal
+  }
+}
+
+enum_property! {
+  /// Common ligature values within the [font-variant-ligatures](https://www.w3.org/TR/css-fonts-4/#font-variant-ligatures-prop) property.
+  pub enum CommonLigValues {
+    /// Common ligatures are enabled.
+    "common-ligatures": CommonLigatures,
+    /// Common ligatures are disabled.
+    "no-common-ligatures": NoCommonLigatures,
+  }
+}
+
+enum_property! {
+  /// Discretionary ligature values within the [font-variant-ligatures](https://www.w3.org/TR/css-fonts-4/#font-variant-ligatures-prop) property.
+  pub enum DiscretionaryLigValues {
+    /// Discretionary ligatures are enabled.
+    "discretionary-ligatures": DiscretionaryLigatures,
+    /// Discretionary ligatures are disabled.
+    "no-discretionary-ligatures": NoDiscretionaryLigatures,
+  }
+}
+
+enum_property! {
+  /// Historical ligature values within the [font-variant-ligatures](https://www.w3.org/TR/css-fonts-4/#font-variant-ligatures-prop) property.
+  pub enum HistoricalLigValues {
+    /// Historical ligatures are enabled.
+    "historical-ligatures": HistoricalLigatures,
+    /// Historical ligatures are disabled.
+    "no-historical-ligatures": NoHistoricalLigatures,
+  }
+}
+
+enum_property! {
+  /// Contextual alternate values within the [font-variant-ligatures](https://www.w3.org/TR/css-fonts-4/#font-variant-ligatures-prop) property.
+  pub enum ContextualAltValues {
+    /// Contextual alternates are enabled.
+    "contextual": Contextual,
+    /// Contextual alternates are disabled.
+    "no-contextual": NoContextual,
+  }
+}
+
+/// A value for the [font-variant-ligatures](https://www.w3.org/TR/css-fonts-4/#font-variant-ligatures-prop) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum FontVariantLigatures {
+  /// The default ligatures for the font are used.
+  Normal,
+  /// All ligatures and contextual forms are disabled.
+  None,
+  /// A combination of ligature values.
+  Values {
+    /// Common ligatures.
+    common: Option<CommonLigValues>,
+    /// Discretionary ligatures.
+    discretionary: Option<DiscretionaryLigValues>,
+    /// Historical ligatures.
+    historical: Option<HistoricalLigValues>,
+    /// Contextual alternates.
+    contextual: Option<ContextualAltValues>,
+  },
+}
+
+impl Default for FontVariantLigatures {
+  fn default() -> FontVariantLigatures {
+    FontVariantLigatures::Normal
+  }
+}
+
+impl<'i> Parse<'i> for FontVariantLigatures {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(FontVariantLigatures::Normal);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(FontVariantLigatures::None);
+    }
+
+    let mut value = FontVariantLigatures::Normal;
+    while value.parse_component(input) {}
+    if value == FontVariantLigatures::Normal {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(value)
+  }
+}
+
+impl FontVariantLigatures {
+  /// Attempts to parse a single ligature value into self, returning whether one was found.
+  fn parse_component<'i, 't>(&mut self, input: &mut Parser<'i, 't>) -> bool {
+    if matches!(self, FontVariantLigatures::Normal) {
+      *self = FontVariantLigatures::Values {
+        common: None,
+        discretionary: None,
+        historical: None,
+        contextual: None,
+      };
+      if self.parse_component(input) {
+        return true;
+      }
+      *self = FontVariantLigatures::Normal;
+      return false;
+    }
+
+    if let FontVariantLigatures::Values {
+      common,
+      discretionary,
+      historical,
+      contextual,
+    } = self
+    {
+      if common.is_none() {
+        if let Ok(value) = input.try_parse(CommonLigValues::parse) {
+          *common = Some(value);
+          return true;
+        }
+      }
+      if discretionary.is_none() {
+        if let Ok(value) = input.try_parse(DiscretionaryLigValues::parse) {
+          *discretionary = Some(value);
+          return true;
+        }
+      }
+      if historical.is_none() {
+        if let Ok(value) = input.try_parse(HistoricalLigValues::parse) {
+          *historical = Some(value);
+          return true;
+        }
+      }
+      if contextual.is_none() {
+        if let Ok(value) = input.try_parse(ContextualAltValues::parse) {
+          *contextual = Some(value);
+          return true;
+        }
+      }
+    }
+
+    false
+  }
+}
+
+impl ToCss for FontVariantLigatures {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      FontVariantLigatures::Normal => dest.write_str("normal"),
+      FontVariantLigatures::None => dest.write_str("none"),
+      FontVariantLigatures::Values {
+        common,
+        discretionary,
+        historical,
+        contextual,
+      } => {
+        let mut needs_space = false;
+        write_optional(common, dest, &mut needs_space)?;
+        write_optional(discretionary, dest, &mut needs_space)?;
+        write_optional(historical, dest, &mut needs_space)?;
+        write_optional(contextual, dest, &mut needs_space)
+      }
+    }
+  }
+}
+
+fn write_optional<T: ToCss, W: std::fmt::Write>(
+  value: &Option<T>,
+  dest: &mut Printer<W>,
+  needs_space: &mut bool,
+) -> Result<(), PrinterError> {
+  if let Some(value) = value {
+    if *needs_space {
+      dest.write_char(' ')?;
+    }
+    value.to_css(dest)?;
+    *needs_space = true;
+  }
+  Ok(())
+}
+
+fn write_flag<W: std::fmt::Write>(
+  value: bool,
+  s: &str,
+  dest: &mut Printer<W>,
+  needs_space: &mut bool,
+) -> Result<(), PrinterError> {
+  if value {
+    if *needs_space {
+      dest.write_char(' ')?;
+    }
+    dest.write_str(s)?;
+    *needs_space = true;
+  }
+  Ok(())
+}
+
+enum_property! {
+  /// Numeric figure values within the [font-variant-numeric](https://www.w3.org/TR/css-fonts-4/#font-variant-numeric-prop) property.
+  pub enum NumericFigureValues {
+    /// Lining numerals are used.
+    "lining-nums": LiningNums,
+    /// Old-style numerals are used.
+    "oldstyle-nums": OldstyleNums,
+  }
+}
+
+enum_property! {
+  /// Numeric spacing values within the [font-variant-numeric](https://www.w3.org/TR/css-fonts-4/#font-variant-numeric-prop) property.
+  pub enum NumericSpacingValues {
+    /// Proportional numerals are used.
+    "proportional-nums": ProportionalNums,
+    /// Tabular numerals are used.
+    "tabular-nums": TabularNums,
+  }
+}
+
+enum_property! {
+  /// Numeric fraction values within the [font-variant-numeric](https://www.w3.org/TR/css-fonts-4/#font-variant-numeric-prop) property.
+  pub enum NumericFractionValues {
+    /// Diagonal fractions are used.
+    "diagonal-fractions": DiagonalFractions,
+    /// Stacked fractions are used.
+    "stacked-fractions": StackedFractions,
+  }
+}
+
+/// A value for the [font-variant-numeric](https://www.w3.org/TR/css-fonts-4/#font-variant-numeric-prop) property.
+///
+/// The default value, with all fields empty, represents `normal`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct FontVariantNumeric {
+  /// The numeric figure style.
+  pub figure: Option<NumericFigureValues>,
+  /// The numeric spacing style.
+  pub spacing: Option<NumericSpacingValues>,
+  /// The fraction style.
+  pub fraction: Option<NumericFractionValues>,
+  /// Whether ordinal forms are used.
+  pub ordinal: bool,
+  /// Whether a slashed zero is used.
+  pub slashed_zero: bool,
+}
+
+impl<'i> Parse<'i> for FontVariantNumeric {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut value = FontVariantNumeric::default();
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(value);
+    }
+
+    while value.parse_component(input) {}
+    if value == FontVariantNumeric::default() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(value)
+  }
+}
+
+impl FontVariantNumeric {
+  /// Attempts to parse a single numeric value into self, returning whether one was found.
+  fn parse_component<'i, 't>(&mut self, input: &mut Parser<'i, 't>) -> bool {
+    if self.figure.is_none() {
+      if let Ok(value) = input.try_parse(NumericFigureValues::parse) {
+        self.figure = Some(value);
+        return true;
+      }
+    }
+    if self.spacing.is_none() {
+      if let Ok(value) = input.try_parse(NumericSpacingValues::parse) {
+        self.spacing = Some(value);
+        return true;
+      }
+    }
+    if self.fraction.is_none() {
+      if let Ok(value) = input.try_parse(NumericFractionValues::parse) {
+        self.fraction = Some(value);
+        return true;
+      }
+    }
+    if !self.ordinal && input.try_parse(|input| input.expect_ident_matching("ordinal")).is_ok() {
+      self.ordinal = true;
+      return true;
+    }
+    if !self.slashed_zero && input.try_parse(|input| input.expect_ident_matching("slashed-zero")).is_ok() {
+      self.slashed_zero = true;
+      return true;
+    }
+    false
+  }
+
+  fn write_components<W: std::fmt::Write>(
+    &self,
+    dest: &mut Printer<W>,
+    needs_space: &mut bool,
+  ) -> Result<(), PrinterError> {
+    write_optional(&self.figure, dest, needs_space)?;
+    write_optional(&self.spacing, dest, needs_space)?;
+    write_optional(&self.fraction, dest, needs_space)?;
+    write_flag(self.ordinal, "ordinal", dest, needs_space)?;
+    write_flag(self.slashed_zero, "slashed-zero", dest, needs_space)
+  }
+}
+
+impl ToCss for FontVariantNumeric {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if *self == FontVariantNumeric::default() {
+      return dest.write_str("normal");
+    }
+
+    self.write_components(dest, &mut false)
+  }
+}
+
+enum_property! {
+  /// East Asian variant values within the [font-variant-east-asian](https://www.w3.org/TR/css-fonts-4/#font-variant-east-asian-prop) property.
+  pub enum EastAsianVariantValues {
+    /// JIS X 0208-1978 glyph forms.
+    "jis78": Jis78,
+    /// JIS X 0208-1983 glyph forms.
+    "jis83": Jis83,
+    /// JIS X 0208-1990 glyph forms.
+    "jis90": Jis90,
+    /// JIS X 0213:2004 glyph forms.
+    "jis04": Jis04,
+    /// Simplified Chinese glyph forms.
+    "simplified": Simplified,
+    /// Traditional Chinese glyph forms.
+    "traditional": Traditional,
+  }
+}
+
+enum_property! {
+  /// East Asian width values within the [font-variant-east-asian](https://www.w3.org/TR/css-fonts-4/#font-variant-east-asian-prop) property.
+  pub enum EastAsianWidthValues {
+    /// Full-width glyphs are used.
+    "full-width": FullWidth,
+    /// Proportionally spaced glyphs are used.
+    "proportional-width": ProportionalWidth,
+  }
+}
+
+/// A value for the [font-variant-east-asian](https://www.w3.org/TR/css-fonts-4/#font-variant-east-asian-prop) property.
+///
+/// The default value, with all fields empty, represents `normal`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct FontVariantEastAsian {
+  /// The glyph variant.
+  pub variant: Option<EastAsianVariantValues>,
+  /// The glyph width.
+  pub width: Option<EastAsianWidthValues>,
+  /// Whether ruby variant glyphs are used.
+  pub ruby: bool,
+}
+
+impl<'i> Parse<'i> for FontVariantEastAsian {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut value = FontVariantEastAsian::default();
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(value);
+    }
+
+    while value.parse_component(input) {}
+    if value == FontVariantEastAsian::default() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(value)
+  }
+}
+
+impl FontVariantEastAsian {
+  /// Attempts to parse a single East Asian value into self, returning whether one was found.
+  fn parse_component<'i, 't>(&mut self, input: &mut Parser<'i, 't>) -> bool {
+    if self.variant.is_none() {
+      if let Ok(value) = input.try_parse(EastAsianVariantValues::parse) {
+        self.variant = Some(value);
+        return true;
+      }
+    }
+    if self.width.is_none() {
+      if let Ok(value) = input.try_parse(EastAsianWidthValues::parse) {
+        self.width = Some(value);
+        return true;
+      }
+    }
+    if !self.ruby && input.try_parse(|input| input.expect_ident_matching("ruby")).is_ok() {
+      self.ruby = true;
+      return true;
+    }
+    false
+  }
+
+  fn write_components<W: std::fmt::Write>(
+    &self,
+    dest: &mut Printer<W>,
+    needs_space: &mut bool,
+  ) -> Result<(), PrinterError> {
+    write_optional(&self.variant, dest, needs_space)?;
+    write_optional(&self.width, dest, needs_space)?;
+    write_flag(self.ruby, "ruby", dest, needs_space)
+  }
+}
+
+impl ToCss for FontVariantEastAsian {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if *self == FontVariantEastAsian::default() {
+      return dest.write_str("normal");
+    }
+
+    self.write_components(dest, &mut false)
+  }
+}
+
+/// A value for the [font-variant-alternates](https://www.w3.org/TR/css-fonts-4/#font-variant-alternates-prop) property.
+///
+/// The named values reference feature values defined in a `@font-feature-values` rule.
+/// The default value, with all fields empty, represents `normal`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct FontVariantAlternates<'i> {
+  /// The `stylistic()` feature value.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  pub stylistic: Option<Ident<'i>>,
+  /// Whether historical forms are enabled.
+  pub historical_forms: bool,
+  /// The `styleset()` feature values.
+  pub styleset: Option<Vec<Ident<'i>>>,
+  /// The `character-variant()` feature values.
+  pub character_variant: Option<Vec<Ident<'i>>>,
+  /// The `swash()` feature value.
+  pub swash: Option<Ident<'i>>,
+  /// The `ornaments()` feature value.
+  pub ornaments: Option<Ident<'i>>,
+  /// The `annotation()` feature value.
+  pub annotation: Option<Ident<'i>>,
+}
+
+impl<'i> Parse<'i> for FontVariantAlternates<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut value = FontVariantAlternates::default();
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(value);
+    }
+
+    while value.parse_component(input) {}
+    if value == FontVariantAlternates::default() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(value)
+  }
+}
+
+impl<'i> FontVariantAlternates<'i> {
+  /// Attempts to parse a single alternates value into self, returning whether one was found.
+  fn parse_component<'t>(&mut self, input: &mut Parser<'i, 't>) -> bool {
+    if !self.historical_forms && input.try_parse(|input| input.expect_ident_matching("historical-forms")).is_ok() {
+      self.historical_forms = true;
+      return true;
+    }
+
+    let res = input.try_parse(|input| {
+      let location = input.current_source_location();
+      let f = input.expect_function()?.clone();
+      input.parse_nested_block(|input| {
+        match_ignore_ascii_case! { &f,
+          "stylistic" if self.stylistic.is_none() => self.stylistic = Some(Ident::parse(input)?),
+          "styleset" if self.styleset.is_none() => self.styleset = Some(input.parse_comma_separated(Ident::parse)?),
+          "character-variant" if self.character_variant.is_none() => {
+            self.character_variant = Some(input.parse_comma_separated(Ident::parse)?)
+          },
+          "swash" if self.swash.is_none() => self.swash = Some(Ident::parse(input)?),
+          "ornaments" if self.ornaments.is_none() => self.ornaments = Some(Ident::parse(input)?),
+          "annotation" if self.annotation.is_none() => self.annotation = Some(Ident::parse(input)?),
+          _ => return Err(location.new_unexpected_token_error(Token::Function(f.clone())))
+        }
+        Ok(())
+      })
+    });
+
+    res.is_ok()
+  }
+
+  fn write_components<W: std::fmt::Write>(
+    &self,
+    dest: &mut Printer<W>,
+    needs_space: &mut bool,
+  ) -> Result<(), PrinterError> {
+    macro_rules! function {
+      ($field: ident, $name: literal) => {
+        if let Some(value) = &self.$field {
+          if *needs_space {
+            dest.write_char(' ')?;
+          }
+          dest.write_str($name)?;
+          dest.write_char('(')?;
+          value.to_css(dest)?;
+          dest.write_char(')')?;
+          *needs_space = true;
+        }
+      };
+    }
+
+    macro_rules! list_function {
+      ($field: ident, $name: literal) => {
+        if let Some(values) = &self.$field {
+          if *needs_space {
+            dest.write_char(' ')?;
+          }
+          dest.write_str($name)?;
+          dest.write_char('(')?;
+          let mut first = true;
+          for value in values {
+            if first {
+              first = false;
+            } else {
+              dest.delim(',', false)?;
+            }
+            value.to_css(dest)?;
+          }
+          dest.write_char(')')?;
+          *needs_space = true;
+        }
+      };
+    }
+
+    function!(stylistic, "stylistic");
+    write_flag(self.historical_forms, "historical-forms", dest, needs_space)?;
+    list_function!(styleset, "styleset");
+    list_function!(character_variant, "character-variant");
+    function!(swash, "swash");
+    function!(ornaments, "ornaments");
+    function!(annotation, "annotation");
+    Ok(())
+  }
+}
+
+impl<'i> ToCss for FontVariantAlternates<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if *self == FontVariantAlternates::default() {
+      return dest.write_str("normal");
+    }
+
+    self.write_components(dest, &mut false)
+  }
+}
+
+define_shorthand! {
+  /// A value for the [font-variant](https://www.w3.org/TR/css-fonts-4/#font-variant-prop) shorthand property.
+  #[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+  pub struct FontVariant<'i> {
+    /// The ligatures.
+    ligatures: FontVariantLigatures(FontVariantLigatures),
+    /// The capitalization.
+    caps: FontVariantCaps(FontVariantCaps),
+    /// The alternate glyphs.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    alternates: FontVariantAlternates(FontVariantAlternates<'i>),
+    /// The numeric glyphs.
+    numeric: FontVariantNumeric(FontVariantNumeric),
+    /// The East Asian glyphs.
+    east_asian: FontVariantEastAsian(FontVariantEastAsian),
+    /// The subscript or superscript glyphs.
+    position: FontVariantPosition(FontVariantPosition),
+  }
+}
+
+impl<'i> Parse<'i> for FontVariant<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut value = FontVariant {
+      ligatures: FontVariantLigatures::Normal,
+      caps: FontVariantCaps::Normal,
+      alternates: FontVariantAlternates::default(),
+      numeric: FontVariantNumeric::default(),
+      east_asian: FontVariantEastAsian::default(),
+      position: FontVariantPosition::Normal,
+    };
+
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(value);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      value.ligatures = FontVariantLigatures::None;
+      return Ok(value);
+    }
+
+    let mut caps = None;
+    let mut position = None;
+    loop {
+      if value.ligatures.parse_component(input)
+        || value.alternates.parse_component(input)
+        || value.numeric.parse_component(input)
+        || value.east_asian.parse_component(input)
+      {
+        continue;
+      }
+
+      if caps.is_none() {
+        if let Ok(c) = input.try_parse(FontVariantCaps::parse) {
+          caps = Some(c);
+          continue;
+        }
+      }
+
+      if position.is_none() {
+        if let Ok(p) = input.try_parse(FontVariantPosition::parse) {
+          position = Some(p);
+          continue;
+        }
+      }
+
+      break;
+    }
+
+    // "normal" is not valid within a combination of values.
+    if matches!(caps, Some(FontVariantCaps::Normal)) || matches!(position, Some(FontVariantPosition::Normal)) {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    if let Some(caps) = caps {
+      value.caps = caps;
+    }
+    if let Some(position) = position {
+      value.position = position;
+    }
+
+    if value.is_normal() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(value)
+  }
+}
+
+impl<'i> FontVariant<'i> {
+  fn is_normal(&self) -> bool {
+    self.ligatures == FontVariantLigatures::Normal && self.is_normal_except_ligatures()
+  }
+
+  fn is_normal_except_ligatures(&self) -> bool {
+    self.caps == FontVariantCaps::Normal
+      && self.alternates == FontVariantAlternates::default()
+      && self.numeric == FontVariantNumeric::default()
+      && self.east_asian == FontVariantEastAsian::default()
+      && self.position == FontVariantPosition::Normal
+  }
+
+  /// Returns whether the shorthand can represent the values. Ligatures may only be
+  /// `none` when all other values are `normal`.
+  fn is_valid(&self) -> bool {
+    self.ligatures != FontVariantLigatures::None || self.is_normal_except_ligatures()
+  }
+}
+
+impl<'i> ToCss for FontVariant<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if self.is_normal() {
+      return dest.write_str("normal");
+    }
+
+    if self.ligatures == FontVariantLigatures::None {
+      return dest.write_str("none");
+    }
+
+    let mut needs_space = false;
+    if self.ligatures != FontVariantLigatures::Normal {
+      self.ligatures.to_css(dest)?;
+      needs_space = true;
+    }
+
+    if self.caps != FontVariantCaps::Normal {
+      if needs_space {
+        dest.write_char(' ')?;
+      }
+      self.caps.to_css(dest)?;
+      needs_space = true;
+    }
+
+    self.alternates.write_components(dest, &mut needs_space)?;
+    self.numeric.write_components(dest, &mut needs_space)?;
+    self.east_asian.write_components(dest, &mut needs_space)?;
+
+    if self.position != FontVariantPosition::Normal {
+      if needs_space {
+        dest.write_char(' ')?;
+      }
+      self.position.to_css(dest)?;
+    }
+
+    Ok(())
+  }
+}
+
 /// A value for the [line-height](https://www.w3.org/TR/2020/WD-css-inline-3-20200827/#propdef-line-height) property.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "visitor", derive(Visit))]
@@ -812,6 +1928,16 @@ pub(crate) struct FontHandler<'i> {
   stretch: Option<FontStretch>,
   line_height: Option<LineHeight>,
   variant_caps: Option<FontVariantCaps>,
+  variant_ligatures: Option<FontVariantLigatures>,
+  variant_alternates: Option<FontVariantAlternates<'i>>,
+  variant_numeric: Option<FontVariantNumeric>,
+  variant_east_asian: Option<FontVariantEastAsian>,
+  variant_position: Option<FontVariantPosition>,
+  kerning: Option<FontKerning>,
+  optical_sizing: Option<FontOpticalSizing>,
+  synthesis: Option<FontSynthesis>,
+  feature_settings: Option<(FontFeatureSettings<'i>, VendorPrefix)>,
+  variation_settings: Option<FontVariationSettings<'i>>,
   has_any: bool,
 }
 
@@ -839,6 +1965,39 @@ impl<'i> PropertyHandler<'i> for FontHandler<'i> {
       FontStretch(val) => property!(stretch, val),
       FontVariantCaps(val) => property!(variant_caps, val),
       LineHeight(val) => property!(line_height, val),
+      FontVariantLigatures(val) => property!(variant_ligatures, val),
+      FontVariantAlternates(val) => property!(variant_alternates, val),
+      FontVariantNumeric(val) => property!(variant_numeric, val),
+      FontVariantEastAsian(val) => property!(variant_east_asian, val),
+      FontVariantPosition(val) => property!(variant_position, val),
+      FontKerning(val) => property!(kerning, val),
+      FontOpticalSizing(val) => property!(optical_sizing, val),
+      FontSynthesis(val) => property!(synthesis, val),
+      FontVariationSettings(val) => property!(variation_settings, val),
+      FontFeatureSettings(val, vp) => {
+        if let Some((cur, prefixes)) = &mut self.feature_settings {
+          // If the value is the same, update the prefix.
+          // If the prefix is the same, then update the value.
+          if val == cur || prefixes.contains(*vp) {
+            *cur = val.clone();
+            *prefixes |= *vp;
+            self.has_any = true;
+            return true;
+          }
+          self.finalize(dest, context);
+        }
+        self.feature_settings = Some((val.clone(), *vp));
+        self.has_any = true;
+      }
+      FontVariant(val) => {
+        self.variant_ligatures = Some(val.ligatures.clone());
+        self.variant_caps = Some(val.caps.clone());
+        self.variant_alternates = Some(val.alternates.clone());
+        self.variant_numeric = Some(val.numeric.clone());
+        self.variant_east_asian = Some(val.east_asian.clone());
+        self.variant_position = Some(val.position.clone());
+        self.has_any = true;
+      }
       Font(val) => {
         self.family = Some(val.family.clone());
         self.size = Some(val.size.clone());
@@ -847,8 +2006,18 @@ impl<'i> PropertyHandler<'i> for FontHandler<'i> {
         self.stretch = Some(val.stretch.clone());
         self.line_height = Some(val.line_height.clone());
         self.variant_caps = Some(val.variant_caps.clone());
+        // The font shorthand resets these properties to their initial values.
+        // It is always output when present, so any previous values can be dropped.
+        self.variant_ligatures = None;
+        self.variant_alternates = None;
+        self.variant_numeric = None;
+        self.variant_east_asian = None;
+        self.variant_position = None;
+        self.kerning = None;
+        self.optical_sizing = None;
+        self.feature_settings = None;
+        self.variation_settings = None;
         self.has_any = true;
-        // TODO: reset other properties
       }
       Unparsed(val) if is_font_property(&val.property_id) => {
         self.finalize(dest, context);
@@ -876,7 +2045,17 @@ impl<'i> PropertyHandler<'i> for FontHandler<'i> {
     let weight = std::mem::take(&mut self.weight);
     let stretch = std::mem::take(&mut self.stretch);
     let line_height = std::mem::take(&mut self.line_height);
-    let variant_caps = std::mem::take(&mut self.variant_caps);
+    let mut variant_caps = std::mem::take(&mut self.variant_caps);
+    let mut variant_ligatures = std::mem::take(&mut self.variant_ligatures);
+    let mut variant_alternates = std::mem::take(&mut self.variant_alternates);
+    let mut variant_numeric = std::mem::take(&mut self.variant_numeric);
+    let mut variant_east_asian = std::mem::take(&mut self.variant_east_asian);
+    let mut variant_position = std::mem::take(&mut self.variant_position);
+    let kerning = std::mem::take(&mut self.kerning);
+    let optical_sizing = std::mem::take(&mut self.optical_sizing);
+    let synthesis = std::mem::take(&mut self.synthesis);
+    let feature_settings = std::mem::take(&mut self.feature_settings);
+    let variation_settings = std::mem::take(&mut self.variation_settings);
 
     if let Some(family) = &mut family {
       if family.len() > 1 {
@@ -886,15 +2065,60 @@ impl<'i> PropertyHandler<'i> for FontHandler<'i> {
       }
     }
 
-    if family.is_some()
+    let is_font = family.is_some()
       && size.is_some()
       && style.is_some()
       && weight.is_some()
       && stretch.is_some()
       && line_height.is_some()
+      && variant_caps.is_some();
+
+    // Combine the variant longhands into the font-variant shorthand when possible.
+    // If the font shorthand is also output, font-variant follows it and includes the caps.
+    let mut variant = None;
+    if variant_ligatures.is_some()
       && variant_caps.is_some()
+      && variant_alternates.is_some()
+      && variant_numeric.is_some()
+      && variant_east_asian.is_some()
+      && variant_position.is_some()
     {
-      let caps = variant_caps.unwrap();
+      let value = FontVariant {
+        ligatures: variant_ligatures.take().unwrap(),
+        caps: variant_caps.take().unwrap(),
+        alternates: variant_alternates.take().unwrap(),
+        numeric: variant_numeric.take().unwrap(),
+        east_asian: variant_east_asian.take().unwrap(),
+        position: variant_position.take().unwrap(),
+      };
+
+      if value.is_valid() {
+        variant = Some(value);
+      } else {
+        variant_ligatures = Some(value.ligatures);
+        variant_caps = Some(value.caps);
+        variant_alternates = Some(value.alternates);
+        variant_numeric = Some(value.numeric);
+        variant_east_asian = Some(value.east_asian);
+        variant_position = Some(value.position);
+      }
+    }
+
+    if is_font {
+      // The font shorthand resets the other variant longhands, so initial values can be omitted.
+      fn clear_default<T: Default + PartialEq>(value: &mut Option<T>) {
+        if matches!(value, Some(v) if *v == T::default()) {
+          *value = None;
+        }
+      }
+
+      clear_default(&mut variant_ligatures);
+      clear_default(&mut variant_alternates);
+      clear_default(&mut variant_numeric);
+      clear_default(&mut variant_east_asian);
+      clear_default(&mut variant_position);
+
+      let caps = variant_caps.take().unwrap_or_default();
       decls.push(Property::Font(Font {
         family: family.unwrap(),
         size: size.unwrap(),
@@ -912,7 +2136,7 @@ impl<'i> PropertyHandler<'i> for FontHandler<'i> {
       // The `font` property only accepts CSS 2.1 values for font-variant caps.
       // If we have a CSS 3+ value, we need to add a separate property.
       if !caps.is_css2() {
-        decls.push(Property::FontVariantCaps(variant_caps.unwrap()))
+        decls.push(Property::FontVariantCaps(caps))
       }
     } else {
       if let Some(val) = family {
@@ -943,6 +2167,57 @@ impl<'i> PropertyHandler<'i> for FontHandler<'i> {
         decls.push(Property::LineHeight(val))
       }
     }
+
+    if let Some(val) = variant {
+      decls.push(Property::FontVariant(val))
+    }
+
+    if let Some(val) = variant_ligatures {
+      decls.push(Property::FontVariantLigatures(val))
+    }
+
+    if let Some(val) = variant_alternates {
+      decls.push(Property::FontVariantAlternates(val))
+    }
+
+    if let Some(val) = variant_numeric {
+      decls.push(Property::FontVariantNumeric(val))
+    }
+
+    if let Some(val) = variant_east_asian {
+      decls.push(Property::FontVariantEastAsian(val))
+    }
+
+    if let Some(val) = variant_position {
+      decls.push(Property::FontVariantPosition(val))
+    }
+
+    if let Some(val) = kerning {
+      decls.push(Property::FontKerning(val))
+    }
+
+    if let Some(val) = optical_sizing {
+      decls.push(Property::FontOpticalSizing(val))
+    }
+
+    if let Some(val) = synthesis {
+      decls.push(Property::FontSynthesis(val))
+    }
+
+    if let Some((mut val, mut prefix)) = feature_settings {
+      val.dedupe();
+      if prefix.contains(VendorPrefix::None) {
+        if let Some(targets) = context.targets {
+          prefix = PrefixFeature::FontFeatureSettings.prefixes_for(targets);
+        }
+      }
+      decls.push(Property::FontFeatureSettings(val, prefix))
+    }
+
+    if let Some(mut val) = variation_settings {
+      val.dedupe();
+      decls.push(Property::FontVariationSettings(val))
+    }
   }
 }
 
@@ -993,6 +2268,17 @@ fn is_font_property(property_id: &PropertyId) -> bool {
     | PropertyId::FontWeight
     | PropertyId::FontStretch
     | PropertyId::FontVariantCaps
+    | PropertyId::FontVariantLigatures
+    | PropertyId::FontVariantAlternates
+    | PropertyId::FontVariantNumeric
+    | PropertyId::FontVariantEastAsian
+    | PropertyId::FontVariantPosition
+    | PropertyId::FontVariant
+    | PropertyId::FontKerning
+    | PropertyId::FontOpticalSizing
+    | PropertyId::FontSynthesis
+    | PropertyId::FontFeatureSettings(_)
+    | PropertyId::FontVariationSettings
     | PropertyId::LineHeight
     | PropertyId::Font => true,
     _ => false,
@@ -2,13 +2,15 @@
 
 #![allow(non_upper_case_globals)]
 
+use crate::compat::Feature;
 use crate::context::PropertyHandlerContext;
 use crate::declaration::{DeclarationBlock, DeclarationList};
 use crate::error::{Error, ErrorLocation, ParserError, PrinterError, PrinterErrorKind};
 use crate::macros::{define_shorthand, impl_shorthand};
 use crate::printer::Printer;
 use crate::properties::{Property, PropertyId};
-use crate::traits::{Parse, PropertyHandler, Shorthand, ToCss};
+use crate::targets::Browsers;
+use crate::traits::{IsCompatible, Parse, PropertyHandler, Shorthand, ToCss};
 use crate::values::ident::CustomIdent;
 use crate::values::length::serialize_dimension;
 use crate::values::number::{CSSInteger, CSSNumber};
@@ -39,6 +41,56 @@ pub enum TrackSizing<'i> {
   /// A list of grid tracks.
   #[cfg_attr(feature = "serde", serde(borrow))]
   TrackList(TrackList<'i>),
+  /// The `subgrid` keyword, with an optional list of line names.
+  Subgrid {
+    /// A list of line names, or repeated line names.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    line_names: Vec<SubgridLineNames<'i>>,
+  },
+  /// The `masonry` keyword.
+  Masonry,
+}
+
+/// An item in a [`<line-name-list>`](https://drafts.csswg.org/css-grid-2/#typedef-line-name-list),
+/// used with the `subgrid` keyword.
+///
+/// See [TrackSizing](TrackSizing).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum SubgridLineNames<'i> {
+  /// A list of line names.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  Names(CustomIdentList<'i>),
+  /// A `repeat()` function containing line names.
+  Repeat(SubgridRepeat<'i>),
+}
+
+/// A [`<name-repeat>`](https://drafts.csswg.org/css-grid-2/#typedef-name-repeat) value,
+/// used to repeat line names within a subgrid.
+///
+/// See [SubgridLineNames](SubgridLineNames).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct SubgridRepeat<'i> {
+  /// The repeat count. Either a positive integer or `auto-fill`.
+  pub count: RepeatCount,
+  /// The line names to repeat.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  pub line_names: Vec<CustomIdentList<'i>>,
 }
 
 /// A [`<track-list>`](https://drafts.csswg.org/css-grid-2/#typedef-track-list) value,
@@ -518,6 +570,24 @@ impl<'i> Parse<'i> for TrackSizing<'i> {
       return Ok(TrackSizing::None);
     }
 
+    if input.try_parse(|input| input.expect_ident_matching("masonry")).is_ok() {
+      return Ok(TrackSizing::Masonry);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("subgrid")).is_ok() {
+      let mut line_names = Vec::new();
+      loop {
+        if let Ok(names) = input.try_parse(parse_line_names) {
+          line_names.push(SubgridLineNames::Names(names));
+        } else if let Ok(repeat) = input.try_parse(SubgridRepeat::parse) {
+          line_names.push(SubgridLineNames::Repeat(repeat));
+        } else {
+          break;
+        }
+      }
+      return Ok(TrackSizing::Subgrid { line_names });
+    }
+
     let track_list = TrackList::parse(input)?;
     Ok(TrackSizing::TrackList(track_list))
   }
@@ -531,6 +601,25 @@ impl<'i> ToCss for TrackSizing<'i> {
     match self {
       TrackSizing::None => dest.write_str("none"),
       TrackSizing::TrackList(list) => list.to_css(dest),
+      TrackSizing::Subgrid { line_names } => {
+        dest.write_str("subgrid")?;
+        let mut first = true;
+        for names in line_names {
+          // Whitespace is required between the keyword and a repeat() function.
+          if first && matches!(names, SubgridLineNames::Repeat(_)) {
+            dest.write_char(' ')?;
+          } else {
+            dest.whitespace()?;
+          }
+          first = false;
+          match names {
+            SubgridLineNames::Names(names) => serialize_line_names(names, dest)?,
+            SubgridLineNames::Repeat(repeat) => repeat.to_css(dest)?,
+          }
+        }
+        Ok(())
+      }
+      TrackSizing::Masonry => dest.write_str("masonry"),
     }
   }
 }
@@ -540,10 +629,70 @@ impl<'i> TrackSizing<'i> {
     match self {
       TrackSizing::None => true,
       TrackSizing::TrackList(list) => list.is_explicit(),
+      TrackSizing::Subgrid { .. } | TrackSizing::Masonry => false,
+    }
+  }
+}
+
+impl<'i> IsCompatible for TrackSizing<'i> {
+  fn is_compatible(&self, browsers: Browsers) -> bool {
+    match self {
+      TrackSizing::Subgrid { .. } => Feature::Subgrid.is_compatible(browsers),
+      TrackSizing::Masonry => Feature::Masonry.is_compatible(browsers),
+      _ => true,
     }
   }
 }
 
+impl<'i> Parse<'i> for SubgridRepeat<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    input.expect_function_matching("repeat")?;
+    input.parse_nested_block(|input| {
+      let location = input.current_source_location();
+      let count = match RepeatCount::parse(input)? {
+        RepeatCount::Number(num) if num < 1 => {
+          return Err(location.new_custom_error(ParserError::InvalidValue));
+        }
+        RepeatCount::AutoFit => return Err(location.new_custom_error(ParserError::InvalidValue)),
+        count => count,
+      };
+
+      input.expect_comma()?;
+      let mut line_names = Vec::new();
+      while let Ok(names) = input.try_parse(parse_line_names) {
+        line_names.push(names);
+      }
+
+      if line_names.is_empty() {
+        return Err(input.new_custom_error(ParserError::InvalidValue));
+      }
+
+      Ok(SubgridRepeat { count, line_names })
+    })
+  }
+}
+
+impl<'i> ToCss for SubgridRepeat<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    dest.write_str("repeat(")?;
+    self.count.to_css(dest)?;
+    dest.delim(',', false)?;
+    let mut first = true;
+    for names in &self.line_names {
+      if first {
+        first = false;
+      } else {
+        dest.whitespace()?;
+      }
+      serialize_line_names(names, dest)?;
+    }
+    dest.write_char(')')
+  }
+}
+
 impl<'i> Parse<'i> for TrackSizeList {
   fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
     let mut res = SmallVec::new();
@@ -1650,8 +1799,19 @@ impl<'i> PropertyHandler<'i> for GridHandler<'i> {
     use Property::*;
 
     match property {
-      GridTemplateColumns(columns) => self.columns = Some(columns.clone()),
-      GridTemplateRows(rows) => self.rows = Some(rows.clone()),
+      GridTemplateColumns(columns) => {
+        // Preserve the previous value as a fallback if the new one is not supported by all targets.
+        if self.columns.is_some() && matches!(context.targets, Some(targets) if !columns.is_compatible(targets)) {
+          self.finalize(dest, context);
+        }
+        self.columns = Some(columns.clone())
+      }
+      GridTemplateRows(rows) => {
+        if self.rows.is_some() && matches!(context.targets, Some(targets) if !rows.is_compatible(targets)) {
+          self.finalize(dest, context);
+        }
+        self.rows = Some(rows.clone())
+      }
       GridTemplateAreas(areas) => self.areas = Some(areas.clone()),
       GridAutoColumns(auto_columns) => self.auto_columns = Some(auto_columns.clone()),
       GridAutoRows(auto_rows) => self.auto_rows = Some(auto_rows.clone()),
@@ -1698,7 +1858,7 @@ impl<'i> PropertyHandler<'i> for GridHandler<'i> {
     true
   }
 
-  fn finalize(&mut self, dest: &mut DeclarationList<'i>, _: &mut PropertyHandlerContext<'i, '_>) {
+  fn finalize(&mut self, dest: &mut DeclarationList<'i>, context: &mut PropertyHandlerContext<'i, '_>) {
     if !self.has_any {
       return;
     }
@@ -1716,7 +1876,15 @@ impl<'i> PropertyHandler<'i> for GridHandler<'i> {
     let mut column_start = std::mem::take(&mut self.column_start);
     let mut column_end = std::mem::take(&mut self.column_end);
 
-    if let (Some(rows_val), Some(columns_val), Some(areas_val)) = (&rows, &columns, &areas) {
+    // Avoid merging values that are not supported by all targets into a shorthand,
+    // otherwise the entire shorthand would be invalid in those browsers.
+    let is_compatible = |val: &Option<TrackSizing>| match (val, context.targets) {
+      (Some(val), Some(targets)) => val.is_compatible(targets),
+      _ => true,
+    };
+    let can_merge = is_compatible(&rows) && is_compatible(&columns);
+
+    if let (true, Some(rows_val), Some(columns_val), Some(areas_val)) = (can_merge, &rows, &columns, &areas) {
       let mut has_template = true;
       if let (Some(auto_rows_val), Some(auto_columns_val), Some(auto_flow_val)) =
         (&auto_rows, &auto_columns, &auto_flow)
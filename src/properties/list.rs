@@ -8,6 +8,7 @@ use crate::macros::{define_shorthand, enum_property, shorthand_handler, shorthan
 use crate::printer::Printer;
 use crate::targets::Browsers;
 use crate::traits::{FallbackValues, Parse, PropertyHandler, Shorthand, ToCss};
+use crate::values::number::CSSInteger;
 use crate::values::string::CSSString;
 use crate::values::{ident::CustomIdent, image::Image};
 #[cfg(feature = "visitor")]
@@ -294,6 +295,204 @@ impl<'i> ToCss for Symbol<'i> {
   }
 }
 
+/// A value for the [counter-increment](https://drafts.csswg.org/css-lists-3/#propdef-counter-increment)
+/// and [counter-set](https://drafts.csswg.org/css-lists-3/#propdef-counter-set) properties.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum CounterChangeList<'i> {
+  /// The `none` keyword.
+  None,
+  /// A list of counters to change.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  Counters(Vec<CounterChange<'i>>),
+}
+
+/// A counter name with an optional value, as used in the
+/// [counter-increment](https://drafts.csswg.org/css-lists-3/#propdef-counter-increment)
+/// and [counter-set](https://drafts.csswg.org/css-lists-3/#propdef-counter-set) properties.
+///
+/// See [CounterChangeList](CounterChangeList).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct CounterChange<'i> {
+  /// The name of the counter.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  pub name: CustomIdent<'i>,
+  /// The value to increment or set the counter to, if any.
+  pub value: Option<CSSInteger>,
+}
+
+impl<'i> Parse<'i> for CounterChangeList<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(CounterChangeList::None);
+    }
+
+    let mut counters = Vec::new();
+    while let Ok(name) = input.try_parse(CustomIdent::parse) {
+      let value = input.try_parse(CSSInteger::parse).ok();
+      counters.push(CounterChange { name, value });
+    }
+
+    if counters.is_empty() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(CounterChangeList::Counters(counters))
+  }
+}
+
+impl ToCss for CounterChangeList<'_> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      CounterChangeList::None => dest.write_str("none"),
+      CounterChangeList::Counters(counters) => {
+        let mut first = true;
+        for counter in counters {
+          if first {
+            first = false;
+          } else {
+            dest.write_char(' ')?;
+          }
+          serialize_counter_name(&counter.name, dest)?;
+          if let Some(value) = counter.value {
+            dest.write_char(' ')?;
+            value.to_css(dest)?;
+          }
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+/// A value for the [counter-reset](https://drafts.csswg.org/css-lists-3/#propdef-counter-reset) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum CounterResetList<'i> {
+  /// The `none` keyword.
+  None,
+  /// A list of counters to reset.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  Counters(Vec<CounterReset<'i>>),
+}
+
+/// A counter name with an optional initial value, as used in the
+/// [counter-reset](https://drafts.csswg.org/css-lists-3/#propdef-counter-reset) property.
+///
+/// See [CounterResetList](CounterResetList).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct CounterReset<'i> {
+  /// The name of the counter.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  pub name: CustomIdent<'i>,
+  /// Whether the counter is reversed, i.e. `reversed(<name>)`.
+  pub reversed: bool,
+  /// The initial value of the counter, if any.
+  pub value: Option<CSSInteger>,
+}
+
+impl<'i> Parse<'i> for CounterResetList<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(CounterResetList::None);
+    }
+
+    let mut counters = Vec::new();
+    loop {
+      let (name, reversed) = if let Ok(name) = input.try_parse(CustomIdent::parse) {
+        (name, false)
+      } else if input.try_parse(|input| input.expect_function_matching("reversed")).is_ok() {
+        (input.parse_nested_block(CustomIdent::parse)?, true)
+      } else {
+        break;
+      };
+
+      let value = input.try_parse(CSSInteger::parse).ok();
+      counters.push(CounterReset { name, reversed, value });
+    }
+
+    if counters.is_empty() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(CounterResetList::Counters(counters))
+  }
+}
+
+impl ToCss for CounterResetList<'_> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      CounterResetList::None => dest.write_str("none"),
+      CounterResetList::Counters(counters) => {
+        let mut first = true;
+        for counter in counters {
+          if first {
+            first = false;
+          } else {
+            dest.write_char(' ')?;
+          }
+          if counter.reversed {
+            dest.write_str("reversed(")?;
+            serialize_counter_name(&counter.name, dest)?;
+            dest.write_char(')')?;
+          } else {
+            serialize_counter_name(&counter.name, dest)?;
+          }
+          if let Some(value) = counter.value {
+            dest.write_char(' ')?;
+            value.to_css(dest)?;
+          }
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+/// Serializes a counter name, which may be renamed when compiled as part of a CSS module.
+/// The built-in `list-item` counter is never renamed.
+pub(crate) fn serialize_counter_name<W>(name: &CustomIdent, dest: &mut Printer<W>) -> Result<(), PrinterError>
+where
+  W: std::fmt::Write,
+{
+  if name.0.eq_ignore_ascii_case("list-item") {
+    return dest.write_str("list-item");
+  }
+
+  if let Some(css_module) = &mut dest.css_module {
+    css_module.reference(&name.0, dest.loc.source_index)
+  }
+  name.to_css(dest)
+}
+
 enum_property! {
   /// A value for the [list-style-position](https://www.w3.org/TR/2020/WD-css-lists-3-20201117/#list-style-position-property) property.
   pub enum ListStylePosition {
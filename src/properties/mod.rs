@@ -97,7 +97,9 @@ pub mod border;
 pub mod border_image;
 pub mod border_radius;
 pub mod box_shadow;
+pub mod columns;
 pub mod contain;
+pub mod content;
 pub mod css_modules;
 pub mod custom;
 pub mod display;
@@ -113,6 +115,7 @@ pub mod outline;
 pub mod overflow;
 pub mod position;
 pub(crate) mod prefix_handler;
+pub mod scroll;
 pub mod size;
 pub mod svg;
 pub mod text;
@@ -145,7 +148,9 @@ use border::*;
 use border_image::*;
 use border_radius::*;
 use box_shadow::*;
+use columns::*;
 use contain::*;
+use content::*;
 use css_modules::*;
 use cssparser::*;
 use custom::*;
@@ -160,6 +165,7 @@ use margin_padding::*;
 use masking::*;
 use outline::*;
 use overflow::*;
+use scroll::*;
 use size::*;
 use smallvec::{smallvec, SmallVec};
 use svg::*;
@@ -1206,6 +1212,12 @@ define_properties! {
   "inset-inline": InsetInline(InsetInline) shorthand: true,
   "inset": Inset(Inset) shorthand: true,
 
+  // https://drafts.csswg.org/css-anchor-position-1
+  "anchor-name": AnchorName(position::AnchorName<'i>),
+  "position-anchor": PositionAnchor(position::PositionAnchor<'i>),
+  "position-area": PositionArea(position::PositionArea),
+  "inset-area": InsetArea(position::PositionArea),
+
   "border-spacing": BorderSpacing(Size2D<Length>),
 
   "border-top-color": BorderTopColor(CssColor) [logical_group: BorderColor, category: Physical],
@@ -1305,6 +1317,16 @@ define_properties! {
   "column-gap": ColumnGap(GapValue),
   "gap": Gap(Gap) shorthand: true,
 
+  "column-width": ColumnWidth(ColumnWidth, VendorPrefix) / WebKit / Moz,
+  "column-count": ColumnCount(ColumnCount, VendorPrefix) / WebKit / Moz,
+  "columns": Columns(Columns, VendorPrefix) / WebKit / Moz shorthand: true,
+  "column-rule-width": ColumnRuleWidth(BorderSideWidth, VendorPrefix) / WebKit / Moz,
+  "column-rule-style": ColumnRuleStyle(LineStyle, VendorPrefix) / WebKit / Moz,
+  "column-rule-color": ColumnRuleColor(CssColor, VendorPrefix) / WebKit / Moz,
+  "column-rule": ColumnRule(ColumnRule, VendorPrefix) / WebKit / Moz shorthand: true,
+  "column-span": ColumnSpan(ColumnSpan, VendorPrefix) / WebKit / Moz,
+  "column-fill": ColumnFill(ColumnFill, VendorPrefix) / WebKit / Moz,
+
   // Old flex (2009): https://www.w3.org/TR/2009/WD-css3-flexbox-20090723/
   "box-orient": BoxOrient(BoxOrient, VendorPrefix) / WebKit / Moz unprefixed: false,
   "box-direction": BoxDirection(BoxDirection, VendorPrefix) / WebKit / Moz unprefixed: false,
@@ -1421,12 +1443,34 @@ define_properties! {
   "scroll-padding-inline": ScrollPaddingInline(ScrollPaddingInline) shorthand: true,
   "scroll-padding": ScrollPadding(ScrollPadding) shorthand: true,
 
+  "scroll-snap-type": ScrollSnapType(ScrollSnapType),
+  "scroll-snap-align": ScrollSnapAlign(ScrollSnapAlign),
+  "scroll-snap-stop": ScrollSnapStop(ScrollSnapStop),
+  "scroll-behavior": ScrollBehavior(ScrollBehavior),
+  "scrollbar-gutter": ScrollbarGutter(ScrollbarGutter),
+  "overscroll-behavior-x": OverscrollBehaviorX(OverscrollBehaviorKeyword) [logical_group: OverscrollBehavior, category: Physical],
+  "overscroll-behavior-y": OverscrollBehaviorY(OverscrollBehaviorKeyword) [logical_group: OverscrollBehavior, category: Physical],
+  "overscroll-behavior-block": OverscrollBehaviorBlock(OverscrollBehaviorKeyword) [logical_group: OverscrollBehavior, category: Logical],
+  "overscroll-behavior-inline": OverscrollBehaviorInline(OverscrollBehaviorKeyword) [logical_group: OverscrollBehavior, category: Logical],
+  "overscroll-behavior": OverscrollBehavior(OverscrollBehavior) shorthand: true,
+
   "font-weight": FontWeight(FontWeight),
   "font-size": FontSize(FontSize),
   "font-stretch": FontStretch(FontStretch),
   "font-family": FontFamily(Vec<FontFamily<'i>>),
   "font-style": FontStyle(FontStyle),
   "font-variant-caps": FontVariantCaps(FontVariantCaps),
+  "font-variant-ligatures": FontVariantLigatures(FontVariantLigatures),
+  "font-variant-alternates": FontVariantAlternates(FontVariantAlternates<'i>),
+  "font-variant-numeric": FontVariantNumeric(FontVariantNumeric),
+  "font-variant-east-asian": FontVariantEastAsian(FontVariantEastAsian),
+  "font-variant-position": FontVariantPosition(FontVariantPosition),
+  "font-variant": FontVariant(FontVariant<'i>) shorthand: true,
+  "font-kerning": FontKerning(FontKerning),
+  "font-optical-sizing": FontOpticalSizing(FontOpticalSizing),
+  "font-synthesis": FontSynthesis(FontSynthesis),
+  "font-feature-settings": FontFeatureSettings(FontFeatureSettings<'i>, VendorPrefix) / WebKit / Moz,
+  "font-variation-settings": FontVariationSettings(FontVariationSettings<'i>),
   "line-height": LineHeight(LineHeight),
   "font": Font(Font<'i>) shorthand: true,
   "vertical-align": VerticalAlign(VerticalAlign),
@@ -1447,6 +1491,19 @@ define_properties! {
   "animation-delay": AnimationDelay(SmallVec<[Time; 1]>, VendorPrefix) / WebKit / Moz / O,
   "animation-fill-mode": AnimationFillMode(SmallVec<[AnimationFillMode; 1]>, VendorPrefix) / WebKit / Moz / O,
   "animation": Animation(AnimationList<'i>, VendorPrefix) / WebKit / Moz / O shorthand: true,
+  "animation-composition": AnimationComposition(SmallVec<[AnimationComposition; 1]>),
+  "animation-timeline": AnimationTimeline(SmallVec<[AnimationTimeline<'i>; 1]>),
+  "animation-range-start": AnimationRangeStart(SmallVec<[AnimationRangeStart; 1]>),
+  "animation-range-end": AnimationRangeEnd(SmallVec<[AnimationRangeEnd; 1]>),
+  "animation-range": AnimationRange(SmallVec<[AnimationRange; 1]>) shorthand: true,
+  "scroll-timeline-name": ScrollTimelineName(SmallVec<[TimelineName<'i>; 1]>),
+  "scroll-timeline-axis": ScrollTimelineAxis(SmallVec<[ScrollAxis; 1]>),
+  "scroll-timeline": ScrollTimeline(SmallVec<[ScrollTimeline<'i>; 1]>) shorthand: true,
+  "view-timeline-name": ViewTimelineName(SmallVec<[TimelineName<'i>; 1]>),
+  "view-timeline-axis": ViewTimelineAxis(SmallVec<[ScrollAxis; 1]>),
+  "view-timeline-inset": ViewTimelineInset(SmallVec<[Size2D<LengthPercentageOrAuto>; 1]>),
+  "view-timeline": ViewTimeline(SmallVec<[ViewTimeline<'i>; 1]>) shorthand: true,
+  "timeline-scope": TimelineScope(TimelineScope<'i>),
 
   // https://drafts.csswg.org/css-transforms-2/
   "transform": Transform(TransformList, VendorPrefix) / WebKit / Moz / Ms / O,
@@ -1505,12 +1562,22 @@ define_properties! {
   "accent-color": AccentColor(ColorOrAuto),
   "appearance": Appearance(Appearance<'i>, VendorPrefix) / WebKit / Moz / Ms,
 
+  // https://drafts.csswg.org/css-color-adjust/#color-scheme-prop
+  "color-scheme": ColorScheme(ColorScheme),
+
   // https://www.w3.org/TR/2020/WD-css-lists-3-20201117
   "list-style-type": ListStyleType(ListStyleType<'i>),
   "list-style-image": ListStyleImage(Image<'i>),
   "list-style-position": ListStylePosition(ListStylePosition),
   "list-style": ListStyle(ListStyle<'i>) shorthand: true,
   "marker-side": MarkerSide(MarkerSide),
+  "counter-reset": CounterReset(CounterResetList<'i>),
+  "counter-increment": CounterIncrement(CounterChangeList<'i>),
+  "counter-set": CounterSet(CounterChangeList<'i>),
+
+  // https://drafts.csswg.org/css-content-3/
+  "content": Content(Content<'i>),
+  "quotes": Quotes(Quotes<'i>),
 
   // CSS modules
   "composes": Composes(Composes<'i>) if css_modules,
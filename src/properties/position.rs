@@ -4,15 +4,19 @@ use super::Property;
 use crate::context::PropertyHandlerContext;
 use crate::declaration::DeclarationList;
 use crate::error::{ParserError, PrinterError};
+use crate::macros::enum_property;
 use crate::prefixes::Feature;
 use crate::printer::Printer;
+use crate::stylesheet::ParserOptions;
 use crate::targets::Browsers;
-use crate::traits::{Parse, PropertyHandler, ToCss};
+use crate::traits::{Parse, ParseWithOptions, PropertyHandler, ToCss};
+use crate::values::ident::{DashedIdent, DashedIdentReference};
 use crate::values::number::CSSInteger;
 use crate::vendor_prefix::VendorPrefix;
 #[cfg(feature = "visitor")]
 use crate::visitor::Visit;
 use cssparser::*;
+use smallvec::SmallVec;
 
 /// A value for the [position](https://www.w3.org/TR/css-position-3/#position-property) property.
 #[derive(Debug, Clone, PartialEq)]
@@ -173,3 +177,274 @@ impl<'i> PropertyHandler<'i> for PositionHandler {
     }
   }
 }
+
+/// A value for the [anchor-name](https://drafts.csswg.org/css-anchor-position-1/#name) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum AnchorName<'i> {
+  /// The element is not an anchor element.
+  None,
+  /// A list of anchor names for the element.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  Names(SmallVec<[DashedIdent<'i>; 1]>),
+}
+
+impl<'i> Parse<'i> for AnchorName<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(AnchorName::None);
+    }
+
+    let names = input.parse_comma_separated(DashedIdent::parse)?;
+    Ok(AnchorName::Names(names.into()))
+  }
+}
+
+impl<'i> ToCss for AnchorName<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      AnchorName::None => dest.write_str("none"),
+      AnchorName::Names(names) => {
+        let mut first = true;
+        for name in names {
+          if first {
+            first = false;
+          } else {
+            dest.delim(',', false)?;
+          }
+          name.to_css(dest)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+/// A value for the [position-anchor](https://drafts.csswg.org/css-anchor-position-1/#position-anchor) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum PositionAnchor<'i> {
+  /// The default anchor is the element's implicit anchor, if any.
+  Auto,
+  /// The default anchor is the element with the given anchor name.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  DashedIdent(DashedIdentReference<'i>),
+}
+
+impl<'i> ParseWithOptions<'i> for PositionAnchor<'i> {
+  fn parse_with_options<'t>(
+    input: &mut Parser<'i, 't>,
+    options: &ParserOptions<'_, 'i>,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      return Ok(PositionAnchor::Auto);
+    }
+
+    let name = DashedIdentReference::parse_with_options(input, options)?;
+    Ok(PositionAnchor::DashedIdent(name))
+  }
+}
+
+impl<'i> ToCss for PositionAnchor<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      PositionAnchor::Auto => dest.write_str("auto"),
+      PositionAnchor::DashedIdent(name) => name.to_css(dest),
+    }
+  }
+}
+
+enum_property! {
+  /// A keyword for the [position-area](https://drafts.csswg.org/css-anchor-position-1/#position-area) property.
+  pub enum PositionAreaKeyword {
+    /// The center region of the grid.
+    "center": Center,
+    /// Spans all regions of the grid in the axis.
+    "span-all": SpanAll,
+    /// The left region of the grid.
+    "left": Left,
+    /// The right region of the grid.
+    "right": Right,
+    /// The left and center regions of the grid.
+    "span-left": SpanLeft,
+    /// The center and right regions of the grid.
+    "span-right": SpanRight,
+    /// The start region of the horizontal axis, in the containing block's writing mode.
+    "x-start": XStart,
+    /// The end region of the horizontal axis, in the containing block's writing mode.
+    "x-end": XEnd,
+    /// The start and center regions of the horizontal axis, in the containing block's writing mode.
+    "span-x-start": SpanXStart,
+    /// The center and end regions of the horizontal axis, in the containing block's writing mode.
+    "span-x-end": SpanXEnd,
+    /// The start region of the horizontal axis, in the element's own writing mode.
+    "x-self-start": XSelfStart,
+    /// The end region of the horizontal axis, in the element's own writing mode.
+    "x-self-end": XSelfEnd,
+    /// The start and center regions of the horizontal axis, in the element's own writing mode.
+    "span-x-self-start": SpanXSelfStart,
+    /// The center and end regions of the horizontal axis, in the element's own writing mode.
+    "span-x-self-end": SpanXSelfEnd,
+    /// The top region of the grid.
+    "top": Top,
+    /// The bottom region of the grid.
+    "bottom": Bottom,
+    /// The top and center regions of the grid.
+    "span-top": SpanTop,
+    /// The center and bottom regions of the grid.
+    "span-bottom": SpanBottom,
+    /// The start region of the vertical axis, in the containing block's writing mode.
+    "y-start": YStart,
+    /// The end region of the vertical axis, in the containing block's writing mode.
+    "y-end": YEnd,
+    /// The start and center regions of the vertical axis, in the containing block's writing mode.
+    "span-y-start": SpanYStart,
+    /// The center and end regions of the vertical axis, in the containing block's writing mode.
+    "span-y-end": SpanYEnd,
+    /// The start region of the vertical axis, in the element's own writing mode.
+    "y-self-start": YSelfStart,
+    /// The end region of the vertical axis, in the element's own writing mode.
+    "y-self-end": YSelfEnd,
+    /// The start and center regions of the vertical axis, in the element's own writing mode.
+    "span-y-self-start": SpanYSelfStart,
+    /// The center and end regions of the vertical axis, in the element's own writing mode.
+    "span-y-self-end": SpanYSelfEnd,
+    /// The start region of the block axis, in the containing block's writing mode.
+    "block-start": BlockStart,
+    /// The end region of the block axis, in the containing block's writing mode.
+    "block-end": BlockEnd,
+    /// The start and center regions of the block axis, in the containing block's writing mode.
+    "span-block-start": SpanBlockStart,
+    /// The center and end regions of the block axis, in the containing block's writing mode.
+    "span-block-end": SpanBlockEnd,
+    /// The start region of the inline axis, in the containing block's writing mode.
+    "inline-start": InlineStart,
+    /// The end region of the inline axis, in the containing block's writing mode.
+    "inline-end": InlineEnd,
+    /// The start and center regions of the inline axis, in the containing block's writing mode.
+    "span-inline-start": SpanInlineStart,
+    /// The center and end regions of the inline axis, in the containing block's writing mode.
+    "span-inline-end": SpanInlineEnd,
+    /// The start region of the block axis, in the element's own writing mode.
+    "self-block-start": SelfBlockStart,
+    /// The end region of the block axis, in the element's own writing mode.
+    "self-block-end": SelfBlockEnd,
+    /// The start and center regions of the block axis, in the element's own writing mode.
+    "span-self-block-start": SpanSelfBlockStart,
+    /// The center and end regions of the block axis, in the element's own writing mode.
+    "span-self-block-end": SpanSelfBlockEnd,
+    /// The start region of the inline axis, in the element's own writing mode.
+    "self-inline-start": SelfInlineStart,
+    /// The end region of the inline axis, in the element's own writing mode.
+    "self-inline-end": SelfInlineEnd,
+    /// The start and center regions of the inline axis, in the element's own writing mode.
+    "span-self-inline-start": SpanSelfInlineStart,
+    /// The center and end regions of the inline axis, in the element's own writing mode.
+    "span-self-inline-end": SpanSelfInlineEnd,
+    /// The start region of the axis, in the containing block's writing mode.
+    "start": Start,
+    /// The end region of the axis, in the containing block's writing mode.
+    "end": End,
+    /// The start and center regions of the axis, in the containing block's writing mode.
+    "span-start": SpanStart,
+    /// The center and end regions of the axis, in the containing block's writing mode.
+    "span-end": SpanEnd,
+    /// The start region of the axis, in the element's own writing mode.
+    "self-start": SelfStart,
+    /// The end region of the axis, in the element's own writing mode.
+    "self-end": SelfEnd,
+    /// The start and center regions of the axis, in the element's own writing mode.
+    "span-self-start": SpanSelfStart,
+    /// The center and end regions of the axis, in the element's own writing mode.
+    "span-self-end": SpanSelfEnd,
+  }
+}
+
+impl PositionAreaKeyword {
+  /// Returns whether the keyword does not specify which axis it applies to.
+  fn is_axis_ambiguous(&self) -> bool {
+    use PositionAreaKeyword::*;
+    matches!(
+      self,
+      Center | SpanAll | Start | End | SpanStart | SpanEnd | SelfStart | SelfEnd | SpanSelfStart | SpanSelfEnd
+    )
+  }
+
+  /// Returns the second keyword implied when only this keyword is specified.
+  fn implied_second(&self) -> PositionAreaKeyword {
+    if self.is_axis_ambiguous() {
+      *self
+    } else {
+      PositionAreaKeyword::SpanAll
+    }
+  }
+}
+
+/// A value for the [position-area](https://drafts.csswg.org/css-anchor-position-1/#position-area) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum PositionArea {
+  /// The property has no effect.
+  None,
+  /// A region of the position-area grid, specified by one or two keywords.
+  Keywords(PositionAreaKeyword, PositionAreaKeyword),
+}
+
+impl<'i> Parse<'i> for PositionArea {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(PositionArea::None);
+    }
+
+    let first = PositionAreaKeyword::parse(input)?;
+    let second = input
+      .try_parse(PositionAreaKeyword::parse)
+      .unwrap_or_else(|_| first.implied_second());
+    Ok(PositionArea::Keywords(first, second))
+  }
+}
+
+impl ToCss for PositionArea {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      PositionArea::None => dest.write_str("none"),
+      PositionArea::Keywords(first, second) => {
+        first.to_css(dest)?;
+        if *second != first.implied_second() {
+          dest.write_char(' ')?;
+          second.to_css(dest)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
@@ -0,0 +1,373 @@
+//! CSS properties related to scrolling.
+
+use super::{Property, PropertyId};
+use crate::compat::Feature;
+use crate::context::PropertyHandlerContext;
+use crate::declaration::{DeclarationBlock, DeclarationList};
+use crate::error::{ParserError, PrinterError};
+use crate::macros::{define_shorthand, enum_property};
+use crate::printer::Printer;
+use crate::traits::{Parse, PropertyHandler, Shorthand, ToCss};
+#[cfg(feature = "visitor")]
+use crate::visitor::Visit;
+use cssparser::*;
+
+enum_property! {
+  /// A value for the [scroll-behavior](https://www.w3.org/TR/css-overflow-3/#smooth-scrolling) property.
+  pub enum ScrollBehavior {
+    /// The scrolling box is scrolled instantly.
+    Auto,
+    /// The scrolling box is scrolled smoothly.
+    Smooth,
+  }
+}
+
+/// A value for the [scrollbar-gutter](https://www.w3.org/TR/css-overflow-4/#scrollbar-gutter-property) property.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum ScrollbarGutter {
+  /// Space for the scrollbar is only reserved when the box overflows.
+  Auto,
+  /// Space for the scrollbar is always reserved.
+  Stable,
+  /// Space for the scrollbar is always reserved on both edges of the box.
+  StableBothEdges,
+}
+
+impl<'i> Parse<'i> for ScrollbarGutter {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      return Ok(ScrollbarGutter::Auto);
+    }
+
+    let both_edges = input.try_parse(|input| input.expect_ident_matching("both-edges")).is_ok();
+    input.expect_ident_matching("stable")?;
+    if both_edges || input.try_parse(|input| input.expect_ident_matching("both-edges")).is_ok() {
+      Ok(ScrollbarGutter::StableBothEdges)
+    } else {
+      Ok(ScrollbarGutter::Stable)
+    }
+  }
+}
+
+impl ToCss for ScrollbarGutter {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      ScrollbarGutter::Auto => dest.write_str("auto"),
+      ScrollbarGutter::Stable => dest.write_str("stable"),
+      ScrollbarGutter::StableBothEdges => dest.write_str("stable both-edges"),
+    }
+  }
+}
+
+enum_property! {
+  /// A scroll snap axis, as used in the [scroll-snap-type](https://www.w3.org/TR/css-scroll-snap-1/#scroll-snap-type) property.
+  pub enum ScrollSnapAxis {
+    /// The horizontal axis.
+    X,
+    /// The vertical axis.
+    Y,
+    /// The block axis.
+    Block,
+    /// The inline axis.
+    Inline,
+    /// Both axes.
+    Both,
+  }
+}
+
+enum_property! {
+  /// A scroll snap strictness, as used in the [scroll-snap-type](https://www.w3.org/TR/css-scroll-snap-1/#scroll-snap-type) property.
+  pub enum ScrollSnapStrictness {
+    /// The scroll container must rest on a snap position when there are no active scrolling operations.
+    Mandatory,
+    /// The scroll container may snap to a snap position when there are no active scrolling operations.
+    Proximity,
+  }
+}
+
+impl Default for ScrollSnapStrictness {
+  fn default() -> ScrollSnapStrictness {
+    ScrollSnapStrictness::Proximity
+  }
+}
+
+/// A value for the [scroll-snap-type](https://www.w3.org/TR/css-scroll-snap-1/#scroll-snap-type) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum ScrollSnapType {
+  /// The scroll container does not snap.
+  None,
+  /// The scroll container snaps on the given axis.
+  Snap {
+    /// The axis to snap on.
+    axis: ScrollSnapAxis,
+    /// The snapping strictness.
+    strictness: ScrollSnapStrictness,
+  },
+}
+
+impl<'i> Parse<'i> for ScrollSnapType {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(ScrollSnapType::None);
+    }
+
+    let axis = ScrollSnapAxis::parse(input)?;
+    let strictness = input.try_parse(ScrollSnapStrictness::parse).unwrap_or_default();
+    Ok(ScrollSnapType::Snap { axis, strictness })
+  }
+}
+
+impl ToCss for ScrollSnapType {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      ScrollSnapType::None => dest.write_str("none"),
+      ScrollSnapType::Snap { axis, strictness } => {
+        axis.to_css(dest)?;
+        if *strictness != ScrollSnapStrictness::default() {
+          dest.write_char(' ')?;
+          strictness.to_css(dest)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+enum_property! {
+  /// A snap alignment keyword, as used in the [scroll-snap-align](https://www.w3.org/TR/css-scroll-snap-1/#scroll-snap-align) property.
+  pub enum ScrollSnapAlignKeyword {
+    /// The box does not define a snap position in this axis.
+    None,
+    /// The start edge of the box is aligned with the snapport.
+    Start,
+    /// The end edge of the box is aligned with the snapport.
+    End,
+    /// The center of the box is aligned with the snapport.
+    Center,
+  }
+}
+
+/// A value for the [scroll-snap-align](https://www.w3.org/TR/css-scroll-snap-1/#scroll-snap-align) property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct ScrollSnapAlign {
+  /// The snap alignment in the block axis.
+  pub block: ScrollSnapAlignKeyword,
+  /// The snap alignment in the inline axis.
+  pub inline: ScrollSnapAlignKeyword,
+}
+
+impl<'i> Parse<'i> for ScrollSnapAlign {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let block = ScrollSnapAlignKeyword::parse(input)?;
+    let inline = input.try_parse(ScrollSnapAlignKeyword::parse).unwrap_or(block);
+    Ok(ScrollSnapAlign { block, inline })
+  }
+}
+
+impl ToCss for ScrollSnapAlign {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    self.block.to_css(dest)?;
+    if self.inline != self.block {
+      dest.write_char(' ')?;
+      self.inline.to_css(dest)?;
+    }
+    Ok(())
+  }
+}
+
+enum_property! {
+  /// A value for the [scroll-snap-stop](https://www.w3.org/TR/css-scroll-snap-1/#scroll-snap-stop) property.
+  pub enum ScrollSnapStop {
+    /// The scroll container may pass over a snap position.
+    Normal,
+    /// The scroll container must not pass over a snap position.
+    Always,
+  }
+}
+
+enum_property! {
+  /// An [overscroll-behavior](https://www.w3.org/TR/css-overscroll-1/#overscroll-behavior-properties) keyword,
+  /// as used in the `overscroll-behavior` shorthand and its longhands.
+  pub enum OverscrollBehaviorKeyword {
+    /// Scroll chaining and overscroll affordances are allowed.
+    Auto,
+    /// Scroll chaining to neighboring scroll containers is prevented.
+    Contain,
+    /// Scroll chaining and overscroll affordances are prevented.
+    None,
+  }
+}
+
+define_shorthand! {
+  /// A value for the [overscroll-behavior](https://www.w3.org/TR/css-overscroll-1/#overscroll-behavior-properties) shorthand property.
+  pub struct OverscrollBehavior {
+    /// The overscroll behavior in the x direction.
+    x: OverscrollBehaviorX(OverscrollBehaviorKeyword),
+    /// The overscroll behavior in the y direction.
+    y: OverscrollBehaviorY(OverscrollBehaviorKeyword),
+  }
+}
+
+impl<'i> Parse<'i> for OverscrollBehavior {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let x = OverscrollBehaviorKeyword::parse(input)?;
+    let y = input.try_parse(OverscrollBehaviorKeyword::parse).unwrap_or(x);
+    Ok(OverscrollBehavior { x, y })
+  }
+}
+
+impl ToCss for OverscrollBehavior {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    self.x.to_css(dest)?;
+    if self.y != self.x {
+      dest.write_char(' ')?;
+      self.y.to_css(dest)?;
+    }
+    Ok(())
+  }
+}
+
+#[derive(Default)]
+pub(crate) struct OverscrollBehaviorHandler {
+  x: Option<OverscrollBehaviorKeyword>,
+  y: Option<OverscrollBehaviorKeyword>,
+  block: Option<OverscrollBehaviorKeyword>,
+  inline: Option<OverscrollBehaviorKeyword>,
+}
+
+impl<'i> PropertyHandler<'i> for OverscrollBehaviorHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    context: &mut PropertyHandlerContext<'i, '_>,
+  ) -> bool {
+    use Property::*;
+
+    // The block and inline axes only depend on the writing mode, not the direction, so
+    // :dir() rules cannot be used as a fallback. When logical properties are unsupported,
+    // the physical equivalent (assuming a horizontal writing mode) is added as a fallback,
+    // followed by the logical property so that browsers supporting it use the correct
+    // axis in vertical writing modes.
+    let logical_supported = context.is_supported(Feature::LogicalOverscrollBehavior);
+
+    macro_rules! physical {
+      ($prop: ident, $val: expr) => {{
+        // Flush logical properties so that the cascade order is preserved.
+        if self.block.is_some() || self.inline.is_some() {
+          self.finalize(dest, context);
+        }
+        self.$prop = Some($val);
+      }};
+    }
+
+    macro_rules! logical {
+      ($prop: ident, $physical: ident, $val: expr) => {{
+        if logical_supported {
+          // Flush physical properties so that the cascade order is preserved.
+          if self.x.is_some() || self.y.is_some() {
+            self.finalize(dest, context);
+          }
+        } else {
+          self.$physical = Some($val);
+        }
+        self.$prop = Some($val);
+      }};
+    }
+
+    match property {
+      OverscrollBehaviorX(val) => physical!(x, *val),
+      OverscrollBehaviorY(val) => physical!(y, *val),
+      OverscrollBehavior(val) => {
+        physical!(x, val.x);
+        physical!(y, val.y);
+      }
+      OverscrollBehaviorBlock(val) => logical!(block, y, *val),
+      OverscrollBehaviorInline(val) => logical!(inline, x, *val),
+      Unparsed(val)
+        if matches!(
+          val.property_id,
+          PropertyId::OverscrollBehaviorX
+            | PropertyId::OverscrollBehaviorY
+            | PropertyId::OverscrollBehaviorBlock
+            | PropertyId::OverscrollBehaviorInline
+            | PropertyId::OverscrollBehavior
+        ) =>
+      {
+        self.finalize(dest, context);
+        if !logical_supported {
+          match val.property_id {
+            PropertyId::OverscrollBehaviorBlock => {
+              dest.push(Unparsed(val.with_property_id(PropertyId::OverscrollBehaviorY)))
+            }
+            PropertyId::OverscrollBehaviorInline => {
+              dest.push(Unparsed(val.with_property_id(PropertyId::OverscrollBehaviorX)))
+            }
+            _ => {}
+          }
+        }
+        dest.push(property.clone());
+      }
+      _ => return false,
+    }
+
+    true
+  }
+
+  fn finalize(&mut self, dest: &mut DeclarationList, _: &mut PropertyHandlerContext<'i, '_>) {
+    let x = std::mem::take(&mut self.x);
+    let y = std::mem::take(&mut self.y);
+    let block = std::mem::take(&mut self.block);
+    let inline = std::mem::take(&mut self.inline);
+
+    match (x, y) {
+      (Some(x), Some(y)) => dest.push(Property::OverscrollBehavior(OverscrollBehavior { x, y })),
+      _ => {
+        if let Some(x) = x {
+          dest.push(Property::OverscrollBehaviorX(x))
+        }
+
+        if let Some(y) = y {
+          dest.push(Property::OverscrollBehaviorY(y))
+        }
+      }
+    }
+
+    if let Some(block) = block {
+      dest.push(Property::OverscrollBehaviorBlock(block))
+    }
+
+    if let Some(inline) = inline {
+      dest.push(Property::OverscrollBehaviorInline(inline))
+    }
+  }
+}
@@ -1,18 +1,25 @@
 //! CSS properties related to user interface.
 
-use crate::declaration::DeclarationBlock;
+#![allow(non_upper_case_globals)]
+
+use crate::compat::Feature;
+use crate::context::PropertyHandlerContext;
+use crate::declaration::{DeclarationBlock, DeclarationList};
 use crate::error::{ParserError, PrinterError};
 use crate::macros::{define_shorthand, enum_property, shorthand_property};
 use crate::printer::Printer;
+use crate::properties::custom::{CustomProperty, CustomPropertyName, Token, TokenList, TokenOrValue};
 use crate::properties::{Property, PropertyId};
 use crate::targets::Browsers;
-use crate::traits::{FallbackValues, Parse, Shorthand, ToCss};
+use crate::traits::{FallbackValues, Parse, PropertyHandler, Shorthand, ToCss};
 use crate::values::color::CssColor;
+use crate::values::ident::DashedIdent;
 use crate::values::number::CSSNumber;
 use crate::values::string::CowArcStr;
 use crate::values::url::Url;
 #[cfg(feature = "visitor")]
 use crate::visitor::Visit;
+use bitflags::bitflags;
 use cssparser::*;
 use smallvec::SmallVec;
 
@@ -415,3 +422,175 @@ impl<'a> schemars::JsonSchema for Appearance<'a> {
     "Appearance".into()
   }
 }
+
+bitflags! {
+  /// A value for the [color-scheme](https://drafts.csswg.org/css-color-adjust/#color-scheme-prop) property.
+  ///
+  /// An empty value represents the `normal` keyword.
+  #[cfg_attr(feature = "visitor", derive(Visit))]
+  #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(from = "SerializedColorScheme", into = "SerializedColorScheme"))]
+  pub struct ColorScheme: u8 {
+    /// The element supports a light color scheme.
+    const Light = 0b01;
+    /// The element supports a dark color scheme.
+    const Dark  = 0b10;
+    /// Forbids the user agent from overriding the color scheme for the element.
+    const Only  = 0b100;
+  }
+}
+
+impl Default for ColorScheme {
+  fn default() -> ColorScheme {
+    ColorScheme::empty()
+  }
+}
+
+impl<'i> Parse<'i> for ColorScheme {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(ColorScheme::empty());
+    }
+
+    let mut res = ColorScheme::empty();
+    let mut has_only = false;
+    while let Ok(ident) = input.try_parse(|input| input.expect_ident_cloned()) {
+      match_ignore_ascii_case! { &ident,
+        // `only` may only appear at the start or end of the list.
+        "only" if !has_only && (res.is_empty() || input.is_exhausted()) => has_only = true,
+        "light" => res |= ColorScheme::Light,
+        "dark" => res |= ColorScheme::Dark,
+        // Custom color schemes are not supported. Fail so the value is preserved as-is.
+        _ => return Err(input.new_custom_error(ParserError::InvalidValue)),
+      }
+    }
+
+    if res.is_empty() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    if has_only {
+      res |= ColorScheme::Only;
+    }
+
+    Ok(res)
+  }
+}
+
+impl ToCss for ColorScheme {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if self.is_empty() {
+      return dest.write_str("normal");
+    }
+
+    if self.contains(ColorScheme::Light) {
+      dest.write_str("light")?;
+      if self.contains(ColorScheme::Dark) {
+        dest.write_char(' ')?;
+      }
+    }
+
+    if self.contains(ColorScheme::Dark) {
+      dest.write_str("dark")?;
+    }
+
+    if self.contains(ColorScheme::Only) {
+      dest.write_str(" only")?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+struct SerializedColorScheme {
+  light: bool,
+  dark: bool,
+  only: bool,
+}
+
+impl From<ColorScheme> for SerializedColorScheme {
+  fn from(color_scheme: ColorScheme) -> Self {
+    Self {
+      light: color_scheme.contains(ColorScheme::Light),
+      dark: color_scheme.contains(ColorScheme::Dark),
+      only: color_scheme.contains(ColorScheme::Only),
+    }
+  }
+}
+
+impl From<SerializedColorScheme> for ColorScheme {
+  fn from(s: SerializedColorScheme) -> ColorScheme {
+    let mut color_scheme = ColorScheme::empty();
+    color_scheme.set(ColorScheme::Light, s.light);
+    color_scheme.set(ColorScheme::Dark, s.dark);
+    color_scheme.set(ColorScheme::Only, s.only);
+    color_scheme
+  }
+}
+
+#[cfg(feature = "jsonschema")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonschema")))]
+impl<'a> schemars::JsonSchema for ColorScheme {
+  fn is_referenceable() -> bool {
+    true
+  }
+
+  fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    SerializedColorScheme::json_schema(gen)
+  }
+
+  fn schema_name() -> String {
+    "ColorScheme".into()
+  }
+}
+
+#[derive(Default)]
+pub(crate) struct ColorSchemeHandler;
+
+impl<'i> PropertyHandler<'i> for ColorSchemeHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    context: &mut PropertyHandlerContext<'i, '_>,
+  ) -> bool {
+    match property {
+      Property::ColorScheme(color_scheme) => {
+        // When light-dark() is unsupported, it is lowered to a pair of custom properties.
+        // Define them here so that exactly one of them is active for the element's color scheme.
+        if !context.is_supported(Feature::LightDark) {
+          if color_scheme.contains(ColorScheme::Light) {
+            dest.push(define_var("--lightningcss-light", Token::Ident("initial".into())));
+            dest.push(define_var("--lightningcss-dark", Token::WhiteSpace(" ".into())));
+
+            if color_scheme.contains(ColorScheme::Dark) {
+              context.add_dark_rule(define_var("--lightningcss-light", Token::WhiteSpace(" ".into())));
+              context.add_dark_rule(define_var("--lightningcss-dark", Token::Ident("initial".into())));
+            }
+          } else if color_scheme.contains(ColorScheme::Dark) {
+            dest.push(define_var("--lightningcss-light", Token::WhiteSpace(" ".into())));
+            dest.push(define_var("--lightningcss-dark", Token::Ident("initial".into())));
+          }
+        }
+
+        dest.push(property.clone());
+        true
+      }
+      _ => false,
+    }
+  }
+
+  fn finalize(&mut self, _: &mut DeclarationList<'i>, _: &mut PropertyHandlerContext<'i, '_>) {}
+}
+
+#[inline]
+fn define_var<'i>(name: &'static str, value: Token<'i>) -> Property<'i> {
+  Property::Custom(CustomProperty {
+    name: CustomPropertyName::Custom(DashedIdent(name.into())),
+    value: TokenList(vec![TokenOrValue::Token(value)]),
+  })
+}
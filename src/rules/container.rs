@@ -6,19 +6,22 @@ use super::Location;
 use super::{CssRuleList, MinifyContext};
 use crate::error::{MinifyError, ParserError, PrinterError};
 use crate::media_query::{
-  define_query_features, operation_to_css, parse_query_condition, to_css_with_parens_if_needed, FeatureToCss,
-  MediaFeatureType, Operator, QueryCondition, QueryConditionFlags, QueryFeature, ValueType,
+  define_query_features, evaluate_operation, operation_to_css, parse_query_condition,
+  to_css_with_parens_if_needed, FeatureRange, FeatureToCss, MediaFeatureName, MediaFeatureType, MediaFeatureValue,
+  Operator, QueryCondition, QueryConditionFlags, QueryFeature, ValueType,
 };
 use crate::parser::DefaultAtRule;
 use crate::printer::Printer;
 use crate::properties::{Property, PropertyId};
 #[cfg(feature = "serde")]
 use crate::serialization::ValueWrapper;
+use crate::stylesheet::{ParserOptions, PrinterOptions};
 use crate::targets::Browsers;
 use crate::traits::{Parse, ToCss};
 use crate::values::ident::CustomIdent;
 #[cfg(feature = "visitor")]
 use crate::visitor::Visit;
+use std::collections::HashMap;
 
 /// A [@container](https://drafts.csswg.org/css-contain-3/#container-rule) rule.
 #[derive(Debug, PartialEq, Clone)]
@@ -244,6 +247,139 @@ impl<'i> ToCss for StyleQuery<'i> {
   }
 }
 
+/// Information about a query container, used to evaluate a [ContainerCondition](ContainerCondition).
+///
+/// Size features are evaluated in pixels. Unknown sizes are assumed to be any non-negative value,
+/// so conditions that cannot match any size (e.g. `(width < 0px)`) still evaluate to false.
+#[derive(Debug, Clone, Default)]
+pub struct ContainerInfo {
+  /// The width of the container, in pixels.
+  pub width: Option<f32>,
+  /// The height of the container, in pixels.
+  pub height: Option<f32>,
+  /// Whether the container has a vertical writing mode, in which `inline-size` refers to the height.
+  pub vertical_writing_mode: bool,
+  /// The computed values of properties on the container, used to evaluate style queries.
+  /// Keys are property names, e.g. `--theme`. Queries for properties not in the map are unknown.
+  pub properties: HashMap<String, String>,
+}
+
+impl ContainerInfo {
+  fn range(&self, size: Option<f32>) -> FeatureRange {
+    match size {
+      Some(size) => FeatureRange::exact(size),
+      None => FeatureRange::at_least(0.0),
+    }
+  }
+
+  fn inline_size(&self) -> Option<f32> {
+    if self.vertical_writing_mode {
+      self.height
+    } else {
+      self.width
+    }
+  }
+
+  fn block_size(&self) -> Option<f32> {
+    if self.vertical_writing_mode {
+      self.width
+    } else {
+      self.height
+    }
+  }
+}
+
+impl<'i> ContainerCondition<'i> {
+  /// Evaluates the condition against the given container. Returns `None` if the result
+  /// depends on information that is not known, e.g. a relative length or a missing property value.
+  pub fn evaluate(&self, container: &ContainerInfo) -> Option<bool> {
+    match self {
+      ContainerCondition::Feature(feature) => feature.evaluate(container),
+      ContainerCondition::Not(condition) => condition.evaluate(container).map(|result| !result),
+      ContainerCondition::Operation { operator, conditions } => evaluate_operation(
+        *operator,
+        conditions.iter().map(|condition| condition.evaluate(container)),
+      ),
+      ContainerCondition::Style(query) => query.evaluate(container),
+    }
+  }
+}
+
+impl<'i> ContainerSizeFeature<'i> {
+  fn evaluate(&self, container: &ContainerInfo) -> Option<bool> {
+    let name = match self {
+      QueryFeature::Plain { name, .. }
+      | QueryFeature::Boolean { name }
+      | QueryFeature::Range { name, .. }
+      | QueryFeature::Interval { name, .. } => match name {
+        MediaFeatureName::Standard(name) => name,
+        _ => return None,
+      },
+    };
+
+    let to_px = |value: &MediaFeatureValue| match value {
+      MediaFeatureValue::Length(length) => length.to_px(),
+      _ => None,
+    };
+
+    match name {
+      ContainerSizeFeatureId::Width => self.evaluate_range(container.range(container.width), to_px),
+      ContainerSizeFeatureId::Height => self.evaluate_range(container.range(container.height), to_px),
+      ContainerSizeFeatureId::InlineSize => self.evaluate_range(container.range(container.inline_size()), to_px),
+      ContainerSizeFeatureId::BlockSize => self.evaluate_range(container.range(container.block_size()), to_px),
+      ContainerSizeFeatureId::AspectRatio => {
+        let ratio = match (container.width, container.height) {
+          (Some(width), Some(height)) => Some(width / height),
+          _ => None,
+        };
+        self.evaluate_range(container.range(ratio), |value| match value {
+          MediaFeatureValue::Ratio(ratio) => Some(ratio.0 / ratio.1),
+          _ => None,
+        })
+      }
+      ContainerSizeFeatureId::Orientation => match self {
+        QueryFeature::Boolean { .. } => Some(true),
+        QueryFeature::Plain {
+          value: MediaFeatureValue::Ident(ident),
+          ..
+        } => {
+          let is_portrait = container.height? >= container.width?;
+          match_ignore_ascii_case! { &ident.0,
+            "portrait" => Some(is_portrait),
+            "landscape" => Some(!is_portrait),
+            _ => Some(false)
+          }
+        }
+        _ => None,
+      },
+    }
+  }
+}
+
+impl<'i> StyleQuery<'i> {
+  fn evaluate(&self, container: &ContainerInfo) -> Option<bool> {
+    match self {
+      StyleQuery::Feature(property) => {
+        let property_id = property.property_id();
+        let value = container.properties.get(property_id.name())?;
+        // Compare the serialized values, so that equivalent values written differently match.
+        let to_css = |property: &Property| {
+          property.value_to_css_string(PrinterOptions {
+            minify: true,
+            ..PrinterOptions::default()
+          })
+        };
+        let computed = Property::parse_string(property_id, value, ParserOptions::default()).ok()?;
+        Some(to_css(property).ok()? == to_css(&computed).ok()?)
+      }
+      StyleQuery::Not(query) => query.evaluate(container).map(|result| !result),
+      StyleQuery::Operation { operator, conditions } => {
+        evaluate_operation(*operator, conditions.iter().map(|query| query.evaluate(container)))
+      }
+    }
+  }
+}
+
 /// A [`<container-name>`](https://drafts.csswg.org/css-contain-3/#typedef-container-name) in a `@container` rule.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "visitor", derive(Visit))]
@@ -277,6 +413,13 @@ impl<'i, T> ContainerRule<'i, T> {
     context: &mut MinifyContext<'_, 'i>,
     parent_is_unused: bool,
   ) -> Result<bool, MinifyError> {
+    // Remove rules with conditions that cannot match any container, e.g. `(width < 0px)`.
+    if context.remove_unmatched_container_queries
+      && self.condition.evaluate(&ContainerInfo::default()) == Some(false)
+    {
+      return Ok(true);
+    }
+
     self.rules.minify(context, parent_is_unused)?;
     Ok(self.rules.0.is_empty())
   }
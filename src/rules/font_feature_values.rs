@@ -0,0 +1,339 @@
+//! The `@font-feature-values` rule.
+
+use super::Location;
+use crate::error::{ParserError, PrinterError};
+use crate::macros::enum_property;
+use crate::printer::Printer;
+use crate::properties::font::FontFamily;
+use crate::traits::{Parse, ToCss};
+use crate::values::ident::Ident;
+use crate::values::number::CSSInteger;
+#[cfg(feature = "visitor")]
+use crate::visitor::Visit;
+use cssparser::*;
+use smallvec::SmallVec;
+
+/// A [@font-feature-values](https://drafts.csswg.org/css-fonts/#font-feature-values) rule.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct FontFeatureValuesRule<'i> {
+  /// The family names that the feature values apply to.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  pub name: Vec<FontFamily<'i>>,
+  /// The feature value blocks within the rule.
+  pub rules: Vec<FontFeatureSubrule<'i>>,
+  /// The location of the rule in the source file.
+  #[cfg_attr(feature = "visitor", skip_visit)]
+  pub loc: Location,
+}
+
+/// A sub-rule of `@font-feature-values`, e.g. `@styleset`.
+///
+/// See [FontFeatureValuesRule](FontFeatureValuesRule).
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct FontFeatureSubrule<'i> {
+  /// The type of the sub-rule.
+  pub name: FontFeatureSubruleType,
+  /// The named feature values declared within the sub-rule.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  pub declarations: Vec<FontFeatureValue<'i>>,
+  /// The location of the rule in the source file.
+  #[cfg_attr(feature = "visitor", skip_visit)]
+  pub loc: Location,
+}
+
+enum_property! {
+  /// The type of a `@font-feature-values` sub-rule.
+  pub enum FontFeatureSubruleType {
+    /// A `@swash` sub-rule.
+    "swash": Swash,
+    /// A `@stylistic` sub-rule.
+    "stylistic": Stylistic,
+    /// An `@ornaments` sub-rule.
+    "ornaments": Ornaments,
+    /// An `@annotation` sub-rule.
+    "annotation": Annotation,
+    /// A `@styleset` sub-rule.
+    "styleset": Styleset,
+    /// A `@character-variant` sub-rule.
+    "character-variant": CharacterVariant,
+  }
+}
+
+impl FontFeatureSubruleType {
+  /// Returns whether the given number of feature indices is valid for this sub-rule type.
+  fn is_valid_len(&self, len: usize) -> bool {
+    match self {
+      FontFeatureSubruleType::Swash
+      | FontFeatureSubruleType::Stylistic
+      | FontFeatureSubruleType::Ornaments
+      | FontFeatureSubruleType::Annotation => len == 1,
+      FontFeatureSubruleType::CharacterVariant => len == 1 || len == 2,
+      FontFeatureSubruleType::Styleset => len > 0,
+    }
+  }
+}
+
+/// A named feature value declaration within a `@font-feature-values` sub-rule, e.g. `nice-style: 12`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct FontFeatureValue<'i> {
+  /// The name of the feature value, referenced from `font-variant-alternates`.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  pub name: Ident<'i>,
+  /// The feature indices.
+  pub values: SmallVec<[CSSInteger; 1]>,
+}
+
+impl<'i> FontFeatureValuesRule<'i> {
+  pub(crate) fn parse<'t>(
+    name: Vec<FontFamily<'i>>,
+    input: &mut Parser<'i, 't>,
+    loc: Location,
+    source_index: u32,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let iter = RuleListParser::new_for_nested_rule(input, FontFeatureValuesParser { source_index });
+    let rules = iter.filter_map(Result::ok).collect();
+    Ok(FontFeatureValuesRule { name, rules, loc })
+  }
+
+  pub(crate) fn parse_prelude<'t>(
+    input: &mut Parser<'i, 't>,
+  ) -> Result<Vec<FontFamily<'i>>, ParseError<'i, ParserError<'i>>> {
+    input.parse_comma_separated(|input| {
+      let location = input.current_source_location();
+      match FontFamily::parse(input)? {
+        FontFamily::Generic(..) => Err(location.new_custom_error(ParserError::InvalidValue)),
+        family => Ok(family),
+      }
+    })
+  }
+
+  pub(crate) fn minify(&mut self) -> bool {
+    // Remove duplicate family names.
+    let mut name = Vec::with_capacity(self.name.len());
+    for family in self.name.drain(..) {
+      if !name.contains(&family) {
+        name.push(family);
+      }
+    }
+    self.name = name;
+
+    // Merge sub-rules of the same type. Later declarations override earlier ones with the same name.
+    let mut rules: Vec<FontFeatureSubrule<'i>> = Vec::with_capacity(self.rules.len());
+    for rule in self.rules.drain(..) {
+      if let Some(existing) = rules.iter_mut().find(|r| r.name == rule.name) {
+        existing.declarations.extend(rule.declarations);
+      } else {
+        rules.push(rule);
+      }
+    }
+
+    for rule in &mut rules {
+      let mut declarations: Vec<FontFeatureValue<'i>> = Vec::with_capacity(rule.declarations.len());
+      for decl in rule.declarations.drain(..) {
+        if let Some(existing) = declarations.iter_mut().find(|d| d.name == decl.name) {
+          existing.values = decl.values;
+        } else {
+          declarations.push(decl);
+        }
+      }
+      rule.declarations = declarations;
+    }
+
+    rules.retain(|rule| !rule.declarations.is_empty());
+    self.rules = rules;
+    self.rules.is_empty()
+  }
+
+  /// Merges the sub-rules of another `@font-feature-values` rule for the same families into this one.
+  pub(crate) fn merge(&mut self, other: &mut FontFeatureValuesRule<'i>) {
+    self.rules.extend(other.rules.drain(..));
+    self.minify();
+  }
+}
+
+struct FontFeatureValuesParser {
+  source_index: u32,
+}
+
+impl<'i> AtRuleParser<'i> for FontFeatureValuesParser {
+  type Prelude = FontFeatureSubruleType;
+  type AtRule = FontFeatureSubrule<'i>;
+  type Error = ParserError<'i>;
+
+  fn parse_prelude<'t>(
+    &mut self,
+    name: CowRcStr<'i>,
+    input: &mut Parser<'i, 't>,
+  ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
+    let location = input.current_source_location();
+    FontFeatureSubruleType::parse_string(&name)
+      .map_err(|_| location.new_custom_error(ParserError::AtRuleInvalid(name.clone().into())))
+  }
+
+  fn parse_block<'t>(
+    &mut self,
+    prelude: Self::Prelude,
+    start: &ParserState,
+    input: &mut Parser<'i, 't>,
+  ) -> Result<Self::AtRule, ParseError<'i, Self::Error>> {
+    let loc = start.source_location();
+    let mut parser = DeclarationListParser::new(input, FontFeatureValueParser { rule_type: prelude });
+    let mut declarations = vec![];
+    while let Some(decl) = parser.next() {
+      if let Ok(decl) = decl {
+        declarations.push(decl);
+      }
+    }
+
+    Ok(FontFeatureSubrule {
+      name: prelude,
+      declarations,
+      loc: Location {
+        source_index: self.source_index,
+        line: loc.line,
+        column: loc.column,
+      },
+    })
+  }
+}
+
+/// Default methods reject all qualified rules.
+impl<'i> QualifiedRuleParser<'i> for FontFeatureValuesParser {
+  type Prelude = ();
+  type QualifiedRule = FontFeatureSubrule<'i>;
+  type Error = ParserError<'i>;
+}
+
+struct FontFeatureValueParser {
+  rule_type: FontFeatureSubruleType,
+}
+
+impl<'i> cssparser::DeclarationParser<'i> for FontFeatureValueParser {
+  type Declaration = FontFeatureValue<'i>;
+  type Error = ParserError<'i>;
+
+  fn parse_value<'t>(
+    &mut self,
+    name: CowRcStr<'i>,
+    input: &mut cssparser::Parser<'i, 't>,
+  ) -> Result<Self::Declaration, cssparser::ParseError<'i, Self::Error>> {
+    let mut values = SmallVec::new();
+    while let Ok(value) = input.try_parse(CSSInteger::parse) {
+      if value.is_negative() {
+        return Err(input.new_custom_error(ParserError::InvalidValue));
+      }
+      values.push(value);
+    }
+
+    input.expect_exhausted()?;
+    if !self.rule_type.is_valid_len(values.len()) {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(FontFeatureValue {
+      name: Ident(name.into()),
+      values,
+    })
+  }
+}
+
+/// Default methods reject all at rules.
+impl<'i> AtRuleParser<'i> for FontFeatureValueParser {
+  type Prelude = ();
+  type AtRule = FontFeatureValue<'i>;
+  type Error = ParserError<'i>;
+}
+
+impl<'i> ToCss for FontFeatureValuesRule<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    #[cfg(feature = "sourcemap")]
+    dest.add_mapping(self.loc);
+    dest.write_str("@font-feature-values ")?;
+    let mut first = true;
+    for family in &self.name {
+      if first {
+        first = false;
+      } else {
+        dest.delim(',', false)?;
+      }
+      family.to_css(dest)?;
+    }
+    dest.whitespace()?;
+    dest.write_char('{')?;
+    dest.indent();
+    let mut first = true;
+    for rule in &self.rules {
+      if first {
+        first = false;
+      } else if !dest.minify {
+        dest.write_char('\n')?;
+      }
+      dest.newline()?;
+      rule.to_css(dest)?;
+    }
+    dest.dedent();
+    dest.newline()?;
+    dest.write_char('}')
+  }
+}
+
+impl<'i> ToCss for FontFeatureSubrule<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    #[cfg(feature = "sourcemap")]
+    dest.add_mapping(self.loc);
+    dest.write_char('@')?;
+    self.name.to_css(dest)?;
+    dest.whitespace()?;
+    dest.write_char('{')?;
+    dest.indent();
+    let len = self.declarations.len();
+    for (i, decl) in self.declarations.iter().enumerate() {
+      dest.newline()?;
+      decl.to_css(dest)?;
+      if i != len - 1 || !dest.minify {
+        dest.write_char(';')?;
+      }
+    }
+    dest.dedent();
+    dest.newline()?;
+    dest.write_char('}')
+  }
+}
+
+impl<'i> ToCss for FontFeatureValue<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    self.name.to_css(dest)?;
+    dest.delim(':', false)?;
+    let mut first = true;
+    for value in &self.values {
+      if first {
+        first = false;
+      } else {
+        dest.write_char(' ')?;
+      }
+      value.to_css(dest)?;
+    }
+    Ok(())
+  }
+}
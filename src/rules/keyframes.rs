@@ -8,6 +8,7 @@ use crate::declaration::DeclarationBlock;
 use crate::error::{ParserError, PrinterError};
 use crate::parser::ParserOptions;
 use crate::printer::Printer;
+use crate::properties::animation::TimelineRangeName;
 use crate::properties::custom::{CustomProperty, UnparsedProperty};
 use crate::properties::Property;
 use crate::targets::Browsers;
@@ -279,6 +280,20 @@ pub enum KeyframeSelector {
   From,
   /// The `to` keyword. Equivalent to 100%.
   To,
+  /// A [named timeline range selector](https://drafts.csswg.org/scroll-animations-1/#named-range-keyframes).
+  TimelineRangePercentage(TimelineRangePercentage),
+}
+
+/// A percentage of a given timeline range, e.g. `entry 20%`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct TimelineRangePercentage {
+  /// The name of the timeline range.
+  pub name: TimelineRangeName,
+  /// The percentage progress between the start and end of the range.
+  pub percentage: Percentage,
 }
 
 impl<'i> Parse<'i> for KeyframeSelector {
@@ -287,6 +302,14 @@ impl<'i> Parse<'i> for KeyframeSelector {
       return Ok(KeyframeSelector::Percentage(val));
     }
 
+    if let Ok(name) = input.try_parse(TimelineRangeName::parse) {
+      let percentage = Percentage::parse(input)?;
+      return Ok(KeyframeSelector::TimelineRangePercentage(TimelineRangePercentage {
+        name,
+        percentage,
+      }));
+    }
+
     let location = input.current_source_location();
     let ident = input.expect_ident()?;
     match_ignore_ascii_case! { &*ident,
@@ -320,6 +343,11 @@ impl ToCss for KeyframeSelector {
         }
       }
       KeyframeSelector::To => dest.write_str("to"),
+      KeyframeSelector::TimelineRangePercentage(TimelineRangePercentage { name, percentage }) => {
+        name.to_css(dest)?;
+        dest.write_char(' ')?;
+        percentage.to_css(dest)
+      }
     }
   }
 }
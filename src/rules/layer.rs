@@ -1,15 +1,26 @@
 //! The `@layer` rule.
 
-use super::{CssRuleList, Location, MinifyContext};
-use crate::error::{MinifyError, ParserError, PrinterError};
+use super::container::ContainerRule;
+use super::import::ImportRule;
+use super::media::MediaRule;
+use super::nesting::NestingRule;
+use super::scope::ScopeRule;
+use super::starting_style::StartingStyleRule;
+use super::style::StyleRule;
+use super::supports::SupportsRule;
+use super::{CssRule, CssRuleList, Location, MinifyContext};
+use crate::declaration::DeclarationBlock;
+use crate::error::{MinifyError, MinifyErrorKind, ParserError, PrinterError};
 use crate::parser::DefaultAtRule;
 use crate::printer::Printer;
+use crate::selector::{increase_specificity, SelectorList};
 use crate::traits::{Parse, ToCss};
 use crate::values::string::CowArcStr;
 #[cfg(feature = "visitor")]
 use crate::visitor::Visit;
 use cssparser::*;
 use smallvec::SmallVec;
+use std::collections::{HashMap, HashSet};
 
 /// A [`<layer-name>`](https://drafts.csswg.org/css-cascade-5/#typedef-layer-name) within
 /// a `@layer` or `@import` rule.
@@ -129,6 +140,19 @@ pub struct LayerBlockRule<'i, R = DefaultAtRule> {
 }
 
 impl<'i, T> LayerBlockRule<'i, T> {
+  /// Collapses layer blocks that only contain a single named layer block into one block with
+  /// a dotted name, e.g. `@layer a { @layer b { ... } }` becomes `@layer a.b { ... }`.
+  pub(crate) fn collapse_nested_names(&mut self) {
+    if let Some(name) = &mut self.name {
+      while let [CssRule::LayerBlock(LayerBlockRule { name: Some(_), .. })] = &self.rules.0[..] {
+        if let Some(CssRule::LayerBlock(nested)) = self.rules.0.pop() {
+          name.0.extend(nested.name.unwrap().0);
+          self.rules = nested.rules;
+        }
+      }
+    }
+  }
+
   pub(crate) fn minify(
     &mut self,
     context: &mut MinifyContext<'_, 'i>,
@@ -163,3 +187,510 @@ impl<'a, 'i, T: ToCss> ToCss for LayerBlockRule<'i, T> {
     dest.write_char('}')
   }
 }
+
+type LayerPath<'i> = SmallVec<[CowArcStr<'i>; 1]>;
+
+/// Tracks the layers declared within a rule list during minification, in order to determine
+/// which `@layer` blocks with the same name can be merged.
+///
+/// Merging a block into an earlier one moves the layers declared within it earlier, so this is only
+/// safe when no other sub-layers of the same layer were first declared in between.
+#[derive(Default)]
+pub(crate) struct LayerMerger<'i> {
+  declared: HashSet<LayerPath<'i>>,
+  blocks: HashMap<LayerPath<'i>, usize>,
+}
+
+impl<'i> LayerMerger<'i> {
+  /// Returns the index of the closest earlier block that a block with the given name can be merged
+  /// into, along with the number of name segments that the earlier block covers.
+  pub fn find(&self, name: &LayerName<'i>) -> Option<(usize, usize)> {
+    (1..=name.0.len())
+      .rev()
+      .find_map(|len| self.blocks.get(&name.0[..len]).map(|index| (*index, len)))
+  }
+
+  /// Records the index of a block that later blocks with the same name may be merged into.
+  pub fn insert(&mut self, name: &LayerName<'i>, index: usize) {
+    self.blocks.insert(name.0.clone(), index);
+  }
+
+  /// Returns whether a layer with the given name was already declared.
+  pub fn is_declared(&self, name: &LayerName<'i>) -> bool {
+    self.declared.contains(&name.0)
+  }
+
+  /// Records the layers declared by a rule. If the rule is being merged into an earlier block
+  /// with the given name, the layers nested within it do not prevent further merges into that block.
+  pub fn declare<T>(&mut self, rule: &CssRule<'i, T>, merged_into: Option<&LayerName<'i>>) {
+    let mut paths = Vec::new();
+    collect_declared_layers(rule, &[], &mut paths);
+    for path in paths {
+      for len in 1..=path.len() {
+        if self.declared.insert(path[..len].into()) {
+          // Later blocks for any of the ancestors of a newly declared layer can no longer be merged.
+          self.blocks.retain(|name, _| {
+            name.len() >= len
+              || merged_into.map(|merged_into| &merged_into.0) == Some(name)
+              || name[..] != path[..name.len()]
+          });
+        }
+      }
+    }
+  }
+}
+
+/// Collects the names of all layers declared by a rule, including within nested rules.
+fn collect_declared_layers<'i, T>(
+  rule: &CssRule<'i, T>,
+  prefix: &[CowArcStr<'i>],
+  paths: &mut Vec<LayerPath<'i>>,
+) {
+  match rule {
+    CssRule::LayerStatement(statement) => {
+      for name in &statement.names {
+        paths.push(prefix.iter().chain(name.0.iter()).cloned().collect());
+      }
+    }
+    CssRule::LayerBlock(LayerBlockRule {
+      name: Some(name),
+      rules,
+      ..
+    }) => {
+      let path: LayerPath = prefix.iter().chain(name.0.iter()).cloned().collect();
+      paths.push(path.clone());
+      collect_declared_layers_in(rules, &path, paths);
+    }
+    CssRule::Import(ImportRule {
+      layer: Some(Some(name)),
+      ..
+    }) => paths.push(prefix.iter().chain(name.0.iter()).cloned().collect()),
+    CssRule::Style(style) => collect_declared_layers_in(&style.rules, prefix, paths),
+    CssRule::Media(media) => collect_declared_layers_in(&media.rules, prefix, paths),
+    CssRule::Supports(supports) => collect_declared_layers_in(&supports.rules, prefix, paths),
+    CssRule::Container(container) => collect_declared_layers_in(&container.rules, prefix, paths),
+    CssRule::Scope(scope) => collect_declared_layers_in(&scope.rules, prefix, paths),
+    CssRule::StartingStyle(starting_style) => collect_declared_layers_in(&starting_style.rules, prefix, paths),
+    CssRule::MozDocument(document) => collect_declared_layers_in(&document.rules, prefix, paths),
+    _ => {}
+  }
+}
+
+fn collect_declared_layers_in<'i, T>(
+  rules: &CssRuleList<'i, T>,
+  prefix: &[CowArcStr<'i>],
+  paths: &mut Vec<LayerPath<'i>>,
+) {
+  for rule in &rules.0 {
+    collect_declared_layers(rule, prefix, paths);
+  }
+}
+
+/// Replaces the `@layer` statements within a rule list with a single statement declaring the
+/// order of all layers in the list, placed before the first layer is declared. This ensures that
+/// the order of the layers no longer depends on the position of the `@layer` blocks, so they can
+/// be merged.
+///
+/// This is skipped if the list contains anonymous layers or layers declared within other rules
+/// (e.g. `@media`), since their position in the order cannot be expressed in a statement.
+pub(crate) fn hoist_layer_statements<'i, T>(rules: &mut Vec<CssRule<'i, T>>) {
+  let loc = match rules.iter().find_map(|rule| match rule {
+    CssRule::LayerStatement(statement) => Some(statement.loc),
+    _ => None,
+  }) {
+    Some(loc) => loc,
+    None => return,
+  };
+
+  let mut names: Vec<LayerPath> = Vec::new();
+  let mut declared = HashSet::new();
+  if !rules
+    .iter()
+    .all(|rule| collect_layer_order(rule, &[], &mut names, &mut declared))
+  {
+    return;
+  }
+
+  let index = rules
+    .iter()
+    .position(|rule| {
+      matches!(
+        rule,
+        CssRule::LayerStatement(..) | CssRule::LayerBlock(..) | CssRule::Import(ImportRule { layer: Some(_), .. })
+      )
+    })
+    .unwrap();
+  rules.retain(|rule| !matches!(rule, CssRule::LayerStatement(..)));
+  rules.insert(
+    index,
+    CssRule::LayerStatement(LayerStatementRule {
+      names: names.into_iter().map(LayerName).collect(),
+      loc,
+    }),
+  );
+}
+
+/// Appends the layers declared by a rule to the list in order. Returns false if the rule contains
+/// layers that cannot be declared by a statement.
+fn collect_layer_order<'i, T>(
+  rule: &CssRule<'i, T>,
+  prefix: &[CowArcStr<'i>],
+  names: &mut Vec<LayerPath<'i>>,
+  declared: &mut HashSet<LayerPath<'i>>,
+) -> bool {
+  match rule {
+    CssRule::LayerStatement(statement) => {
+      for name in &statement.names {
+        declare_layer(prefix.iter().chain(name.0.iter()).cloned().collect(), names, declared);
+      }
+      true
+    }
+    CssRule::LayerBlock(LayerBlockRule {
+      name: Some(name),
+      rules,
+      ..
+    }) => {
+      let path: LayerPath = prefix.iter().chain(name.0.iter()).cloned().collect();
+      declare_layer(path.clone(), names, declared);
+      rules.0.iter().all(|rule| collect_layer_order(rule, &path, names, declared))
+    }
+    CssRule::Import(ImportRule {
+      layer: Some(Some(name)),
+      ..
+    }) => {
+      declare_layer(prefix.iter().chain(name.0.iter()).cloned().collect(), names, declared);
+      true
+    }
+    CssRule::LayerBlock(..) | CssRule::Import(ImportRule { layer: Some(None), .. }) => false,
+    rule => {
+      let mut paths = Vec::new();
+      collect_declared_layers(rule, prefix, &mut paths);
+      paths.is_empty()
+    }
+  }
+}
+
+fn declare_layer<'i>(path: LayerPath<'i>, names: &mut Vec<LayerPath<'i>>, declared: &mut HashSet<LayerPath<'i>>) {
+  if declared.contains(&path) {
+    return;
+  }
+
+  for len in 1..=path.len() {
+    declared.insert(path[..len].into());
+  }
+
+  // Declaring a nested layer also declares its ancestors, so the previous name can be omitted.
+  if matches!(names.last(), Some(last) if path.starts_with(last)) {
+    names.pop();
+  }
+  names.push(path);
+}
+
+/// Flattens the `@layer` rules within a style sheet into unlayered rules, for browsers without
+/// cascade layer support.
+///
+/// Style rules are kept in place, and the precedence of each layer is preserved by increasing the
+/// specificity of the style rules within it using `:not(#\#)` pseudo classes. Unlayered style rules
+/// have the highest precedence for normal declarations, and the lowest for `!important` declarations,
+/// so `!important` declarations are split into a separate rule. Returns a list of warnings for rules
+/// that could not be flattened. If layers are nested within style rules, nothing is flattened.
+pub(crate) fn flatten_layers<'i, T>(rules: &mut CssRuleList<'i, T>) -> Vec<MinifyError> {
+  let mut tree = LayerTree {
+    nodes: vec![Vec::new()],
+    blocks: Vec::new(),
+    max_ids: 0,
+    has_nested_layers: false,
+    warnings: Vec::new(),
+  };
+  tree.visit_rules(rules, 0, None);
+
+  // The order of layers nested within style rules depends on where the nested rules end up once
+  // nesting is lowered, so the style sheet is left as is.
+  if tree.has_nested_layers {
+    return tree.warnings;
+  }
+
+  let mut ranks = vec![0; tree.nodes.len()];
+  tree.assign_ranks(0, &mut ranks, &mut 0);
+
+  let mut flattener = Flattener {
+    ranks,
+    blocks: tree.blocks.iter(),
+    // Each layer must override any difference in specificity between the rules in lower layers.
+    step: if tree.blocks.is_empty() { 0 } else { tree.max_ids + 1 },
+    layer_count: tree.nodes.len() - 1,
+  };
+  flattener.flatten_rules(rules, flattener.layer_count);
+  tree.warnings
+}
+
+/// The tree of declared layers. The root (index 0) represents unlayered rules.
+struct LayerTree<'i> {
+  /// The child layers of each layer, in the order they were declared. Anonymous layers have no name.
+  nodes: Vec<Vec<(Option<CowArcStr<'i>>, usize)>>,
+  /// The layer for each `@layer` block, in source order.
+  blocks: Vec<usize>,
+  /// The maximum number of ids in the specificity of a style rule, including its parents.
+  max_ids: usize,
+  has_nested_layers: bool,
+  warnings: Vec<MinifyError>,
+}
+
+impl<'i> LayerTree<'i> {
+  fn child(&mut self, parent: usize, name: Option<&CowArcStr<'i>>) -> usize {
+    if let Some(name) = name {
+      if let Some((_, index)) = self.nodes[parent].iter().find(|(n, _)| n.as_ref() == Some(name)) {
+        return *index;
+      }
+    }
+
+    let index = self.nodes.len();
+    self.nodes.push(Vec::new());
+    self.nodes[parent].push((name.cloned(), index));
+    index
+  }
+
+  fn declare(&mut self, parent: usize, name: &LayerName<'i>) -> usize {
+    name.0.iter().fold(parent, |parent, name| self.child(parent, Some(name)))
+  }
+
+  fn visit_rules<T>(&mut self, rules: &CssRuleList<'i, T>, layer: usize, parent_ids: Option<usize>) {
+    for rule in &rules.0 {
+      match rule {
+        CssRule::LayerStatement(statement) if parent_ids.is_none() => {
+          for name in &statement.names {
+            self.declare(layer, name);
+          }
+        }
+        CssRule::LayerBlock(block) if parent_ids.is_none() => {
+          let layer = match &block.name {
+            Some(name) => self.declare(layer, name),
+            None => self.child(layer, None),
+          };
+          self.blocks.push(layer);
+          self.visit_rules(&block.rules, layer, None);
+        }
+        CssRule::LayerStatement(LayerStatementRule { loc, .. })
+        | CssRule::LayerBlock(LayerBlockRule { loc, .. }) => {
+          self.has_nested_layers = true;
+          self.warnings.push(MinifyError {
+            kind: MinifyErrorKind::UnsupportedNestedLayer,
+            loc: *loc,
+          })
+        }
+        CssRule::Import(import) if import.layer.is_some() => self.warnings.push(MinifyError {
+          kind: MinifyErrorKind::UnsupportedLayerImport,
+          loc: import.loc,
+        }),
+        CssRule::Style(style) => self.visit_style_rule(style, layer, parent_ids),
+        CssRule::Nesting(nesting) => self.visit_style_rule(&nesting.style, layer, parent_ids),
+        CssRule::Media(media) => self.visit_rules(&media.rules, layer, parent_ids),
+        CssRule::Supports(supports) => self.visit_rules(&supports.rules, layer, parent_ids),
+        CssRule::Container(container) => self.visit_rules(&container.rules, layer, parent_ids),
+        CssRule::Scope(scope) => self.visit_rules(&scope.rules, layer, parent_ids),
+        CssRule::StartingStyle(starting_style) => self.visit_rules(&starting_style.rules, layer, parent_ids),
+        CssRule::MozDocument(document) => self.visit_rules(&document.rules, layer, parent_ids),
+        _ => {}
+      }
+    }
+  }
+
+  fn visit_style_rule<T>(&mut self, style: &StyleRule<'i, T>, layer: usize, parent_ids: Option<usize>) {
+    let ids = parent_ids.unwrap_or(0)
+      + style
+        .selectors
+        .0
+        .iter()
+        .map(|selector| (selector.specificity() >> 20) as usize)
+        .max()
+        .unwrap_or(0);
+    self.max_ids = self.max_ids.max(ids);
+    self.visit_rules(&style.rules, layer, Some(ids));
+  }
+
+  /// Assigns the precedence of each layer. Sub-layers have lower precedence than their parent.
+  fn assign_ranks(&self, layer: usize, ranks: &mut Vec<usize>, next: &mut usize) {
+    for (_, child) in &self.nodes[layer] {
+      self.assign_ranks(*child, ranks, next);
+    }
+    ranks[layer] = *next;
+    *next += 1;
+  }
+}
+
+struct Flattener<'a> {
+  ranks: Vec<usize>,
+  blocks: std::slice::Iter<'a, usize>,
+  step: usize,
+  layer_count: usize,
+}
+
+impl<'a> Flattener<'a> {
+  fn flatten_rules<'i, T>(&mut self, rules: &mut CssRuleList<'i, T>, rank: usize) {
+    let mut result = Vec::with_capacity(rules.0.len());
+    for rule in std::mem::take(&mut rules.0) {
+      match rule {
+        CssRule::LayerStatement(_) => {}
+        CssRule::LayerBlock(mut block) => {
+          let rank = self.ranks[*self.blocks.next().unwrap()];
+          self.flatten_rules(&mut block.rules, rank);
+          result.extend(block.rules.0);
+        }
+        CssRule::Style(mut style) => {
+          let important = split_important(&mut style);
+          if important.is_none() || !style.is_empty() {
+            increase_rule_specificity(&mut style, rank * self.step);
+            result.push(CssRule::Style(style));
+          }
+          if let Some(mut important) = important {
+            increase_rule_specificity(&mut important, (self.layer_count - rank) * self.step);
+            result.push(CssRule::Style(important));
+          }
+        }
+        CssRule::Media(mut media) => {
+          self.flatten_rules(&mut media.rules, rank);
+          result.push(CssRule::Media(media));
+        }
+        CssRule::Supports(mut supports) => {
+          self.flatten_rules(&mut supports.rules, rank);
+          result.push(CssRule::Supports(supports));
+        }
+        CssRule::Container(mut container) => {
+          self.flatten_rules(&mut container.rules, rank);
+          result.push(CssRule::Container(container));
+        }
+        CssRule::Scope(mut scope) => {
+          self.flatten_rules(&mut scope.rules, rank);
+          result.push(CssRule::Scope(scope));
+        }
+        CssRule::StartingStyle(mut starting_style) => {
+          self.flatten_rules(&mut starting_style.rules, rank);
+          result.push(CssRule::StartingStyle(starting_style));
+        }
+        CssRule::MozDocument(mut document) => {
+          self.flatten_rules(&mut document.rules, rank);
+          result.push(CssRule::MozDocument(document));
+        }
+        rule => result.push(rule),
+      }
+    }
+    rules.0 = result;
+  }
+}
+
+fn increase_rule_specificity<'i, T>(style: &mut StyleRule<'i, T>, ids: usize) {
+  if ids > 0 {
+    style.selectors = SelectorList::new(
+      style
+        .selectors
+        .0
+        .iter()
+        .map(|selector| increase_specificity(selector, ids))
+        .collect(),
+    );
+  }
+}
+
+/// Moves the `!important` declarations within a style rule and its nested rules into a separate rule.
+fn split_important<'i, T>(style: &mut StyleRule<'i, T>) -> Option<StyleRule<'i, T>> {
+  let important_declarations = std::mem::take(&mut style.declarations.important_declarations);
+  let rules = split_important_rules(&mut style.rules);
+  if important_declarations.is_empty() && rules.0.is_empty() {
+    return None;
+  }
+
+  Some(StyleRule {
+    selectors: style.selectors.clone(),
+    vendor_prefix: style.vendor_prefix,
+    declarations: DeclarationBlock {
+      important_declarations,
+      declarations: Vec::new(),
+    },
+    rules,
+    loc: style.loc,
+  })
+}
+
+fn split_important_rules<'i, T>(rules: &mut CssRuleList<'i, T>) -> CssRuleList<'i, T> {
+  let mut result = Vec::new();
+  for rule in &mut rules.0 {
+    match rule {
+      CssRule::Style(style) => {
+        if let Some(important) = split_important(style) {
+          result.push(CssRule::Style(important));
+        }
+      }
+      CssRule::Nesting(nesting) => {
+        if let Some(style) = split_important(&mut nesting.style) {
+          result.push(CssRule::Nesting(NestingRule {
+            style,
+            loc: nesting.loc,
+          }));
+        }
+      }
+      CssRule::Media(media) => {
+        let rules = split_important_rules(&mut media.rules);
+        if !rules.0.is_empty() {
+          result.push(CssRule::Media(MediaRule {
+            query: media.query.clone(),
+            rules,
+            loc: media.loc,
+          }));
+        }
+      }
+      CssRule::Supports(supports) => {
+        let rules = split_important_rules(&mut supports.rules);
+        if !rules.0.is_empty() {
+          result.push(CssRule::Supports(SupportsRule {
+            condition: supports.condition.clone(),
+            rules,
+            loc: supports.loc,
+          }));
+        }
+      }
+      CssRule::Container(container) => {
+        let rules = split_important_rules(&mut container.rules);
+        if !rules.0.is_empty() {
+          result.push(CssRule::Container(ContainerRule {
+            name: container.name.clone(),
+            condition: container.condition.clone(),
+            rules,
+            loc: container.loc,
+          }));
+        }
+      }
+      CssRule::Scope(scope) => {
+        let rules = split_important_rules(&mut scope.rules);
+        if !rules.0.is_empty() {
+          result.push(CssRule::Scope(ScopeRule {
+            scope_start: scope.scope_start.clone(),
+            scope_end: scope.scope_end.clone(),
+            rules,
+            loc: scope.loc,
+          }));
+        }
+      }
+      CssRule::StartingStyle(starting_style) => {
+        let rules = split_important_rules(&mut starting_style.rules);
+        if !rules.0.is_empty() {
+          result.push(CssRule::StartingStyle(StartingStyleRule {
+            rules,
+            loc: starting_style.loc,
+          }));
+        }
+      }
+      CssRule::LayerBlock(layer) => {
+        let rules = split_important_rules(&mut layer.rules);
+        if !rules.0.is_empty() {
+          result.push(CssRule::LayerBlock(LayerBlockRule {
+            name: layer.name.clone(),
+            rules,
+            loc: layer.loc,
+          }));
+        }
+      }
+      _ => {}
+    }
+  }
+  CssRuleList(result)
+}
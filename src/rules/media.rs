@@ -1,9 +1,9 @@
 //! The `@media` rule.
 
 use super::Location;
-use super::{CssRuleList, MinifyContext};
+use super::{CssRule, CssRuleList, MinifyContext};
 use crate::error::{MinifyError, PrinterError};
-use crate::media_query::MediaList;
+use crate::media_query::{Environment, MediaList};
 use crate::parser::DefaultAtRule;
 use crate::printer::Printer;
 use crate::traits::ToCss;
@@ -38,6 +38,8 @@ impl<'i, T> MediaRule<'i, T> {
       self.query.transform_custom_media(self.loc, custom_media)?;
     }
 
+    self.query.simplify();
+
     if let Some(targets) = context.targets {
       self.query.transform_resolution(*targets);
     }
@@ -46,6 +48,73 @@ impl<'i, T> MediaRule<'i, T> {
   }
 }
 
+/// Removes the `@media` rules that do not match the given environment, and replaces the rules that
+/// always match it with their contents. Rules that depend on unknown information are kept.
+pub(crate) fn prune_media_rules<'i, T>(rules: &mut CssRuleList<'i, T>, environment: &Environment) {
+  let mut result = Vec::with_capacity(rules.0.len());
+  for mut rule in std::mem::take(&mut rules.0) {
+    match &mut rule {
+      CssRule::Media(media) => {
+        let matches = media.query.evaluate(environment);
+        if matches == Some(false) {
+          continue;
+        }
+
+        prune_media_rules(&mut media.rules, environment);
+        if matches == Some(true) {
+          result.append(&mut media.rules.0);
+          continue;
+        }
+      }
+      CssRule::Import(import) => match import.media.evaluate(environment) {
+        Some(false) => continue,
+        Some(true) => import.media = MediaList::new(),
+        None => {}
+      },
+      CssRule::Style(style) => prune_media_rules(&mut style.rules, environment),
+      CssRule::Nesting(nesting) => prune_media_rules(&mut nesting.style.rules, environment),
+      // Layer blocks affect the order of layers even when empty.
+      CssRule::LayerBlock(layer) => prune_media_rules(&mut layer.rules, environment),
+      // Other conditional group rules are removed if all of their rules were removed.
+      CssRule::Supports(supports) => {
+        prune_media_rules(&mut supports.rules, environment);
+        if supports.rules.0.is_empty() {
+          continue;
+        }
+      }
+      CssRule::Container(container) => {
+        prune_media_rules(&mut container.rules, environment);
+        if container.rules.0.is_empty() {
+          continue;
+        }
+      }
+      CssRule::Scope(scope) => {
+        prune_media_rules(&mut scope.rules, environment);
+        if scope.rules.0.is_empty() {
+          continue;
+        }
+      }
+      CssRule::StartingStyle(starting_style) => {
+        prune_media_rules(&mut starting_style.rules, environment);
+        if starting_style.rules.0.is_empty() {
+          continue;
+        }
+      }
+      CssRule::MozDocument(document) => {
+        prune_media_rules(&mut document.rules, environment);
+        if document.rules.0.is_empty() {
+          continue;
+        }
+      }
+      _ => {}
+    }
+
+    result.push(rule);
+  }
+
+  rules.0 = result;
+}
+
 impl<'a, 'i, T: ToCss> ToCss for MediaRule<'i, T> {
   fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
   where
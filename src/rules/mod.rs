@@ -41,6 +41,7 @@ pub mod counter_style;
 pub mod custom_media;
 pub mod document;
 pub mod font_face;
+pub mod font_feature_values;
 pub mod font_palette_values;
 pub mod import;
 pub mod keyframes;
@@ -49,14 +50,18 @@ pub mod media;
 pub mod namespace;
 pub mod nesting;
 pub mod page;
+pub mod position_try;
 pub mod property;
+pub mod scope;
+pub mod starting_style;
 pub mod style;
 pub mod supports;
 pub mod unknown;
 pub mod viewport;
 
+use self::font_feature_values::FontFeatureValuesRule;
 use self::font_palette_values::FontPaletteValuesRule;
-use self::layer::{LayerBlockRule, LayerStatementRule};
+use self::layer::{hoist_layer_statements, LayerBlockRule, LayerMerger, LayerName, LayerStatementRule};
 use self::property::PropertyRule;
 use crate::context::PropertyHandlerContext;
 use crate::declaration::DeclarationHandler;
@@ -69,7 +74,7 @@ use crate::prefixes::Feature;
 use crate::printer::Printer;
 use crate::rules::keyframes::KeyframesName;
 use crate::selector::{downlevel_selectors, get_prefix, is_equivalent, SelectorList};
-use crate::stylesheet::ParserOptions;
+use crate::stylesheet::{ParserOptions, UsedSymbols};
 use crate::targets::Browsers;
 use crate::traits::{AtRuleParser, ToCss};
 use crate::values::string::CowArcStr;
@@ -88,6 +93,9 @@ use media::MediaRule;
 use namespace::NamespaceRule;
 use nesting::NestingRule;
 use page::PageRule;
+use position_try::PositionTryRule;
+use scope::ScopeRule;
+use starting_style::StartingStyleRule;
 use std::collections::{HashMap, HashSet};
 use std::hash::{BuildHasherDefault, Hasher};
 use style::StyleRule;
@@ -140,6 +148,8 @@ pub enum CssRule<'i, R = DefaultAtRule> {
   FontFace(FontFaceRule<'i>),
   /// A `@font-palette-values` rule.
   FontPaletteValues(FontPaletteValuesRule<'i>),
+  /// A `@font-feature-values` rule.
+  FontFeatureValues(FontFeatureValuesRule<'i>),
   /// A `@page` rule.
   Page(PageRule<'i>),
   /// A `@supports` rule.
@@ -164,6 +174,12 @@ pub enum CssRule<'i, R = DefaultAtRule> {
   Property(PropertyRule<'i>),
   /// A `@container` rule.
   Container(ContainerRule<'i, R>),
+  /// A `@scope` rule.
+  Scope(ScopeRule<'i, R>),
+  /// A `@starting-style` rule.
+  StartingStyle(StartingStyleRule<'i, R>),
+  /// A `@position-try` rule.
+  PositionTry(PositionTryRule<'i>),
   /// A placeholder for a rule that was removed.
   Ignored,
   /// An unknown at-rule.
@@ -252,6 +268,10 @@ impl<'i, 'de: 'i, R: serde::Deserialize<'de>> serde::Deserialize<'de> for CssRul
         let rule = FontPaletteValuesRule::deserialize(deserializer)?;
         Ok(CssRule::FontPaletteValues(rule))
       }
+      "font-feature-values" => {
+        let rule = FontFeatureValuesRule::deserialize(deserializer)?;
+        Ok(CssRule::FontFeatureValues(rule))
+      }
       "page" => {
         let rule = PageRule::deserialize(deserializer)?;
         Ok(CssRule::Page(rule))
@@ -300,6 +320,18 @@ impl<'i, 'de: 'i, R: serde::Deserialize<'de>> serde::Deserialize<'de> for CssRul
         let rule = ContainerRule::deserialize(deserializer)?;
         Ok(CssRule::Container(rule))
       }
+      "scope" => {
+        let rule = ScopeRule::deserialize(deserializer)?;
+        Ok(CssRule::Scope(rule))
+      }
+      "starting-style" => {
+        let rule = StartingStyleRule::deserialize(deserializer)?;
+        Ok(CssRule::StartingStyle(rule))
+      }
+      "position-try" => {
+        let rule = PositionTryRule::deserialize(deserializer)?;
+        Ok(CssRule::PositionTry(rule))
+      }
       "ignored" => Ok(CssRule::Ignored),
       "unknown" => {
         let rule = UnknownAtRule::deserialize(deserializer)?;
@@ -326,6 +358,7 @@ impl<'a, 'i, T: ToCss> ToCss for CssRule<'i, T> {
       CssRule::Keyframes(keyframes) => keyframes.to_css(dest),
       CssRule::FontFace(font_face) => font_face.to_css(dest),
       CssRule::FontPaletteValues(f) => f.to_css(dest),
+      CssRule::FontFeatureValues(f) => f.to_css(dest),
       CssRule::Page(font_face) => font_face.to_css(dest),
       CssRule::Supports(supports) => supports.to_css(dest),
       CssRule::CounterStyle(counter_style) => counter_style.to_css(dest),
@@ -338,6 +371,9 @@ impl<'a, 'i, T: ToCss> ToCss for CssRule<'i, T> {
       CssRule::LayerBlock(layer) => layer.to_css(dest),
       CssRule::Property(property) => property.to_css(dest),
       CssRule::Container(container) => container.to_css(dest),
+      CssRule::Scope(scope) => scope.to_css(dest),
+      CssRule::StartingStyle(rule) => rule.to_css(dest),
+      CssRule::PositionTry(rule) => rule.to_css(dest),
       CssRule::Unknown(unknown) => unknown.to_css(dest),
       CssRule::Custom(rule) => rule.to_css(dest).map_err(|_| PrinterError {
         kind: PrinterErrorKind::FmtError,
@@ -466,6 +502,8 @@ pub(crate) struct MinifyContext<'a, 'i> {
   pub important_handler: &'a mut DeclarationHandler<'i>,
   pub handler_context: &'a mut PropertyHandlerContext<'i, 'a>,
   pub unused_symbols: &'a HashSet<String>,
+  pub used_symbols: Option<&'a UsedSymbols>,
+  pub remove_unmatched_container_queries: bool,
   pub custom_media: Option<HashMap<CowArcStr<'i>, CustomMediaRule<'i>>>,
   pub css_modules: bool,
 }
@@ -477,11 +515,17 @@ impl<'i, T> CssRuleList<'i, T> {
     parent_is_unused: bool,
   ) -> Result<(), MinifyError> {
     let mut keyframe_rules = HashMap::new();
-    let mut layer_rules = HashMap::new();
+    let mut layer_merger = LayerMerger::default();
+    let mut font_feature_values_rules = HashMap::new();
     let mut style_rules =
       HashMap::with_capacity_and_hasher(self.0.len(), BuildHasherDefault::<PrecomputedHasher>::default());
     let mut rules = Vec::new();
+    hoist_layer_statements(&mut self.0);
     for mut rule in self.0.drain(..) {
+      if !matches!(rule, CssRule::LayerBlock(..)) {
+        layer_merger.declare(&rule, None);
+      }
+
       match &mut rule {
         CssRule::Keyframes(keyframes) => {
           if context.unused_symbols.contains(match &keyframes.name {
@@ -569,23 +613,81 @@ impl<'i, T> CssRuleList<'i, T> {
             continue;
           }
         }
+        CssRule::StartingStyle(starting_style) => {
+          if let Some(CssRule::StartingStyle(last_rule)) = rules.last_mut() {
+            last_rule.rules.0.extend(starting_style.rules.0.drain(..));
+            last_rule.minify(context, parent_is_unused)?;
+            continue;
+          }
+
+          if starting_style.minify(context, parent_is_unused)? {
+            continue;
+          }
+        }
+        CssRule::PositionTry(rule) => {
+          rule.minify(context);
+        }
+        CssRule::Scope(scope) => {
+          if let Some(targets) = context.targets {
+            if let Some(mut lowered) = scope.lower(*targets) {
+              lowered.minify(context, parent_is_unused)?;
+              rules.extend(lowered.0);
+              continue;
+            }
+          }
+
+          if scope.minify(context, parent_is_unused)? {
+            continue;
+          }
+        }
         CssRule::LayerBlock(layer) => {
-          // Merging non-adjacent layer rules is safe because they are applied
-          // in the order they are first defined.
-          if let Some(name) = &layer.name {
-            if let Some(idx) = layer_rules.get(name) {
-              if let Some(CssRule::LayerBlock(last_rule)) = rules.get_mut(*idx) {
-                last_rule.rules.0.extend(layer.rules.0.drain(..));
-                last_rule.minify(context, parent_is_unused)?;
-                continue;
+          layer.collapse_nested_names();
+
+          // Merge the block into an earlier block for the same layer, or one of its ancestors.
+          // Layers are applied in the order they are first declared, so this is safe as long as
+          // no other sub-layers of the earlier block were declared in between.
+          let merge = layer.name.as_ref().and_then(|name| {
+            let (idx, len) = layer_merger.find(name)?;
+            Some((idx, LayerName(name.0[..len].into())))
+          });
+          if let Some((idx, target)) = merge {
+            layer_merger.declare(&rule, Some(&target));
+            if let (CssRule::LayerBlock(layer), Some(CssRule::LayerBlock(last_rule))) = (rule, rules.get_mut(idx))
+            {
+              let mut name = layer.name.unwrap();
+              if name.0.len() > target.0.len() {
+                name.0.drain(..target.0.len());
+                last_rule.rules.0.push(CssRule::LayerBlock(LayerBlockRule {
+                  name: Some(name),
+                  rules: layer.rules,
+                  loc: layer.loc,
+                }));
+              } else {
+                last_rule.rules.0.extend(layer.rules.0);
               }
+              last_rule.minify(context, parent_is_unused)?;
             }
-
-            layer_rules.insert(name.clone(), rules.len());
+            continue;
           }
-          if layer.minify(context, parent_is_unused)? {
+
+          let is_declared = match &layer.name {
+            Some(name) => layer_merger.is_declared(name),
+            None => true,
+          };
+          layer_merger.declare(&rule, None);
+          let layer = match &mut rule {
+            CssRule::LayerBlock(layer) => layer,
+            _ => unreachable!(),
+          };
+
+          // Empty blocks can only be removed if they do not affect the order of layers.
+          if layer.minify(context, parent_is_unused)? && is_declared {
             continue;
           }
+
+          if let Some(name) = &layer.name {
+            layer_merger.insert(name, rules.len());
+          }
         }
         CssRule::MozDocument(document) => document.minify(context)?,
         CssRule::Style(style) => {
@@ -626,6 +728,7 @@ impl<'i, T> CssRuleList<'i, T> {
 
           let supports = context.handler_context.get_supports_rules(&style);
           let logical = context.handler_context.get_logical_rules(&style);
+          let dark = context.handler_context.get_dark_rules(&style);
           if !merged && !style.is_empty() {
             let source_index = style.loc.source_index;
             let has_no_rules = style.rules.0.is_empty();
@@ -661,6 +764,7 @@ impl<'i, T> CssRuleList<'i, T> {
           }
 
           rules.extend(supports);
+          rules.extend(dark);
           continue;
         }
         CssRule::CounterStyle(counter_style) => {
@@ -687,6 +791,22 @@ impl<'i, T> CssRuleList<'i, T> {
             continue;
           }
         }
+        CssRule::FontFeatureValues(f) => {
+          if f.minify() {
+            continue;
+          }
+
+          // Rules for the same families can be merged, since @font-feature-values
+          // rules only interact with each other.
+          if let Some(idx) = font_feature_values_rules.get(&f.name) {
+            if let Some(CssRule::FontFeatureValues(existing)) = rules.get_mut(*idx) {
+              existing.merge(f);
+              continue;
+            }
+          }
+
+          font_feature_values_rules.insert(f.name.clone(), rules.len());
+        }
         CssRule::Property(property) => {
           if context.unused_symbols.contains(property.name.0.as_ref()) {
             continue;
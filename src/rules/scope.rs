@@ -0,0 +1,179 @@
+//! The `@scope` rule.
+
+use super::Location;
+use super::{CssRule, CssRuleList, MinifyContext};
+use crate::compat::Feature;
+use crate::error::{MinifyError, PrinterError};
+use crate::parser::DefaultAtRule;
+use crate::printer::Printer;
+use crate::selector::{descendants_of, scope_selector, Component, Selector, SelectorList};
+use crate::targets::Browsers;
+use crate::traits::ToCss;
+#[cfg(feature = "visitor")]
+use crate::visitor::Visit;
+
+/// A [@scope](https://drafts.csswg.org/css-cascade-6/#scope-atrule) rule.
+///
+/// @scope (<scope-start>) [to (<scope-end>)]? {
+///  <stylesheet>
+/// }
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct ScopeRule<'i, R = DefaultAtRule> {
+  /// A selector list used to identify the scoping root(s).
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  pub scope_start: Option<SelectorList<'i>>,
+  /// A selector list used to identify any scoping limits.
+  pub scope_end: Option<SelectorList<'i>>,
+  /// Nested rules within the `@scope` rule.
+  pub rules: CssRuleList<'i, R>,
+  /// The location of the rule in the source file.
+  #[cfg_attr(feature = "visitor", skip_visit)]
+  pub loc: Location,
+}
+
+impl<'i, T> ScopeRule<'i, T> {
+  pub(crate) fn minify(
+    &mut self,
+    context: &mut MinifyContext<'_, 'i>,
+    parent_is_unused: bool,
+  ) -> Result<bool, MinifyError> {
+    self.rules.minify(context, parent_is_unused)?;
+    Ok(self.rules.0.is_empty())
+  }
+
+  /// Rewrites the rules within the `@scope` rule so that they apply to the scoped
+  /// elements without the `@scope` rule, for browsers that don't support it.
+  /// Style rules are made relative to `:where(<scope-start>)`, and elements
+  /// within the scoping limit are excluded with `:not()`.
+  ///
+  /// Returns `None` if the targets support `@scope`, if they don't support
+  /// the `:where()` and `:not()` selectors needed to lower it, or if the rule
+  /// has no `<scope-start>`.
+  pub(crate) fn lower(&mut self, targets: Browsers) -> Option<CssRuleList<'i, T>> {
+    if Feature::AtScope.is_compatible(targets)
+      || !Feature::CssMatchesPseudo.is_compatible(targets)
+      || !Feature::CssNotSelList.is_compatible(targets)
+    {
+      return None;
+    }
+
+    // Without a prelude, the scoping root is the parent of the style sheet's owner node,
+    // which can't be expressed as a selector.
+    let root = Component::Where(self.scope_start.as_ref()?.0.to_vec().into_boxed_slice());
+
+    let limit = self.scope_end.as_ref().map(|end| {
+      let mut selectors = Vec::with_capacity(end.0.len() * 2);
+      for selector in end.0.iter() {
+        selectors.push(scope_selector(selector, &root));
+      }
+      for i in 0..selectors.len() {
+        selectors.push(descendants_of(&selectors[i]));
+      }
+      Component::Negation(vec![Selector::from(Component::Where(selectors.into_boxed_slice()))].into_boxed_slice())
+    });
+
+    let mut rules = CssRuleList(std::mem::take(&mut self.rules.0));
+    lower_rules(&mut rules, &root, limit.as_ref(), targets);
+    Some(rules)
+  }
+}
+
+fn lower_rules<'i, T>(
+  rules: &mut CssRuleList<'i, T>,
+  root: &Component<'i>,
+  limit: Option<&Component<'i>>,
+  targets: Browsers,
+) {
+  let mut result = Vec::with_capacity(rules.0.len());
+  for rule in rules.0.drain(..) {
+    match rule {
+      CssRule::Style(mut style) => {
+        let selectors = style
+          .selectors
+          .0
+          .iter()
+          .map(|selector| {
+            let mut selector = scope_selector(selector, root);
+            if let Some(limit) = limit {
+              selector.append(limit.clone());
+            }
+            selector
+          })
+          .collect();
+        style.selectors = SelectorList::new(selectors);
+        result.push(CssRule::Style(style));
+      }
+      CssRule::Media(mut media) => {
+        lower_rules(&mut media.rules, root, limit, targets);
+        result.push(CssRule::Media(media));
+      }
+      CssRule::Supports(mut supports) => {
+        lower_rules(&mut supports.rules, root, limit, targets);
+        result.push(CssRule::Supports(supports));
+      }
+      CssRule::Container(mut container) => {
+        lower_rules(&mut container.rules, root, limit, targets);
+        result.push(CssRule::Container(container));
+      }
+      CssRule::LayerBlock(mut layer) => {
+        lower_rules(&mut layer.rules, root, limit, targets);
+        result.push(CssRule::LayerBlock(layer));
+      }
+      CssRule::MozDocument(mut document) => {
+        lower_rules(&mut document.rules, root, limit, targets);
+        result.push(CssRule::MozDocument(document));
+      }
+      CssRule::StartingStyle(mut starting_style) => {
+        lower_rules(&mut starting_style.rules, root, limit, targets);
+        result.push(CssRule::StartingStyle(starting_style));
+      }
+      CssRule::Scope(mut scope) => {
+        // Nested scopes are relative to the outer scope, so lower them
+        // first and then apply the outer scope to the result. They can
+        // always be lowered if the outer scope could be.
+        let mut nested = scope.lower(targets).unwrap();
+        lower_rules(&mut nested, root, limit, targets);
+        result.extend(nested.0);
+      }
+      rule => result.push(rule),
+    }
+  }
+  rules.0 = result;
+}
+
+impl<'i, T: ToCss> ToCss for ScopeRule<'i, T> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    #[cfg(feature = "sourcemap")]
+    dest.add_mapping(self.loc);
+    dest.write_str("@scope")?;
+    dest.whitespace()?;
+    if let Some(scope_start) = &self.scope_start {
+      dest.write_char('(')?;
+      scope_start.to_css(dest)?;
+      dest.write_char(')')?;
+      dest.whitespace()?;
+    }
+    if let Some(scope_end) = &self.scope_end {
+      if dest.minify {
+        dest.write_char(' ')?;
+      }
+      dest.write_str("to (")?;
+      scope_end.to_css(dest)?;
+      dest.write_char(')')?;
+      dest.whitespace()?;
+    }
+    dest.write_char('{')?;
+    dest.indent();
+    dest.newline()?;
+    self.rules.to_css(dest)?;
+    dest.dedent();
+    dest.newline()?;
+    dest.write_char('}')
+  }
+}
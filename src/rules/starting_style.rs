@@ -0,0 +1,54 @@
+//! The `@starting-style` rule.
+
+use super::Location;
+use super::{CssRuleList, MinifyContext};
+use crate::error::{MinifyError, PrinterError};
+use crate::parser::DefaultAtRule;
+use crate::printer::Printer;
+use crate::traits::ToCss;
+#[cfg(feature = "visitor")]
+use crate::visitor::Visit;
+
+/// A [@starting-style](https://drafts.csswg.org/css-transitions-2/#defining-before-change-style-the-starting-style-rule) rule.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct StartingStyleRule<'i, R = DefaultAtRule> {
+  /// Nested rules within the `@starting-style` rule.
+  #[cfg_attr(feature = "serde", serde(borrow))]
+  pub rules: CssRuleList<'i, R>,
+  /// The location of the rule in the source file.
+  #[cfg_attr(feature = "visitor", skip_visit)]
+  pub loc: Location,
+}
+
+impl<'i, T> StartingStyleRule<'i, T> {
+  pub(crate) fn minify(
+    &mut self,
+    context: &mut MinifyContext<'_, 'i>,
+    parent_is_unused: bool,
+  ) -> Result<bool, MinifyError> {
+    self.rules.minify(context, parent_is_unused)?;
+    Ok(self.rules.0.is_empty())
+  }
+}
+
+impl<'i, T: ToCss> ToCss for StartingStyleRule<'i, T> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    #[cfg(feature = "sourcemap")]
+    dest.add_mapping(self.loc);
+    dest.write_str("@starting-style")?;
+    dest.whitespace()?;
+    dest.write_char('{')?;
+    dest.indent();
+    dest.newline()?;
+    self.rules.to_css(dest)?;
+    dest.dedent();
+    dest.newline()?;
+    dest.write_char('}')
+  }
+}
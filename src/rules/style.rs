@@ -13,7 +13,7 @@ use crate::error::{MinifyError, PrinterError, PrinterErrorKind};
 use crate::parser::DefaultAtRule;
 use crate::printer::Printer;
 use crate::rules::CssRuleList;
-use crate::selector::{is_compatible, is_unused, SelectorList};
+use crate::selector::{is_compatible, is_unmatched, is_unused, SelectorList};
 use crate::targets::Browsers;
 use crate::traits::ToCss;
 use crate::vendor_prefix::VendorPrefix;
@@ -57,8 +57,12 @@ impl<'i, T> StyleRule<'i, T> {
     parent_is_unused: bool,
   ) -> Result<bool, MinifyError> {
     let mut unused = false;
-    if !context.unused_symbols.is_empty() {
-      if is_unused(&mut self.selectors.0.iter(), &context.unused_symbols, parent_is_unused) {
+    if !context.unused_symbols.is_empty() || context.used_symbols.is_some() {
+      let is_unmatched = match context.used_symbols {
+        Some(used_symbols) => is_unmatched(&self.selectors.0, used_symbols, parent_is_unused),
+        None => false,
+      };
+      if is_unmatched || is_unused(&mut self.selectors.0.iter(), &context.unused_symbols, parent_is_unused) {
         if self.rules.0.is_empty() {
           return Ok(true);
         }
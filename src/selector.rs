@@ -5,7 +5,7 @@ use crate::error::{ParserError, PrinterError};
 use crate::printer::Printer;
 use crate::properties::custom::TokenList;
 use crate::rules::StyleContext;
-use crate::stylesheet::{ParserOptions, PrinterOptions};
+use crate::stylesheet::{ParserOptions, PrinterOptions, UsedSymbols};
 use crate::targets::Browsers;
 use crate::traits::{Parse, ParseWithOptions, ToCss};
 use crate::values::ident::{CustomIdent, Ident};
@@ -1931,6 +1931,67 @@ fn downlevel_dir<'i>(dir: Direction, targets: Browsers) -> Component<'i> {
   }
 }
 
+/// Rewrites a selector within an `@scope` rule so that it is relative to the given scoping root.
+/// Occurrences of `:scope` and `&` are replaced by the root, otherwise the root is prepended
+/// using a descendant combinator.
+pub(crate) fn scope_selector<'i>(selector: &Selector<'i>, root: &Component<'i>) -> Selector<'i> {
+  let mut components = parse_order_components(selector);
+  if !replace_scope(&mut components, root) {
+    components.splice(0..0, [root.clone(), Component::Combinator(Combinator::Descendant)]);
+  }
+  Selector::from(components)
+}
+
+/// Returns a selector matching all descendants of the elements matched by the given selector.
+pub(crate) fn descendants_of<'i>(selector: &Selector<'i>) -> Selector<'i> {
+  let mut components = parse_order_components(selector);
+  components.push(Component::Combinator(Combinator::Descendant));
+  components.push(Component::ExplicitUniversalType);
+  Selector::from(components)
+}
+
+fn parse_order_components<'i>(selector: &Selector<'i>) -> Vec<Component<'i>> {
+  // Compound selectors are stored in reverse order, but their contents are not.
+  // See serialize_selector.
+  let mut combinators = selector.iter_raw_match_order().rev().filter_map(|x| x.as_combinator());
+  let compound_selectors = selector.iter_raw_match_order().as_slice().split(|x| x.is_combinator()).rev();
+  let mut components = Vec::with_capacity(selector.len());
+  for compound in compound_selectors {
+    components.extend(compound.iter().cloned());
+    if let Some(combinator) = combinators.next() {
+      components.push(Component::Combinator(combinator));
+    }
+  }
+  components
+}
+
+fn replace_scope<'i>(components: &mut [Component<'i>], root: &Component<'i>) -> bool {
+  let mut replaced = false;
+  for component in components {
+    match component {
+      Component::Scope | Component::Nesting => {
+        *component = root.clone();
+        replaced = true;
+      }
+      // :has() uses an implicit :scope for relative selectors, which refers to the subject, not the scoping root.
+      Component::Is(selectors)
+      | Component::Where(selectors)
+      | Component::Any(_, selectors)
+      | Component::Negation(selectors) => {
+        for selector in selectors.iter_mut() {
+          let mut inner = parse_order_components(selector);
+          if replace_scope(&mut inner, root) {
+            *selector = Selector::from(inner);
+            replaced = true;
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+  replaced
+}
+
 /// Determines whether a selector list contains only unused selectors.
 /// A selector is considered unused if it contains a class or id component that exists in the set of unused symbols.
 pub(crate) fn is_unused(
@@ -1968,6 +2029,74 @@ pub(crate) fn is_unused(
   })
 }
 
+/// Increases the number of ids in the specificity of a selector without changing which elements it matches.
+/// This is done by adding `:not(#\#)` pseudo classes to the subject compound selector, before any pseudo elements.
+pub(crate) fn increase_specificity<'i>(selector: &Selector<'i>, ids: usize) -> Selector<'i> {
+  let mut components = parse_order_components(selector);
+  let subject_start = components
+    .iter()
+    .rposition(|c| matches!(c, Component::Combinator(combinator) if *combinator != Combinator::PseudoElement))
+    .map_or(0, |i| i + 1);
+  let index = components[subject_start..]
+    .iter()
+    .position(|c| {
+      matches!(
+        c,
+        Component::Combinator(Combinator::PseudoElement) | Component::PseudoElement(_)
+      )
+    })
+    .map_or(components.len(), |i| subject_start + i);
+  // Wrapped in :global() so that CSS modules does not rename it.
+  let id = Selector::from(Component::NonTSPseudoClass(PseudoClass::Global {
+    selector: Box::new(Selector::from(Component::ID(Ident("#".into())))),
+  }));
+  components.splice(
+    index..index,
+    std::iter::repeat_with(|| Component::Negation(vec![id.clone()].into_boxed_slice())).take(ids),
+  );
+  Selector::from(components)
+}
+
+/// Determines whether every selector in a list can never match an element, given an inventory of the
+/// symbols used in the documents the style sheet applies to. A selector can never match if any of its
+/// components requires a class, id, element name, or attribute that is not in the inventory.
+pub(crate) fn is_unmatched(selectors: &[Selector], used_symbols: &UsedSymbols, parent_is_unused: bool) -> bool {
+  selectors.iter().all(|selector| {
+    selector.iter_raw_match_order().any(|component| match component {
+      Component::Class(name) => !used_symbols.classes.contains(name.0.as_ref()),
+      Component::ID(name) => !used_symbols.ids.contains(name.0.as_ref()),
+      Component::LocalName(local_name) => {
+        !used_symbols.elements.contains(local_name.name.0.as_ref())
+          && !used_symbols.elements.contains(local_name.lower_name.0.as_ref())
+      }
+      // Attribute values may change dynamically, so only the attribute name is checked.
+      Component::AttributeInNoNamespaceExists {
+        local_name,
+        local_name_lower,
+      } => {
+        !used_symbols.attributes.contains(local_name.0.as_ref())
+          && !used_symbols.attributes.contains(local_name_lower.0.as_ref())
+      }
+      Component::AttributeInNoNamespace { local_name, .. } => {
+        !used_symbols.attributes.contains(local_name.0.as_ref())
+      }
+      Component::AttributeOther(attr) => {
+        !used_symbols.attributes.contains(attr.local_name.0.as_ref())
+          && !used_symbols.attributes.contains(attr.local_name_lower.0.as_ref())
+      }
+      // An element matches these if it matches any of the selectors within them.
+      Component::Is(selectors)
+      | Component::Where(selectors)
+      | Component::Any(_, selectors)
+      | Component::Has(selectors) => is_unmatched(selectors, used_symbols, parent_is_unused),
+      Component::NthOf(nth_of) => is_unmatched(nth_of.selectors(), used_symbols, parent_is_unused),
+      Component::Nesting => parent_is_unused,
+      // A negation may always match, e.g. `:not(.unused)` matches every element.
+      _ => false,
+    })
+  })
+}
+
 #[cfg(feature = "visitor")]
 #[cfg_attr(docsrs, doc(cfg(feature = "visitor")))]
 impl<'i, T: Visit<'i, T, V>, V: Visitor<'i, T>> Visit<'i, T, V> for SelectorList<'i> {
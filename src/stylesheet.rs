@@ -5,21 +5,26 @@
 
 use crate::compat::Feature;
 use crate::context::{DeclarationContext, PropertyHandlerContext};
-use crate::css_modules::{CssModule, CssModuleExports, CssModuleReferences};
+use crate::css_modules::{CssModule, CssModuleExports, CssModuleReferences, ExternalComposes};
 use crate::declaration::{DeclarationBlock, DeclarationHandler};
-use crate::dependencies::Dependency;
+use crate::dependencies::{Dependency, InlinedAsset};
 use crate::error::{Error, ErrorLocation, MinifyErrorKind, ParserError, PrinterError, PrinterErrorKind};
+use crate::media_query::Environment;
 use crate::parser::{DefaultAtRule, DefaultAtRuleParser, TopLevelRuleParser};
 use crate::printer::Printer;
+use crate::rules::layer::flatten_layers;
+use crate::rules::media::prune_media_rules;
 use crate::rules::{CssRule, CssRuleList, MinifyContext};
 use crate::targets::Browsers;
 use crate::traits::{AtRuleParser, ToCss};
+use crate::tree_shaking::remove_unreferenced_at_rules;
 #[cfg(feature = "visitor")]
 use crate::visitor::{Visit, VisitTypes, Visitor};
 use cssparser::{Parser, ParserInput, RuleListParser};
 #[cfg(feature = "sourcemap")]
 use parcel_sourcemap::SourceMap;
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 pub use crate::parser::ParserOptions;
 pub use crate::printer::PrinterOptions;
@@ -59,7 +64,7 @@ pub use crate::printer::PseudoClasses;
 /// let res = stylesheet.to_css(PrinterOptions::default()).unwrap();
 /// assert_eq!(res.code, ".foo, .bar {\n  color: red;\n}\n");
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(
   feature = "serde",
   derive(serde::Serialize, serde::Deserialize),
@@ -79,6 +84,21 @@ pub struct StyleSheet<'i, 'o, T = DefaultAtRule> {
   pub sources: Vec<String>,
   /// The source map URL extracted from the original style sheet.
   pub(crate) source_map_urls: Vec<Option<String>>,
+  /// The original values of `url()` references that were rewritten by the bundler, keyed by the
+  /// source index, line and column of the url, so that dependencies refer to the original source.
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub(crate) original_urls: HashMap<(u32, u32, u32), String>,
+  /// Assets that were inlined as `data:` URIs by the bundler, keyed by the source index,
+  /// line and column of the url.
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub(crate) inlined_urls: HashMap<(u32, u32, u32), InlinedAsset>,
+  /// The source index of the file whose CSS module exports are returned by `to_css`.
+  /// For bundles, this is the entry file.
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub(crate) entry_source_index: u32,
+  /// The classes composed by files of a bundle that are printed in another chunk, e.g. the shared chunk.
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub(crate) external_composes: Option<Arc<ExternalComposes>>,
   #[cfg_attr(feature = "serde", serde(skip))]
   /// The options the style sheet was originally parsed with.
   options: ParserOptions<'o, 'i>,
@@ -93,6 +113,61 @@ pub struct MinifyOptions {
   /// A list of known unused symbols, including CSS class names,
   /// ids, and `@keyframe` names. The declarations of these will be removed.
   pub unused_symbols: HashSet<String>,
+  /// Whether to remove `@keyframes`, `@counter-style`, `@font-palette-values`, and `@property`
+  /// rules that are never referenced within the style sheet. The removed rules are returned
+  /// in the [MinifyResult](MinifyResult).
+  pub remove_unreferenced_at_rules: bool,
+  /// The symbols used by the documents the style sheet applies to, e.g. collected from HTML or JSX.
+  /// If provided, style rules with selectors that can never match any element will be removed.
+  pub used_symbols: Option<UsedSymbols>,
+  /// Whether to remove `@container` rules with conditions that can never match any container,
+  /// e.g. `(width < 0px)`. See [ContainerCondition::evaluate](crate::rules::container::ContainerCondition::evaluate).
+  pub remove_unmatched_container_queries: bool,
+}
+
+/// An inventory of the symbols used by a set of documents, e.g. collected from HTML or JSX files.
+///
+/// A selector is considered to never match if it requires a class name, id, element name,
+/// or attribute that does not appear in the inventory. Attribute values are assumed to be
+/// dynamic, so only the presence of an attribute name is checked.
+///
+/// See [MinifyOptions](MinifyOptions).
+#[derive(Debug, Clone, Default)]
+pub struct UsedSymbols {
+  /// Class names used in the documents.
+  pub classes: HashSet<String>,
+  /// Element ids used in the documents.
+  pub ids: HashSet<String>,
+  /// Element names used in the documents. HTML element names should be lowercase.
+  pub elements: HashSet<String>,
+  /// Attribute names used in the documents. HTML attribute names should be lowercase.
+  pub attributes: HashSet<String>,
+}
+
+/// A result returned from `minify`.
+#[derive(Debug, Default, PartialEq)]
+pub struct MinifyResult {
+  /// A list of at-rules that were removed because they were never referenced,
+  /// if the `remove_unreferenced_at_rules` option was enabled.
+  pub unreferenced_at_rules: Vec<UnreferencedAtRule>,
+  /// Warnings for rules that could not be faithfully lowered for the browser targets,
+  /// e.g. `@layer` rules that could not be flattened.
+  pub warnings: Vec<Error<MinifyErrorKind>>,
+}
+
+/// An at-rule that was removed because it was never referenced within the style sheet.
+///
+/// See [MinifyOptions](MinifyOptions).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UnreferencedAtRule {
+  /// A `@keyframes` rule with the given name, not used by any `animation` or `animation-name`.
+  Keyframes(String),
+  /// A `@counter-style` rule with the given name, not used by `list-style`, `content`, or another counter style.
+  CounterStyle(String),
+  /// A `@font-palette-values` rule with the given name, not used by `font-palette`.
+  FontPaletteValues(String),
+  /// A `@property` rule for a custom property that is never set or read.
+  Property(String),
 }
 
 /// A result returned from `to_css`, including the serialize CSS
@@ -110,6 +185,9 @@ pub struct ToCssResult {
   /// A list of dependencies (e.g. `@import` or `url()`) found in
   /// the style sheet, if the `analyze_dependencies` option is enabled.
   pub dependencies: Option<Vec<Dependency>>,
+  /// A list of assets that were inlined as `data:` URIs by the bundler, if the
+  /// `analyze_dependencies` option is enabled. Inlined urls are not included in `dependencies`.
+  pub inlined_assets: Option<Vec<InlinedAsset>>,
 }
 
 impl<'i, 'o> StyleSheet<'i, 'o, DefaultAtRule> {
@@ -132,6 +210,10 @@ where
     StyleSheet {
       sources,
       source_map_urls: Vec::new(),
+      original_urls: HashMap::new(),
+      inlined_urls: HashMap::new(),
+      entry_source_index: 0,
+      external_composes: None,
       rules,
       options,
     }
@@ -170,6 +252,10 @@ where
     Ok(StyleSheet {
       sources: vec![options.filename.clone()],
       source_map_urls: vec![parser.current_source_map_url().map(|s| s.to_owned())],
+      original_urls: HashMap::new(),
+      inlined_urls: HashMap::new(),
+      entry_source_index: 0,
+      external_composes: None,
       rules: CssRuleList(rules),
       options,
     })
@@ -188,7 +274,7 @@ where
   }
 
   /// Minify and transform the style sheet for the provided browser targets.
-  pub fn minify(&mut self, options: MinifyOptions) -> Result<(), Error<MinifyErrorKind>> {
+  pub fn minify(&mut self, options: MinifyOptions) -> Result<MinifyResult, Error<MinifyErrorKind>> {
     let mut context = PropertyHandlerContext::new(options.targets, &options.unused_symbols);
     let mut handler = DeclarationHandler::new(options.targets);
     let mut important_handler = DeclarationHandler::new(options.targets);
@@ -210,12 +296,31 @@ where
       None
     };
 
+    // Cascade layers must be flattened before rules are merged, so the layer of each rule is known.
+    let mut warnings = Vec::new();
+    if let Some(targets) = options.targets {
+      if !Feature::CascadeLayers.is_compatible(targets) {
+        warnings = flatten_layers(&mut self.rules)
+          .into_iter()
+          .map(|e| Error {
+            kind: e.kind,
+            loc: Some(ErrorLocation::new(
+              e.loc,
+              self.sources[e.loc.source_index as usize].clone(),
+            )),
+          })
+          .collect();
+      }
+    }
+
     let mut ctx = MinifyContext {
       targets: &options.targets,
       handler: &mut handler,
       important_handler: &mut important_handler,
       handler_context: &mut context,
       unused_symbols: &options.unused_symbols,
+      used_symbols: options.used_symbols.as_ref(),
+      remove_unmatched_container_queries: options.remove_unmatched_container_queries,
       custom_media,
       css_modules: self.options.css_modules.is_some(),
     };
@@ -228,7 +333,16 @@ where
       )),
     })?;
 
-    Ok(())
+    let unreferenced_at_rules = if options.remove_unreferenced_at_rules {
+      remove_unreferenced_at_rules(&mut self.rules)
+    } else {
+      Vec::new()
+    };
+
+    Ok(MinifyResult {
+      unreferenced_at_rules,
+      warnings,
+    })
   }
 
   /// Serialize the style sheet to a CSS string.
@@ -241,6 +355,8 @@ where
     #[cfg(feature = "sourcemap")]
     {
       printer.sources = Some(&self.sources);
+      printer.original_urls = Some(&self.original_urls);
+      printer.inlined_urls = Some(&self.inlined_urls);
     }
 
     #[cfg(feature = "sourcemap")]
@@ -252,13 +368,16 @@ where
       let mut references = HashMap::new();
       printer.css_module = Some(CssModule::new(config, &self.sources, project_root, &mut references));
 
+      printer.css_module.as_mut().unwrap().external = self.external_composes.as_deref();
+
       self.rules.to_css(&mut printer)?;
       printer.newline()?;
 
       Ok(ToCssResult {
         dependencies: printer.dependencies,
+        inlined_assets: printer.inlined_assets,
         exports: Some(std::mem::take(
-          &mut printer.css_module.unwrap().exports_by_source_index[0],
+          &mut printer.css_module.unwrap().exports_by_source_index[self.entry_source_index as usize],
         )),
         code: dest,
         references: Some(references),
@@ -269,6 +388,7 @@ where
 
       Ok(ToCssResult {
         dependencies: printer.dependencies,
+        inlined_assets: printer.inlined_assets,
         code: dest,
         exports: None,
         references: None,
@@ -291,6 +411,28 @@ where
   }
 }
 
+impl<'i, 'o, T: Clone> StyleSheet<'i, 'o, T> {
+  /// Returns a copy of the style sheet for the given environment, e.g. a class of devices.
+  ///
+  /// `@media` rules and `@import` rules that do not match the environment are removed, and `@media` rules
+  /// that always match it are replaced by their contents. Rules that depend on information not provided
+  /// by the environment are kept as is. See [MediaList::evaluate](crate::media_query::MediaList::evaluate).
+  pub fn prune_for_environment(&self, environment: &Environment) -> StyleSheet<'i, 'o, T> {
+    let mut rules = self.rules.clone();
+    prune_media_rules(&mut rules, environment);
+    StyleSheet {
+      rules,
+      sources: self.sources.clone(),
+      source_map_urls: self.source_map_urls.clone(),
+      original_urls: self.original_urls.clone(),
+      inlined_urls: self.inlined_urls.clone(),
+      entry_source_index: self.entry_source_index,
+      external_composes: self.external_composes.clone(),
+      options: self.options.clone(),
+    }
+  }
+}
+
 /// An inline style attribute, as in HTML or SVG.
 ///
 /// Style attributes can be parsed from a string, minified and transformed
@@ -360,11 +502,14 @@ impl<'i> StyleAttribute<'i> {
     let mut dest = String::with_capacity(1);
     let mut printer = Printer::new(&mut dest, options);
     printer.sources = Some(&self.sources);
+    // There are no rules to define the light-dark() custom properties in style attributes.
+    printer.light_dark_vars = false;
 
     self.declarations.to_css(&mut printer)?;
 
     Ok(ToCssResult {
       dependencies: printer.dependencies,
+      inlined_assets: printer.inlined_assets,
       code: dest,
       exports: None,
       references: None,
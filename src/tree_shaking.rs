@@ -0,0 +1,359 @@
+//! Removal of at-rules that are never referenced within a style sheet.
+//!
+//! This computes which `@keyframes`, `@counter-style`, `@font-palette-values`, and `@property`
+//! rules are reachable from the declarations in a style sheet, and removes the rest. Values that
+//! could not be fully parsed (e.g. containing `var()`) are scanned conservatively, so any identifier
+//! that appears within them is treated as a reference.
+
+use crate::declaration::DeclarationBlock;
+use crate::properties::animation::AnimationName;
+use crate::properties::content::{Content, ContentItem};
+use crate::properties::custom::{CustomPropertyName, Token, TokenList, TokenOrValue, UnresolvedColor};
+use crate::properties::list::{CounterStyle, ListStyleType};
+use crate::properties::{Property, PropertyId};
+use crate::rules::container::{ContainerCondition, StyleQuery};
+use crate::rules::keyframes::KeyframesName;
+use crate::rules::{CssRule, CssRuleList};
+use crate::stylesheet::UnreferencedAtRule;
+use crate::values::string::CowArcStr;
+use std::collections::{HashMap, HashSet};
+
+/// Removes all unreferenced `@keyframes`, `@counter-style`, `@font-palette-values`, and `@property`
+/// rules from the given rule list, and returns a list of the removed rules.
+pub(crate) fn remove_unreferenced_at_rules<'i, T>(rules: &mut CssRuleList<'i, T>) -> Vec<UnreferencedAtRule> {
+  // Imported style sheets may reference rules in this one, so nothing can be safely removed
+  // unless they have been inlined by the bundler.
+  if rules.0.iter().any(|rule| matches!(rule, CssRule::Import(..))) {
+    return Vec::new();
+  }
+
+  let mut references = References::default();
+  references.visit_rules(rules);
+
+  // Custom at-rules may reference anything, so nothing can be safely removed.
+  if references.has_custom_rules {
+    return Vec::new();
+  }
+
+  references.resolve_counter_styles();
+
+  let mut removed = Vec::new();
+  let mut seen = HashSet::new();
+  references.remove_rules(rules, &mut removed, &mut seen);
+  removed
+}
+
+#[derive(Default)]
+struct References<'i> {
+  /// Identifiers and strings that may reference a `@keyframes` or `@counter-style` rule.
+  idents: HashSet<CowArcStr<'i>>,
+  /// Dashed identifiers that may reference a `@font-palette-values` or `@property` rule.
+  dashed_idents: HashSet<CowArcStr<'i>>,
+  /// Identifiers referenced within each `@counter-style` rule, e.g. via `system: extends` or `fallback`.
+  /// These are only reachable if the counter style itself is referenced.
+  counter_style_references: HashMap<CowArcStr<'i>, HashSet<CowArcStr<'i>>>,
+  has_custom_rules: bool,
+}
+
+impl<'i> References<'i> {
+  fn visit_rules<T>(&mut self, rules: &CssRuleList<'i, T>) {
+    for rule in &rules.0 {
+      match rule {
+        CssRule::Style(style) => {
+          self.visit_declarations(&style.declarations);
+          self.visit_rules(&style.rules);
+        }
+        CssRule::Nesting(nesting) => {
+          self.visit_declarations(&nesting.style.declarations);
+          self.visit_rules(&nesting.style.rules);
+        }
+        CssRule::Media(media) => self.visit_rules(&media.rules),
+        CssRule::Supports(supports) => self.visit_rules(&supports.rules),
+        CssRule::Container(container) => {
+          self.visit_container_condition(&container.condition);
+          self.visit_rules(&container.rules);
+        }
+        CssRule::LayerBlock(layer) => self.visit_rules(&layer.rules),
+        CssRule::Scope(scope) => self.visit_rules(&scope.rules),
+        CssRule::StartingStyle(starting_style) => self.visit_rules(&starting_style.rules),
+        CssRule::MozDocument(document) => self.visit_rules(&document.rules),
+        CssRule::Keyframes(keyframes) => {
+          for keyframe in &keyframes.keyframes {
+            self.visit_declarations(&keyframe.declarations);
+          }
+        }
+        CssRule::Page(page) => {
+          self.visit_declarations(&page.declarations);
+          for margin_rule in &page.rules {
+            self.visit_declarations(&margin_rule.declarations);
+          }
+        }
+        CssRule::Viewport(viewport) => self.visit_declarations(&viewport.declarations),
+        CssRule::PositionTry(position_try) => self.visit_declarations(&position_try.declarations),
+        CssRule::CounterStyle(counter_style) => {
+          let idents = std::mem::take(&mut self.idents);
+          self.visit_declarations(&counter_style.declarations);
+          let references = std::mem::replace(&mut self.idents, idents);
+          self
+            .counter_style_references
+            .entry(counter_style.name.0.clone())
+            .or_default()
+            .extend(references);
+        }
+        CssRule::Unknown(unknown) => {
+          self.visit_token_list(&unknown.prelude);
+          if let Some(block) = &unknown.block {
+            self.visit_token_list(block);
+          }
+        }
+        CssRule::Custom(_) => self.has_custom_rules = true,
+        _ => {}
+      }
+    }
+  }
+
+  fn visit_declarations(&mut self, declarations: &DeclarationBlock<'i>) {
+    for (property, _) in declarations.iter() {
+      self.visit_property(property);
+    }
+  }
+
+  fn visit_property(&mut self, property: &Property<'i>) {
+    match property {
+      Property::AnimationName(names, _) => {
+        for name in names {
+          self.visit_animation_name(name);
+        }
+      }
+      Property::Animation(animations, _) => {
+        for animation in animations {
+          self.visit_animation_name(&animation.name);
+        }
+      }
+      Property::ListStyleType(list_style_type) => self.visit_list_style_type(list_style_type),
+      Property::ListStyle(list_style) => self.visit_list_style_type(&list_style.list_style_type),
+      Property::Content(Content::Items { items, alt }) => {
+        for item in items.iter().chain(alt.iter()) {
+          match item {
+            ContentItem::Counter { style, .. } | ContentItem::Counters { style, .. } => {
+              self.visit_counter_style(style)
+            }
+            _ => {}
+          }
+        }
+      }
+      Property::FontPalette(palette) => {
+        self.dashed_idents.insert(palette.ident.0.clone());
+      }
+      Property::TransitionProperty(properties, _) => {
+        for property in properties {
+          self.visit_property_id(property);
+        }
+      }
+      Property::Transition(transitions, _) => {
+        for transition in transitions {
+          self.visit_property_id(&transition.property);
+        }
+      }
+      Property::Custom(custom) => {
+        match &custom.name {
+          CustomPropertyName::Custom(name) => {
+            self.dashed_idents.insert(name.0.clone());
+          }
+          // `will-change` is not parsed into a typed property, so collect the custom properties it
+          // names explicitly.
+          CustomPropertyName::Unknown(name) if name.eq_ignore_ascii_case("will-change") => {
+            for token in &custom.value.0 {
+              match token {
+                TokenOrValue::DashedIdent(ident) => {
+                  self.dashed_idents.insert(ident.0.clone());
+                }
+                TokenOrValue::Token(Token::Ident(ident)) if ident.starts_with("--") => {
+                  self.dashed_idents.insert(ident.clone());
+                }
+                _ => {}
+              }
+            }
+          }
+          _ => {}
+        }
+        self.visit_token_list(&custom.value);
+      }
+      Property::Unparsed(unparsed) => self.visit_token_list(&unparsed.value),
+      _ => {}
+    }
+  }
+
+  fn visit_property_id(&mut self, property_id: &PropertyId<'i>) {
+    if let PropertyId::Custom(CustomPropertyName::Custom(name)) = property_id {
+      self.dashed_idents.insert(name.0.clone());
+    }
+  }
+
+  fn visit_animation_name(&mut self, name: &AnimationName<'i>) {
+    match name {
+      AnimationName::Ident(ident) => {
+        self.idents.insert(ident.0.clone());
+      }
+      AnimationName::String(s) => {
+        self.idents.insert(s.clone());
+      }
+      AnimationName::None => {}
+    }
+  }
+
+  fn visit_list_style_type(&mut self, list_style_type: &ListStyleType<'i>) {
+    if let ListStyleType::CounterStyle(style) = list_style_type {
+      self.visit_counter_style(style);
+    }
+  }
+
+  fn visit_counter_style(&mut self, style: &CounterStyle<'i>) {
+    if let CounterStyle::Name(name) = style {
+      self.idents.insert(name.0.clone());
+    }
+  }
+
+  fn visit_container_condition(&mut self, condition: &ContainerCondition<'i>) {
+    match condition {
+      ContainerCondition::Not(condition) => self.visit_container_condition(condition),
+      ContainerCondition::Operation { conditions, .. } => {
+        for condition in conditions {
+          self.visit_container_condition(condition);
+        }
+      }
+      ContainerCondition::Style(query) => self.visit_style_query(query),
+      ContainerCondition::Feature(_) => {}
+    }
+  }
+
+  fn visit_style_query(&mut self, query: &StyleQuery<'i>) {
+    match query {
+      StyleQuery::Feature(property) => self.visit_property(property),
+      StyleQuery::Not(query) => self.visit_style_query(query),
+      StyleQuery::Operation { conditions, .. } => {
+        for query in conditions {
+          self.visit_style_query(query);
+        }
+      }
+    }
+  }
+
+  fn visit_token_list(&mut self, token_list: &TokenList<'i>) {
+    for token in &token_list.0 {
+      match token {
+        TokenOrValue::Token(Token::Ident(ident)) => {
+          self.idents.insert(ident.clone());
+          self.dashed_idents.insert(ident.clone());
+        }
+        TokenOrValue::Token(Token::String(s)) => {
+          self.idents.insert(s.clone());
+        }
+        TokenOrValue::DashedIdent(ident) => {
+          self.dashed_idents.insert(ident.0.clone());
+        }
+        TokenOrValue::Var(var) => {
+          self.dashed_idents.insert(var.name.ident.0.clone());
+          if let Some(fallback) = &var.fallback {
+            self.visit_token_list(fallback);
+          }
+        }
+        TokenOrValue::Env(env) => {
+          if let Some(fallback) = &env.fallback {
+            self.visit_token_list(fallback);
+          }
+        }
+        TokenOrValue::Function(f) => self.visit_token_list(&f.arguments),
+        TokenOrValue::UnresolvedColor(color) => match color {
+          UnresolvedColor::RGB { alpha, .. } | UnresolvedColor::HSL { alpha, .. } => {
+            self.visit_token_list(alpha);
+          }
+          UnresolvedColor::LightDark { light, dark } => {
+            self.visit_token_list(light);
+            self.visit_token_list(dark);
+          }
+        },
+        _ => {}
+      }
+    }
+  }
+
+  /// Marks the identifiers referenced by reachable counter styles as referenced.
+  fn resolve_counter_styles(&mut self) {
+    let mut stack: Vec<_> = self.idents.iter().cloned().collect();
+    while let Some(name) = stack.pop() {
+      if let Some(references) = self.counter_style_references.remove(&name) {
+        for reference in references {
+          if self.idents.insert(reference.clone()) {
+            stack.push(reference);
+          }
+        }
+      }
+    }
+  }
+
+  /// Removes unreferenced rules from the given list. Returns whether the list became empty as a result.
+  fn remove_rules<T>(
+    &self,
+    rules: &mut CssRuleList<'i, T>,
+    removed: &mut Vec<UnreferencedAtRule>,
+    seen: &mut HashSet<UnreferencedAtRule>,
+  ) -> bool {
+    if rules.0.is_empty() {
+      return false;
+    }
+
+    rules.0.retain_mut(|rule| {
+      let unreferenced = match rule {
+        CssRule::Keyframes(keyframes) => {
+          let name = match &keyframes.name {
+            KeyframesName::Ident(ident) => &ident.0,
+            KeyframesName::Custom(s) => s,
+          };
+          (!self.idents.contains(name)).then(|| UnreferencedAtRule::Keyframes(name.to_string()))
+        }
+        CssRule::CounterStyle(counter_style) => (!self.idents.contains(&counter_style.name.0))
+          .then(|| UnreferencedAtRule::CounterStyle(counter_style.name.0.to_string())),
+        CssRule::FontPaletteValues(palette) => (!self.dashed_idents.contains(&palette.name.0))
+          .then(|| UnreferencedAtRule::FontPaletteValues(palette.name.0.to_string())),
+        CssRule::Property(property) => (!self.dashed_idents.contains(&property.name.0))
+          .then(|| UnreferencedAtRule::Property(property.name.0.to_string())),
+        // Conditional group rules that only contained unreferenced rules are removed entirely.
+        CssRule::Media(media) => return !self.remove_rules(&mut media.rules, removed, seen),
+        CssRule::Supports(supports) => return !self.remove_rules(&mut supports.rules, removed, seen),
+        CssRule::Container(container) => return !self.remove_rules(&mut container.rules, removed, seen),
+        CssRule::Scope(scope) => return !self.remove_rules(&mut scope.rules, removed, seen),
+        CssRule::StartingStyle(starting_style) => {
+          return !self.remove_rules(&mut starting_style.rules, removed, seen)
+        }
+        CssRule::MozDocument(document) => return !self.remove_rules(&mut document.rules, removed, seen),
+        // Empty layer blocks still affect the layer order, and style rules may have declarations.
+        CssRule::LayerBlock(layer) => {
+          self.remove_rules(&mut layer.rules, removed, seen);
+          None
+        }
+        CssRule::Style(style) => {
+          self.remove_rules(&mut style.rules, removed, seen);
+          None
+        }
+        CssRule::Nesting(nesting) => {
+          self.remove_rules(&mut nesting.style.rules, removed, seen);
+          None
+        }
+        _ => None,
+      };
+
+      match unreferenced {
+        Some(symbol) => {
+          if seen.insert(symbol.clone()) {
+            removed.push(symbol);
+          }
+          false
+        }
+        None => true,
+      }
+    });
+
+    rules.0.is_empty()
+  }
+}
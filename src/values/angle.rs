@@ -1,15 +1,16 @@
 //! CSS angle values.
 
 use super::calc::Calc;
-use super::length::serialize_dimension;
+use super::length::{impl_try_from_length, serialize_dimension};
 use super::number::CSSNumber;
 use super::percentage::DimensionPercentage;
 use crate::error::{ParserError, PrinterError};
 use crate::printer::Printer;
+use crate::targets::Browsers;
 use crate::traits::{
   impl_op,
   private::{AddInternal, TryAdd},
-  Map, Op, Parse, Sign, ToCss, Zero,
+  IsCompatible, Map, Op, Parse, Sign, ToCss, Zero,
 };
 #[cfg(feature = "visitor")]
 use crate::visitor::Visit;
@@ -163,6 +164,12 @@ impl Angle {
   }
 }
 
+impl IsCompatible for Angle {
+  fn is_compatible(&self, _browsers: Browsers) -> bool {
+    true
+  }
+}
+
 impl Zero for Angle {
   fn is_zero(&self) -> bool {
     use Angle::*;
@@ -276,6 +283,8 @@ impl_op!(Angle, std::ops::Add, add);
 /// May be specified as either an angle or a percentage that resolves to an angle.
 pub type AnglePercentage = DimensionPercentage<Angle>;
 
+impl_try_from_length!(Angle);
+
 macro_rules! impl_try_from_angle {
   ($t: ty) => {
     impl TryFrom<crate::values::angle::Angle> for $t {
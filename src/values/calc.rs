@@ -12,7 +12,7 @@ use crate::visitor::Visit;
 use cssparser::*;
 
 use super::angle::Angle;
-use super::length::Length;
+use super::length::{AnchorFunction, AnchorSizeFunction, Length, LengthPercentage, LengthValue};
 use super::number::CSSNumber;
 use super::percentage::Percentage;
 use super::time::Time;
@@ -50,6 +50,32 @@ pub enum MathFunction<V> {
   Sign(Calc<V>),
   /// The [`hypot()`](https://drafts.csswg.org/css-values-4/#funcdef-hypot) function.
   Hypot(Vec<Calc<V>>),
+  /// The [`sin()`](https://drafts.csswg.org/css-values-4/#funcdef-sin) function.
+  Sin(Calc<Angle>),
+  /// The [`cos()`](https://drafts.csswg.org/css-values-4/#funcdef-cos) function.
+  Cos(Calc<Angle>),
+  /// The [`tan()`](https://drafts.csswg.org/css-values-4/#funcdef-tan) function.
+  Tan(Calc<Angle>),
+  /// The [`asin()`](https://drafts.csswg.org/css-values-4/#funcdef-asin) function.
+  Asin(Calc<CSSNumber>),
+  /// The [`acos()`](https://drafts.csswg.org/css-values-4/#funcdef-acos) function.
+  Acos(Calc<CSSNumber>),
+  /// The [`atan()`](https://drafts.csswg.org/css-values-4/#funcdef-atan) function.
+  Atan(Calc<CSSNumber>),
+  /// The [`atan2()`](https://drafts.csswg.org/css-values-4/#funcdef-atan2) function.
+  Atan2(Atan2Arguments),
+  /// The [`pow()`](https://drafts.csswg.org/css-values-4/#funcdef-pow) function.
+  Pow(Calc<CSSNumber>, Calc<CSSNumber>),
+  /// The [`sqrt()`](https://drafts.csswg.org/css-values-4/#funcdef-sqrt) function.
+  Sqrt(Calc<CSSNumber>),
+  /// The [`log()`](https://drafts.csswg.org/css-values-4/#funcdef-log) function.
+  Log(Calc<CSSNumber>, Option<Calc<CSSNumber>>),
+  /// The [`exp()`](https://drafts.csswg.org/css-values-4/#funcdef-exp) function.
+  Exp(Calc<CSSNumber>),
+  /// The [`anchor()`](https://drafts.csswg.org/css-anchor-position-1/#anchor-pos) function.
+  Anchor(AnchorFunction),
+  /// The [`anchor-size()`](https://drafts.csswg.org/css-anchor-position-1/#anchor-size-fn) function.
+  AnchorSize(AnchorSizeFunction),
 }
 
 impl<V: IsCompatible> IsCompatible for MathFunction<V> {
@@ -82,10 +108,283 @@ impl<V: IsCompatible> IsCompatible for MathFunction<V> {
       MathFunction::Hypot(v) => {
         Feature::HypotFunction.is_compatible(browsers) && v.iter().all(|v| v.is_compatible(browsers))
       }
+      MathFunction::Sin(v) => Feature::SinFunction.is_compatible(browsers) && v.is_compatible(browsers),
+      MathFunction::Cos(v) => Feature::CosFunction.is_compatible(browsers) && v.is_compatible(browsers),
+      MathFunction::Tan(v) => Feature::TanFunction.is_compatible(browsers) && v.is_compatible(browsers),
+      MathFunction::Asin(v) => Feature::AsinFunction.is_compatible(browsers) && v.is_compatible(browsers),
+      MathFunction::Acos(v) => Feature::AcosFunction.is_compatible(browsers) && v.is_compatible(browsers),
+      MathFunction::Atan(v) => Feature::AtanFunction.is_compatible(browsers) && v.is_compatible(browsers),
+      MathFunction::Atan2(args) => Feature::Atan2Function.is_compatible(browsers) && args.is_compatible(browsers),
+      MathFunction::Pow(a, b) => {
+        Feature::PowFunction.is_compatible(browsers) && a.is_compatible(browsers) && b.is_compatible(browsers)
+      }
+      MathFunction::Sqrt(v) => Feature::SqrtFunction.is_compatible(browsers) && v.is_compatible(browsers),
+      MathFunction::Log(v, base) => {
+        Feature::LogFunction.is_compatible(browsers)
+          && v.is_compatible(browsers)
+          && base.as_ref().map_or(true, |b| b.is_compatible(browsers))
+      }
+      MathFunction::Exp(v) => Feature::ExpFunction.is_compatible(browsers) && v.is_compatible(browsers),
+      MathFunction::Anchor(a) => {
+        Feature::AnchorFunction.is_compatible(browsers)
+          && a.fallback.as_ref().map_or(true, |f| f.is_compatible(browsers))
+      }
+      MathFunction::AnchorSize(a) => {
+        Feature::AnchorSizeFunction.is_compatible(browsers)
+          && a.fallback.as_ref().map_or(true, |f| f.is_compatible(browsers))
+      }
     }
   }
 }
 
+impl<V> MathFunction<V> {
+  /// Returns whether the function contains a trigonometric or exponential function
+  /// whose arguments could not be resolved at parse time.
+  fn has_unresolved_function(&self) -> bool {
+    match self {
+      MathFunction::Calc(v) | MathFunction::Abs(v) | MathFunction::Sign(v) => v.has_unresolved_function(),
+      MathFunction::Min(v) | MathFunction::Max(v) | MathFunction::Hypot(v) => {
+        v.iter().any(|v| v.has_unresolved_function())
+      }
+      MathFunction::Clamp(a, b, c) => {
+        a.has_unresolved_function() || b.has_unresolved_function() || c.has_unresolved_function()
+      }
+      MathFunction::Round(_, a, b) | MathFunction::Rem(a, b) | MathFunction::Mod(a, b) => {
+        a.has_unresolved_function() || b.has_unresolved_function()
+      }
+      MathFunction::Sin(_)
+      | MathFunction::Cos(_)
+      | MathFunction::Tan(_)
+      | MathFunction::Asin(_)
+      | MathFunction::Acos(_)
+      | MathFunction::Atan(_)
+      | MathFunction::Atan2(_)
+      | MathFunction::Pow(..)
+      | MathFunction::Sqrt(_)
+      | MathFunction::Log(..)
+      | MathFunction::Exp(_)
+      | MathFunction::Anchor(_)
+      | MathFunction::AnchorSize(_) => true,
+    }
+  }
+
+  /// Evaluates a trigonometric or exponential function, if its arguments are resolvable.
+  ///
+  /// Functions are already folded when parsing, but values may also be constructed
+  /// or modified after parsing, e.g. by a visitor.
+  fn evaluate(&self) -> Option<ResolvedValue> {
+    let res = match self {
+      MathFunction::Sin(v) => ResolvedValue::Number(resolve_radians(v)?.sin()),
+      MathFunction::Cos(v) => ResolvedValue::Number(resolve_radians(v)?.cos()),
+      MathFunction::Tan(v) => ResolvedValue::Number(resolve_radians(v)?.tan()),
+      MathFunction::Asin(v) => ResolvedValue::Angle(Angle::Rad(resolve_number(v)?.asin())),
+      MathFunction::Acos(v) => ResolvedValue::Angle(Angle::Rad(resolve_number(v)?.acos())),
+      MathFunction::Atan(v) => ResolvedValue::Angle(Angle::Rad(resolve_number(v)?.atan())),
+      MathFunction::Atan2(args) => ResolvedValue::Angle(args.evaluate()?),
+      MathFunction::Pow(a, b) => ResolvedValue::Number(resolve_number(a)?.powf(resolve_number(b)?)),
+      MathFunction::Sqrt(v) => ResolvedValue::Number(resolve_number(v)?.sqrt()),
+      MathFunction::Log(v, None) => ResolvedValue::Number(resolve_number(v)?.ln()),
+      MathFunction::Log(v, Some(base)) => ResolvedValue::Number(resolve_number(v)?.log(resolve_number(base)?)),
+      MathFunction::Exp(v) => ResolvedValue::Number(resolve_number(v)?.exp()),
+      _ => return None,
+    };
+
+    match res {
+      ResolvedValue::Number(v) | ResolvedValue::Angle(Angle::Rad(v)) if !v.is_finite() => None,
+      res => Some(res),
+    }
+  }
+
+  /// Returns whether the function is compatible with the given browsers, ignoring its arguments.
+  fn is_function_compatible(&self, browsers: Browsers) -> bool {
+    let feature = match self {
+      MathFunction::Sin(_) => Feature::SinFunction,
+      MathFunction::Cos(_) => Feature::CosFunction,
+      MathFunction::Tan(_) => Feature::TanFunction,
+      MathFunction::Asin(_) => Feature::AsinFunction,
+      MathFunction::Acos(_) => Feature::AcosFunction,
+      MathFunction::Atan(_) => Feature::AtanFunction,
+      MathFunction::Atan2(_) => Feature::Atan2Function,
+      MathFunction::Pow(..) => Feature::PowFunction,
+      MathFunction::Sqrt(_) => Feature::SqrtFunction,
+      MathFunction::Log(..) => Feature::LogFunction,
+      MathFunction::Exp(_) => Feature::ExpFunction,
+      _ => return true,
+    };
+
+    feature.is_compatible(browsers)
+  }
+}
+
+/// The result of evaluating a trigonometric or exponential function.
+enum ResolvedValue {
+  Number(CSSNumber),
+  Angle(Angle),
+}
+
+impl ToCss for ResolvedValue {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      ResolvedValue::Number(v) => v.to_css(dest),
+      ResolvedValue::Angle(v) => v.to_css(dest),
+    }
+  }
+}
+
+/// Resolves the argument of a trigonometric function to radians.
+fn resolve_radians(v: &Calc<Angle>) -> Option<f32> {
+  match v {
+    Calc::Value(v) => Some(v.to_radians()),
+    Calc::Number(v) => Some(*v),
+    Calc::Function(f) => match f.evaluate()? {
+      ResolvedValue::Angle(v) => Some(v.to_radians()),
+      ResolvedValue::Number(v) => Some(v),
+    },
+    _ => None,
+  }
+}
+
+fn resolve_number(v: &Calc<CSSNumber>) -> Option<f32> {
+  match v {
+    Calc::Value(v) => Some(**v),
+    Calc::Number(v) => Some(*v),
+    Calc::Function(f) => match f.evaluate()? {
+      ResolvedValue::Number(v) => Some(v),
+      ResolvedValue::Angle(_) => None,
+    },
+    _ => None,
+  }
+}
+
+/// The arguments of an [`atan2()`](https://drafts.csswg.org/css-values-4/#funcdef-atan2) function
+/// that could not be resolved at parse time. Both arguments must be of the same type.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum Atan2Arguments {
+  /// Number arguments.
+  Number(Calc<CSSNumber>, Calc<CSSNumber>),
+  /// Length or percentage arguments.
+  LengthPercentage(Calc<LengthPercentage>, Calc<LengthPercentage>),
+  /// Angle arguments.
+  Angle(Calc<Angle>, Calc<Angle>),
+  /// Time arguments.
+  Time(Calc<Time>, Calc<Time>),
+}
+
+impl<'i> Parse<'i> for Atan2Arguments {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if let Ok((a, b)) = input.try_parse(|input| parse_atan2_pair(input, true)) {
+      return Ok(Atan2Arguments::Number(a, b));
+    }
+
+    if let Ok((a, b)) = input.try_parse(|input| parse_atan2_pair(input, false)) {
+      return Ok(Atan2Arguments::LengthPercentage(a, b));
+    }
+
+    if let Ok((a, b)) = input.try_parse(|input| parse_atan2_pair(input, false)) {
+      return Ok(Atan2Arguments::Angle(a, b));
+    }
+
+    let (a, b) = parse_atan2_pair(input, false)?;
+    Ok(Atan2Arguments::Time(a, b))
+  }
+}
+
+impl ToCss for Atan2Arguments {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    fn write<V: ToCss + std::ops::Mul<f32, Output = V> + TrySign + Clone + std::fmt::Debug, W: std::fmt::Write>(
+      a: &Calc<V>,
+      b: &Calc<V>,
+      dest: &mut Printer<W>,
+    ) -> Result<(), PrinterError> {
+      a.to_css(dest)?;
+      dest.delim(',', false)?;
+      b.to_css(dest)
+    }
+
+    match self {
+      Atan2Arguments::Number(a, b) => write(a, b, dest),
+      Atan2Arguments::LengthPercentage(a, b) => write(a, b, dest),
+      Atan2Arguments::Angle(a, b) => write(a, b, dest),
+      Atan2Arguments::Time(a, b) => write(a, b, dest),
+    }
+  }
+}
+
+impl IsCompatible for Atan2Arguments {
+  fn is_compatible(&self, browsers: Browsers) -> bool {
+    match self {
+      Atan2Arguments::Number(a, b) => a.is_compatible(browsers) && b.is_compatible(browsers),
+      Atan2Arguments::LengthPercentage(a, b) => a.is_compatible(browsers) && b.is_compatible(browsers),
+      Atan2Arguments::Angle(a, b) => a.is_compatible(browsers) && b.is_compatible(browsers),
+      Atan2Arguments::Time(a, b) => a.is_compatible(browsers) && b.is_compatible(browsers),
+    }
+  }
+}
+
+impl Atan2Arguments {
+  fn evaluate(&self) -> Option<Angle> {
+    match self {
+      Atan2Arguments::Number(a, b) => Some(Angle::Rad(resolve_number(a)?.atan2(resolve_number(b)?))),
+      Atan2Arguments::LengthPercentage(a, b) => atan2(a, b),
+      Atan2Arguments::Angle(a, b) => atan2(a, b),
+      Atan2Arguments::Time(a, b) => atan2(a, b),
+    }
+  }
+}
+
+/// Computes `atan2()` of two values of the same type, if they can be resolved.
+fn atan2<V: TryOp>(a: &Calc<V>, b: &Calc<V>) -> Option<Angle> {
+  match (a, b) {
+    (Calc::Value(a), Calc::Value(b)) => a.try_op_to(&**b, |a, b| Angle::Rad(a.atan2(b))),
+    (Calc::Number(a), Calc::Number(b)) => Some(Angle::Rad(a.atan2(*b))),
+    _ => None,
+  }
+}
+
+fn parse_atan2_pair<'i, 't, V>(
+  input: &mut Parser<'i, 't>,
+  numbers: bool,
+) -> Result<(Calc<V>, Calc<V>), ParseError<'i, ParserError<'i>>>
+where
+  V: Parse<'i>
+    + std::ops::Mul<f32, Output = V>
+    + AddInternal
+    + TryOp
+    + TryMap
+    + TrySign
+    + std::cmp::PartialOrd<V>
+    + Into<Calc<V>>
+    + From<Calc<V>>
+    + TryFrom<Angle>
+    + TryFrom<LengthValue>
+    + Clone
+    + std::fmt::Debug,
+{
+  let a = Calc::<V>::parse_sum(input, |_| None)?;
+  input.expect_comma()?;
+  let b = Calc::<V>::parse_sum(input, |_| None)?;
+
+  // Plain numbers cannot be mixed with dimensions.
+  if !numbers && (matches!(a, Calc::Number(_)) || matches!(b, Calc::Number(_))) {
+    return Err(input.new_custom_error(ParserError::InvalidValue));
+  }
+
+  Ok((a, b))
+}
+
 enum_property! {
   /// A [rounding strategy](https://www.w3.org/TR/css-values-4/#typedef-rounding-strategy),
   /// as used in the `round()` function.
@@ -126,6 +425,14 @@ impl<V: ToCss + std::ops::Mul<f32, Output = V> + TrySign + Clone + std::fmt::Deb
   where
     W: std::fmt::Write,
   {
+    // Fold trigonometric and exponential functions with resolvable arguments when minifying,
+    // or when the targets don't support them.
+    if dest.minify || matches!(dest.targets, Some(targets) if !self.is_function_compatible(targets)) {
+      if let Some(v) = self.evaluate() {
+        return v.to_css(dest);
+      }
+    }
+
     match self {
       MathFunction::Calc(calc) => {
         dest.write_str("calc(")?;
@@ -230,10 +537,44 @@ impl<V: ToCss + std::ops::Mul<f32, Output = V> + TrySign + Clone + std::fmt::Deb
         }
         dest.write_char(')')
       }
+      MathFunction::Sin(v) => write_fn("sin", v, dest),
+      MathFunction::Cos(v) => write_fn("cos", v, dest),
+      MathFunction::Tan(v) => write_fn("tan", v, dest),
+      MathFunction::Asin(v) => write_fn("asin", v, dest),
+      MathFunction::Acos(v) => write_fn("acos", v, dest),
+      MathFunction::Atan(v) => write_fn("atan", v, dest),
+      MathFunction::Atan2(args) => write_fn("atan2", args, dest),
+      MathFunction::Pow(a, b) => {
+        dest.write_str("pow(")?;
+        a.to_css(dest)?;
+        dest.delim(',', false)?;
+        b.to_css(dest)?;
+        dest.write_char(')')
+      }
+      MathFunction::Sqrt(v) => write_fn("sqrt", v, dest),
+      MathFunction::Log(v, base) => {
+        dest.write_str("log(")?;
+        v.to_css(dest)?;
+        if let Some(base) = base {
+          dest.delim(',', false)?;
+          base.to_css(dest)?;
+        }
+        dest.write_char(')')
+      }
+      MathFunction::Exp(v) => write_fn("exp", v, dest),
+      MathFunction::Anchor(a) => a.to_css(dest),
+      MathFunction::AnchorSize(a) => a.to_css(dest),
     }
   }
 }
 
+fn write_fn<T: ToCss, W: std::fmt::Write>(name: &str, arg: &T, dest: &mut Printer<W>) -> Result<(), PrinterError> {
+  dest.write_str(name)?;
+  dest.write_char('(')?;
+  arg.to_css(dest)?;
+  dest.write_char(')')
+}
+
 /// A mathematical expression used within the [`calc()`](https://www.w3.org/TR/css-values-4/#calc-func) function.
 ///
 /// This type supports generic value types. Values such as [Length](super::length::Length), [Percentage](super::percentage::Percentage),
@@ -262,6 +603,17 @@ pub enum Calc<V> {
   Function(Box<MathFunction<V>>),
 }
 
+impl<V> Calc<V> {
+  fn has_unresolved_function(&self) -> bool {
+    match self {
+      Calc::Value(_) | Calc::Number(_) => false,
+      Calc::Sum(a, b) => a.has_unresolved_function() || b.has_unresolved_function(),
+      Calc::Product(_, v) => v.has_unresolved_function(),
+      Calc::Function(f) => f.has_unresolved_function(),
+    }
+  }
+}
+
 impl<V: IsCompatible> IsCompatible for Calc<V> {
   fn is_compatible(&self, browsers: Browsers) -> bool {
     match self {
@@ -316,6 +668,7 @@ impl<
       + Into<Calc<V>>
       + From<Calc<V>>
       + TryFrom<Angle>
+      + TryFrom<LengthValue>
       + Clone
       + std::fmt::Debug,
   > Parse<'i> for Calc<V>
@@ -337,6 +690,7 @@ impl<
       + Into<Calc<V>>
       + From<Calc<V>>
       + TryFrom<Angle>
+      + TryFrom<LengthValue>
       + Clone
       + std::fmt::Debug,
   > Calc<V>
@@ -453,20 +807,26 @@ impl<
           Self::parse_math_fn(input, modulo, MathFunction::Mod, parse_ident)
         })
       },
-      "sin" => Self::parse_trig(input, f32::sin, false, parse_ident),
-      "cos" => Self::parse_trig(input, f32::cos, false, parse_ident),
-      "tan" => Self::parse_trig(input, f32::tan, false, parse_ident),
-      "asin" => Self::parse_trig(input, f32::asin, true, parse_ident),
-      "acos" => Self::parse_trig(input, f32::acos, true, parse_ident),
-      "atan" => Self::parse_trig(input, f32::atan, true, parse_ident),
+      "sin" => Self::parse_trig(input, f32::sin, MathFunction::Sin, parse_ident),
+      "cos" => Self::parse_trig(input, f32::cos, MathFunction::Cos, parse_ident),
+      "tan" => Self::parse_trig(input, f32::tan, MathFunction::Tan, parse_ident),
+      "asin" => Self::parse_inverse_trig(input, f32::asin, MathFunction::Asin, parse_ident),
+      "acos" => Self::parse_inverse_trig(input, f32::acos, MathFunction::Acos, parse_ident),
+      "atan" => Self::parse_inverse_trig(input, f32::atan, MathFunction::Atan, parse_ident),
       "atan2" => {
         input.parse_nested_block(|input| {
-          let res = Self::parse_atan2(input, parse_ident)?;
-          if let Ok(v) = V::try_from(res) {
-            return Ok(Calc::Value(Box::new(v)))
+          if let Ok(res) = input.try_parse(|input| Self::parse_atan2(input, parse_ident)) {
+            if let Ok(v) = V::try_from(res) {
+              return Ok(Calc::Value(Box::new(v)))
+            }
+
+            return Err(input.new_custom_error(ParserError::InvalidValue))
           }
 
-          Err(input.new_custom_error(ParserError::InvalidValue))
+          // Arguments with incompatible units (e.g. `atan2(1px, 1vw)`) cannot be resolved
+          // until computed value time, so preserve the function if the result is valid here.
+          let args = Atan2Arguments::parse(input)?;
+          Self::unresolved_angle(input, MathFunction::Atan2(args))
         })
       },
       "pow" => {
@@ -474,22 +834,30 @@ impl<
           let a = Self::parse_numeric(input, parse_ident)?;
           input.expect_comma()?;
           let b = Self::parse_numeric(input, parse_ident)?;
-          Ok(Calc::Number(a.powf(b)))
+          match (&a, &b) {
+            (Calc::Number(a), Calc::Number(b)) => Ok(Calc::Number(a.powf(*b))),
+            _ => Ok(Calc::Function(Box::new(MathFunction::Pow(a, b))))
+          }
         })
       },
       "log" => {
         input.parse_nested_block(|input| {
           let value = Self::parse_numeric(input, parse_ident)?;
-          if input.try_parse(|input| input.expect_comma()).is_ok() {
-            let base = Self::parse_numeric(input, parse_ident)?;
-            Ok(Calc::Number(value.log(base)))
+          let base = if input.try_parse(|input| input.expect_comma()).is_ok() {
+            Some(Self::parse_numeric(input, parse_ident)?)
           } else {
-            Ok(Calc::Number(value.ln()))
+            None
+          };
+
+          match (&value, &base) {
+            (Calc::Number(value), Some(Calc::Number(base))) => Ok(Calc::Number(value.log(*base))),
+            (Calc::Number(value), None) => Ok(Calc::Number(value.ln())),
+            _ => Ok(Calc::Function(Box::new(MathFunction::Log(value, base))))
           }
         })
       },
-      "sqrt" => Self::parse_numeric_fn(input, f32::sqrt, parse_ident),
-      "exp" => Self::parse_numeric_fn(input, f32::exp, parse_ident),
+      "sqrt" => Self::parse_numeric_fn(input, f32::sqrt, MathFunction::Sqrt, parse_ident),
+      "exp" => Self::parse_numeric_fn(input, f32::exp, MathFunction::Exp, parse_ident),
       "hypot" => {
         input.parse_nested_block(|input| {
           let args: Vec<Self> = input.parse_comma_separated(|input| Calc::parse_sum(input, parse_ident))?;
@@ -528,6 +896,14 @@ impl<
 
           Ok(Calc::Function(Box::new(MathFunction::Sign(v))))
         })
+      },
+      "anchor" => {
+        let anchor = input.parse_nested_block(AnchorFunction::parse_arguments)?;
+        Self::unresolved_length(input, MathFunction::Anchor(anchor))
+      },
+      "anchor-size" => {
+        let anchor = input.parse_nested_block(AnchorSizeFunction::parse_arguments)?;
+        Self::unresolved_length(input, MathFunction::AnchorSize(anchor))
       },
        _ => Err(location.new_unexpected_token_error(Token::Ident(f.clone()))),
     }
@@ -734,10 +1110,15 @@ impl<
     None
   }
 
-  fn parse_trig<'t, F: FnOnce(f32) -> f32, Parse: Copy + Fn(&str) -> Option<Calc<V>>>(
+  fn parse_trig<
+    't,
+    F: FnOnce(f32) -> f32,
+    G: FnOnce(Calc<Angle>) -> MathFunction<V>,
+    Parse: Copy + Fn(&str) -> Option<Calc<V>>,
+  >(
     input: &mut Parser<'i, 't>,
     f: F,
-    to_angle: bool,
+    fallback: G,
     parse_ident: Parse,
   ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
     input.parse_nested_block(|input| {
@@ -747,28 +1128,72 @@ impl<
           _ => None,
         })
       })?;
-      let rad = match v {
-        Calc::Value(angle) if !to_angle => f(angle.to_radians()),
-        Calc::Number(v) => f(v),
-        _ => return Err(input.new_custom_error(ParserError::InvalidValue)),
+
+      match v {
+        Calc::Value(angle) => Ok(Calc::Number(f(angle.to_radians()))),
+        Calc::Number(v) => Ok(Calc::Number(f(v))),
+        v => Ok(Calc::Function(Box::new(fallback(v)))),
+      }
+    })
+  }
+
+  fn parse_inverse_trig<
+    't,
+    F: FnOnce(f32) -> f32,
+    G: FnOnce(Calc<CSSNumber>) -> MathFunction<V>,
+    Parse: Copy + Fn(&str) -> Option<Calc<V>>,
+  >(
+    input: &mut Parser<'i, 't>,
+    f: F,
+    fallback: G,
+    parse_ident: Parse,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    input.parse_nested_block(|input| {
+      let v = match Self::parse_numeric(input, parse_ident)? {
+        Calc::Number(v) => v,
+        v => return Self::unresolved_angle(input, fallback(v)),
       };
 
-      if to_angle && !rad.is_nan() {
-        if let Ok(v) = V::try_from(Angle::Rad(rad)) {
-          return Ok(Calc::Value(Box::new(v)));
-        } else {
-          return Err(input.new_custom_error(ParserError::InvalidValue));
-        }
+      let rad = f(v);
+      if rad.is_nan() {
+        return Ok(Calc::Number(rad));
+      }
+
+      if let Ok(v) = V::try_from(Angle::Rad(rad)) {
+        Ok(Calc::Value(Box::new(v)))
       } else {
-        Ok(Calc::Number(rad))
+        Err(input.new_custom_error(ParserError::InvalidValue))
       }
     })
   }
 
+  /// Returns a function that resolves to an angle, if angles are valid in this context.
+  fn unresolved_angle<'t>(
+    input: &mut Parser<'i, 't>,
+    f: MathFunction<V>,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if V::try_from(Angle::Rad(0.0)).is_ok() {
+      Ok(Calc::Function(Box::new(f)))
+    } else {
+      Err(input.new_custom_error(ParserError::InvalidValue))
+    }
+  }
+
+  fn unresolved_length<'t>(
+    input: &mut Parser<'i, 't>,
+    f: MathFunction<V>,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if V::try_from(LengthValue::Px(0.0)).is_ok() {
+      Ok(Calc::Function(Box::new(f)))
+    } else {
+      Err(input.new_custom_error(ParserError::InvalidValue))
+    }
+  }
+
   fn parse_numeric<'t, Parse: Copy + Fn(&str) -> Option<Calc<V>>>(
     input: &mut Parser<'i, 't>,
     parse_ident: Parse,
-  ) -> Result<f32, ParseError<'i, ParserError<'i>>> {
+  ) -> Result<Calc<CSSNumber>, ParseError<'i, ParserError<'i>>> {
     let v: Calc<CSSNumber> = Calc::parse_sum(input, |v| {
       parse_ident(v).and_then(|v| match v {
         Calc::Number(v) => Some(Calc::Number(v)),
@@ -776,20 +1201,25 @@ impl<
       })
     })?;
     match v {
-      Calc::Number(n) => Ok(n),
-      Calc::Value(v) => Ok(*v),
-      _ => Err(input.new_custom_error(ParserError::InvalidValue)),
+      Calc::Value(v) => Ok(Calc::Number(*v)),
+      v => Ok(v),
     }
   }
 
-  fn parse_numeric_fn<'t, F: FnOnce(f32) -> f32, Parse: Copy + Fn(&str) -> Option<Calc<V>>>(
+  fn parse_numeric_fn<
+    't,
+    F: FnOnce(f32) -> f32,
+    G: FnOnce(Calc<CSSNumber>) -> MathFunction<V>,
+    Parse: Copy + Fn(&str) -> Option<Calc<V>>,
+  >(
     input: &mut Parser<'i, 't>,
     f: F,
+    fallback: G,
     parse_ident: Parse,
   ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
-    input.parse_nested_block(|input| {
-      let v = Self::parse_numeric(input, parse_ident)?;
-      Ok(Calc::Number(f(v)))
+    input.parse_nested_block(|input| match Self::parse_numeric(input, parse_ident)? {
+      Calc::Number(v) => Ok(Calc::Number(f(v))),
+      v => Ok(Calc::Function(Box::new(fallback(v)))),
     })
   }
 
@@ -832,19 +1262,8 @@ impl<
     input.expect_comma()?;
     let b = Calc::<V>::parse_sum(input, parse_ident)?;
 
-    match (&a, &b) {
-      (Calc::Value(a), Calc::Value(b)) => {
-        if let Some(v) = a.try_op_to(&**b, |a, b| Angle::Rad(a.atan2(b))) {
-          return Ok(v);
-        }
-      }
-      (Calc::Number(a), Calc::Number(b)) => return Ok(Angle::Rad(a.atan2(*b))),
-      _ => {}
-    }
-
-    // We don't have a way to represent arguments that aren't angles, so just error.
-    // This will fall back to an unparsed property, leaving the atan2() function intact.
-    Err(input.new_custom_error(ParserError::InvalidValue))
+    // Unresolved arguments are preserved in an Atan2 function instead.
+    atan2(&a, &b).ok_or_else(|| input.new_custom_error(ParserError::InvalidValue))
   }
 
   fn parse_hypot<'t>(args: &Vec<Self>) -> Result<Option<Self>, ParseError<'i, ParserError<'i>>> {
@@ -906,6 +1325,12 @@ impl<V: AddInternal + std::convert::Into<Calc<V>> + std::convert::From<Calc<V>>
 {
   fn add(self, other: Calc<V>) -> Calc<V> {
     match (self, other) {
+      // Unresolved trig and exponential functions may be used in types such as <angle> and <number>
+      // that cannot represent a calc() expression, so they must not be converted to a value.
+      (a, b) if a.has_unresolved_function() || b.has_unresolved_function() => match (a, b) {
+        (Calc::Sum(a, b), c @ Calc::Value(_)) if matches!(*b, Calc::Value(_)) => Calc::Sum(a, Box::new(b.add(c))),
+        (a, b) => Calc::Sum(Box::new(a), Box::new(b)),
+      },
       (Calc::Value(a), Calc::Value(b)) => (a.add(*b)).into(),
       (Calc::Number(a), Calc::Number(b)) => Calc::Number(a + b),
       (Calc::Value(a), b) => (a.add(V::from(b))).into(),
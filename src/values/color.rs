@@ -57,6 +57,10 @@ pub enum CssColor {
   Predefined(Box<PredefinedColor>),
   /// A floating point representation of an RGB, HSL, or HWB color when it contains `none` components.
   Float(Box<FloatColor>),
+  /// The [light-dark()](https://drafts.csswg.org/css-color-5/#light-dark) function.
+  #[cfg_attr(feature = "serde", serde(with = "LightDark"))]
+  #[cfg_attr(feature = "visitor", skip_type)]
+  LightDark(Box<CssColor>, Box<CssColor>),
 }
 
 #[cfg(feature = "serde")]
@@ -86,6 +90,39 @@ impl CurrentColor {
   }
 }
 
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+enum LightDark {
+  LightDark { light: CssColor, dark: CssColor },
+}
+
+#[cfg(feature = "serde")]
+impl LightDark {
+  fn serialize<S>(light: &Box<CssColor>, dark: &Box<CssColor>, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serde::Serialize::serialize(
+      &LightDark::LightDark {
+        light: (**light).clone(),
+        dark: (**dark).clone(),
+      },
+      serializer,
+    )
+  }
+
+  fn deserialize<'de, D>(deserializer: D) -> Result<(Box<CssColor>, Box<CssColor>), D::Error>
+  where
+    D: serde::Deserializer<'de>,
+  {
+    use serde::Deserialize;
+    let LightDark::LightDark { light, dark } = Deserialize::deserialize(deserializer)?;
+    Ok((Box::new(light), Box::new(dark)))
+  }
+}
+
 // Convert RGBA to SRGB to serialize so we get a tagged struct.
 #[cfg(feature = "serde")]
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -204,21 +241,42 @@ bitflags! {
 enum_property! {
   /// A [color space](https://www.w3.org/TR/css-color-4/#interpolation-space) keyword
   /// used in interpolation functions such as `color-mix()`.
-  enum ColorSpaceName {
+  pub enum ColorSpaceName {
+    /// The `srgb` color space.
     "srgb": SRGB,
+    /// The `srgb-linear` color space.
     "srgb-linear": SRGBLinear,
+    /// The `lab` color space.
     "lab": LAB,
+    /// The `oklab` color space.
     "oklab": OKLAB,
+    /// The `xyz` color space, an alias of `xyz-d65`.
     "xyz": XYZ,
+    /// The `xyz-d50` color space.
     "xyz-d50": XYZd50,
+    /// The `xyz-d65` color space.
     "xyz-d65": XYZd65,
+    /// The `hsl` color space.
     "hsl": Hsl,
+    /// The `hwb` color space.
     "hwb": Hwb,
+    /// The `lch` color space.
     "lch": LCH,
+    /// The `oklch` color space.
     "oklch": OKLCH,
   }
 }
 
+impl ColorSpaceName {
+  /// Returns whether the color space has a hue component.
+  pub fn is_polar(&self) -> bool {
+    matches!(
+      self,
+      ColorSpaceName::Hsl | ColorSpaceName::Hwb | ColorSpaceName::LCH | ColorSpaceName::OKLCH
+    )
+  }
+}
+
 enum_property! {
   /// A hue [interpolation method](https://www.w3.org/TR/css-color-4/#typedef-hue-interpolation-method)
   /// used in interpolation functions such as `color-mix()`.
@@ -236,6 +294,61 @@ enum_property! {
   }
 }
 
+/// A [color interpolation method](https://www.w3.org/TR/css-color-4/#color-interpolation-method),
+/// as used in `color-mix()` and gradients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct ColorInterpolationMethod {
+  /// The color space in which to interpolate.
+  pub space: ColorSpaceName,
+  /// The method used to interpolate hues in polar color spaces.
+  pub hue: HueInterpolationMethod,
+}
+
+impl<'i> Parse<'i> for ColorInterpolationMethod {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    input.expect_ident_matching("in")?;
+    let space = ColorSpaceName::parse(input)?;
+
+    let hue = if space.is_polar() {
+      let hue = input.try_parse(HueInterpolationMethod::parse);
+      if hue.is_ok() {
+        input.expect_ident_matching("hue")?;
+      }
+      hue.unwrap_or(HueInterpolationMethod::Shorter)
+    } else {
+      HueInterpolationMethod::Shorter
+    };
+
+    Ok(ColorInterpolationMethod { space, hue })
+  }
+}
+
+impl ToCss for ColorInterpolationMethod {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    dest.write_str("in ")?;
+    self.space.to_css(dest)?;
+    if self.hue != HueInterpolationMethod::Shorter {
+      dest.write_char(' ')?;
+      self.hue.to_css(dest)?;
+      dest.write_str(" hue")?;
+    }
+    Ok(())
+  }
+}
+
+impl ColorInterpolationMethod {
+  /// Mixes two colors using this interpolation method, including the specified amount of each.
+  pub(crate) fn mix(&self, first: &CssColor, p1: f32, second: &CssColor, p2: f32) -> CssColor {
+    mix_colors(self.space, first, p1, second, p2, self.hue)
+  }
+}
+
 impl ColorFallbackKind {
   pub(crate) fn lowest(&self) -> ColorFallbackKind {
     // This finds the lowest set bit.
@@ -287,17 +400,26 @@ impl CssColor {
 
   /// Converts the color to RGBA.
   pub fn to_rgb(&self) -> CssColor {
-    RGBA::from(self).into()
+    match self {
+      CssColor::LightDark(..) => self.get_fallback(ColorFallbackKind::RGB),
+      _ => RGBA::from(self).into(),
+    }
   }
 
   /// Converts the color to the LAB color space.
   pub fn to_lab(&self) -> CssColor {
-    LAB::from(self).into()
+    match self {
+      CssColor::LightDark(..) => self.get_fallback(ColorFallbackKind::LAB),
+      _ => LAB::from(self).into(),
+    }
   }
 
   /// Converts the color to the P3 color space.
   pub fn to_p3(&self) -> CssColor {
-    P3::from(self).into()
+    match self {
+      CssColor::LightDark(..) => self.get_fallback(ColorFallbackKind::P3),
+      _ => P3::from(self).into(),
+    }
   }
 
   pub(crate) fn get_possible_fallbacks(&self, targets: Browsers) -> ColorFallbackKind {
@@ -306,6 +428,9 @@ impl CssColor {
     // compatible with our browser targets.
     let mut fallbacks = match self {
       CssColor::CurrentColor | CssColor::RGBA(_) | CssColor::Float(..) => return ColorFallbackKind::empty(),
+      CssColor::LightDark(light, dark) => {
+        return light.get_possible_fallbacks(targets) | dark.get_possible_fallbacks(targets)
+      }
       CssColor::LAB(lab) => match &**lab {
         LABColor::LAB(..) | LABColor::LCH(..) => ColorFallbackKind::LAB.and_below(),
         LABColor::OKLAB(..) | LABColor::OKLCH(..) => ColorFallbackKind::OKLAB.and_below(),
@@ -362,8 +487,12 @@ impl CssColor {
 
   /// Returns a fallback color for the given fallback type.
   pub fn get_fallback(&self, kind: ColorFallbackKind) -> CssColor {
-    if matches!(self, CssColor::RGBA(_)) {
-      return self.clone();
+    match self {
+      CssColor::CurrentColor | CssColor::RGBA(_) => return self.clone(),
+      CssColor::LightDark(light, dark) => {
+        return CssColor::LightDark(Box::new(light.get_fallback(kind)), Box::new(dark.get_fallback(kind)))
+      }
+      _ => {}
     }
 
     match kind {
@@ -379,6 +508,8 @@ impl IsCompatible for CssColor {
   fn is_compatible(&self, browsers: Browsers) -> bool {
     match self {
       CssColor::CurrentColor | CssColor::RGBA(_) | CssColor::Float(..) => true,
+      // light-dark() is lowered to custom properties when unsupported, so only the inner colors matter.
+      CssColor::LightDark(light, dark) => light.is_compatible(browsers) && dark.is_compatible(browsers),
       CssColor::LAB(lab) => match &**lab {
         LABColor::LAB(..) | LABColor::LCH(..) => Feature::LabColors.is_compatible(browsers),
         LABColor::OKLAB(..) | LABColor::OKLCH(..) => Feature::OklabColors.is_compatible(browsers),
@@ -397,15 +528,15 @@ impl FallbackValues for CssColor {
 
     let mut res = Vec::new();
     if fallbacks.contains(ColorFallbackKind::RGB) {
-      res.push(self.to_rgb());
+      res.push(self.get_fallback(ColorFallbackKind::RGB));
     }
 
     if fallbacks.contains(ColorFallbackKind::P3) {
-      res.push(self.to_p3());
+      res.push(self.get_fallback(ColorFallbackKind::P3));
     }
 
     if fallbacks.contains(ColorFallbackKind::LAB) {
-      *self = self.to_lab();
+      *self = self.get_fallback(ColorFallbackKind::LAB);
     }
 
     res
@@ -514,6 +645,27 @@ impl ToCss for CssColor {
         let srgb = SRGB::from(**float);
         CssColor::from(srgb).to_css(dest)
       }
+      CssColor::LightDark(light, dark) => {
+        if dest.light_dark_vars {
+          // Lower to a pair of custom properties, where exactly one is set to `initial`
+          // (its fallback is used) and the other is empty, based on the color-scheme.
+          dest.write_str("var(--lightningcss-light")?;
+          dest.delim(',', false)?;
+          light.to_css(dest)?;
+          dest.write_char(')')?;
+          dest.whitespace()?;
+          dest.write_str("var(--lightningcss-dark")?;
+          dest.delim(',', false)?;
+          dark.to_css(dest)?;
+          return dest.write_char(')');
+        }
+
+        dest.write_str("light-dark(")?;
+        light.to_css(dest)?;
+        dest.delim(',', false)?;
+        dark.to_css(dest)?;
+        dest.write_char(')')
+      }
     }
   }
 }
@@ -742,7 +894,13 @@ impl ComponentParser {
     input: &mut Parser<'i, 't>,
   ) -> Result<(), ParseError<'i, ParserError<'i>>> {
     if input.try_parse(|input| input.expect_ident_matching("from")).is_ok() {
-      let from = T::from(CssColor::parse(input)?).resolve();
+      let location = input.current_source_location();
+      let from = CssColor::parse(input)?;
+      if matches!(from, CssColor::CurrentColor | CssColor::LightDark(..)) {
+        // These depend on computed values, so the origin color cannot be resolved at parse time.
+        return Err(location.new_custom_error(ParserError::InvalidValue));
+      }
+      let from = T::from(from).resolve();
       self.from = Some(RelativeComponentParser::new(&from));
     }
 
@@ -880,6 +1038,14 @@ fn parse_color_function<'i, 't>(input: &mut Parser<'i, 't>) -> Result<CssColor,
     "color-mix" => {
       input.parse_nested_block(parse_color_mix)
     },
+    "light-dark" => {
+      input.parse_nested_block(|input| {
+        let light = CssColor::parse(input)?;
+        input.expect_comma()?;
+        let dark = CssColor::parse(input)?;
+        Ok(CssColor::LightDark(Box::new(light), Box::new(dark)))
+      })
+    },
     _ => Err(location.new_unexpected_token_error(
       cssparser::Token::Ident(function.clone())
     ))
@@ -2574,7 +2740,7 @@ macro_rules! color_space {
           CssColor::LAB(lab) => (**lab).into(),
           CssColor::Predefined(predefined) => (**predefined).into(),
           CssColor::Float(float) => (**float).into(),
-          CssColor::CurrentColor => unreachable!(),
+          CssColor::CurrentColor | CssColor::LightDark(..) => unreachable!(),
         }
       }
     }
@@ -2586,7 +2752,7 @@ macro_rules! color_space {
           CssColor::LAB(lab) => (*lab).into(),
           CssColor::Predefined(predefined) => (*predefined).into(),
           CssColor::Float(float) => (*float).into(),
-          CssColor::CurrentColor => unreachable!(),
+          CssColor::CurrentColor | CssColor::LightDark(..) => unreachable!(),
         }
       }
     }
@@ -2826,23 +2992,7 @@ where
 }
 
 fn parse_color_mix<'i, 't>(input: &mut Parser<'i, 't>) -> Result<CssColor, ParseError<'i, ParserError<'i>>> {
-  input.expect_ident_matching("in")?;
-  let method = ColorSpaceName::parse(input)?;
-
-  let hue_method = if matches!(
-    method,
-    ColorSpaceName::Hsl | ColorSpaceName::Hwb | ColorSpaceName::LCH | ColorSpaceName::OKLCH
-  ) {
-    let hue_method = input.try_parse(HueInterpolationMethod::parse);
-    if hue_method.is_ok() {
-      input.expect_ident_matching("hue")?;
-    }
-    hue_method
-  } else {
-    Ok(HueInterpolationMethod::Shorter)
-  };
-
-  let hue_method = hue_method.unwrap_or(HueInterpolationMethod::Shorter);
+  let method = ColorInterpolationMethod::parse(input)?;
   input.expect_comma()?;
 
   let first_percent = input.try_parse(|input| input.expect_percentage());
@@ -2871,23 +3021,52 @@ fn parse_color_mix<'i, 't>(input: &mut Parser<'i, 't>) -> Result<CssColor, Parse
     return Err(input.new_custom_error(ParserError::InvalidValue));
   }
 
-  Ok(match method {
-    ColorSpaceName::SRGB => first_color.interpolate::<SRGB>(p1, &second_color, p2, hue_method),
-    ColorSpaceName::SRGBLinear => first_color.interpolate::<SRGBLinear>(p1, &second_color, p2, hue_method),
-    ColorSpaceName::Hsl => first_color.interpolate::<HSL>(p1, &second_color, p2, hue_method),
-    ColorSpaceName::Hwb => first_color.interpolate::<HWB>(p1, &second_color, p2, hue_method),
-    ColorSpaceName::LAB => first_color.interpolate::<LAB>(p1, &second_color, p2, hue_method),
-    ColorSpaceName::LCH => first_color.interpolate::<LCH>(p1, &second_color, p2, hue_method),
-    ColorSpaceName::OKLAB => first_color.interpolate::<OKLAB>(p1, &second_color, p2, hue_method),
-    ColorSpaceName::OKLCH => first_color.interpolate::<OKLCH>(p1, &second_color, p2, hue_method),
+  Ok(method.mix(&first_color, p1, &second_color, p2))
+}
+
+fn mix_colors(
+  method: ColorSpaceName,
+  first_color: &CssColor,
+  p1: f32,
+  second_color: &CssColor,
+  p2: f32,
+  hue_method: HueInterpolationMethod,
+) -> CssColor {
+  // Mixing with light-dark() produces a light-dark() of the mixed light and dark colors.
+  if matches!(first_color, CssColor::LightDark(..)) || matches!(second_color, CssColor::LightDark(..)) {
+    let (first_light, first_dark) = first_color.light_dark();
+    let (second_light, second_dark) = second_color.light_dark();
+    return CssColor::LightDark(
+      Box::new(mix_colors(method, first_light, p1, second_light, p2, hue_method)),
+      Box::new(mix_colors(method, first_dark, p1, second_dark, p2, hue_method)),
+    );
+  }
+
+  match method {
+    ColorSpaceName::SRGB => first_color.interpolate::<SRGB>(p1, second_color, p2, hue_method),
+    ColorSpaceName::SRGBLinear => first_color.interpolate::<SRGBLinear>(p1, second_color, p2, hue_method),
+    ColorSpaceName::Hsl => first_color.interpolate::<HSL>(p1, second_color, p2, hue_method),
+    ColorSpaceName::Hwb => first_color.interpolate::<HWB>(p1, second_color, p2, hue_method),
+    ColorSpaceName::LAB => first_color.interpolate::<LAB>(p1, second_color, p2, hue_method),
+    ColorSpaceName::LCH => first_color.interpolate::<LCH>(p1, second_color, p2, hue_method),
+    ColorSpaceName::OKLAB => first_color.interpolate::<OKLAB>(p1, second_color, p2, hue_method),
+    ColorSpaceName::OKLCH => first_color.interpolate::<OKLCH>(p1, second_color, p2, hue_method),
     ColorSpaceName::XYZ | ColorSpaceName::XYZd65 => {
-      first_color.interpolate::<XYZd65>(p1, &second_color, p2, hue_method)
+      first_color.interpolate::<XYZd65>(p1, second_color, p2, hue_method)
     }
-    ColorSpaceName::XYZd50 => first_color.interpolate::<XYZd50>(p1, &second_color, p2, hue_method),
-  })
+    ColorSpaceName::XYZd50 => first_color.interpolate::<XYZd50>(p1, second_color, p2, hue_method),
+  }
 }
 
 impl CssColor {
+  /// Returns the light and dark variants of the color. Colors other than light-dark() are the same in both.
+  fn light_dark(&self) -> (&CssColor, &CssColor) {
+    match self {
+      CssColor::LightDark(light, dark) => (light, dark),
+      _ => (self, self),
+    }
+  }
+
   fn get_type_id(&self) -> TypeId {
     match self {
       CssColor::RGBA(..) => TypeId::of::<SRGB>(),
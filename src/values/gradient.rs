@@ -1,7 +1,9 @@
 //! CSS gradient values.
 
 use super::angle::{Angle, AnglePercentage};
-use super::color::{ColorFallbackKind, CssColor};
+use super::color::{
+  ColorFallbackKind, ColorInterpolationMethod, ColorSpaceName, CssColor, HueInterpolationMethod,
+};
 use super::length::{Length, LengthPercentage};
 use super::number::CSSNumber;
 use super::percentage::{DimensionPercentage, NumberOrPercentage, Percentage};
@@ -18,6 +20,7 @@ use crate::vendor_prefix::VendorPrefix;
 #[cfg(feature = "visitor")]
 use crate::visitor::Visit;
 use cssparser::*;
+use std::borrow::Cow;
 
 #[cfg(feature = "serde")]
 use crate::serialization::ValueWrapper;
@@ -230,6 +233,8 @@ pub struct LinearGradient {
   pub vendor_prefix: VendorPrefix,
   /// The direction of the gradient.
   pub direction: LineDirection,
+  /// The color interpolation method, if specified.
+  pub interpolation: Option<ColorInterpolationMethod>,
   /// The color stops and transition hints for the gradient.
   pub items: Vec<GradientItem<LengthPercentage>>,
 }
@@ -239,17 +244,22 @@ impl LinearGradient {
     input: &mut Parser<'i, 't>,
     vendor_prefix: VendorPrefix,
   ) -> Result<LinearGradient, ParseError<'i, ParserError<'i>>> {
-    let direction = if let Ok(direction) =
-      input.try_parse(|input| LineDirection::parse(input, vendor_prefix != VendorPrefix::None))
-    {
+    let mut interpolation = parse_interpolation(input, vendor_prefix);
+    let direction = input
+      .try_parse(|input| LineDirection::parse(input, vendor_prefix != VendorPrefix::None))
+      .ok();
+    if interpolation.is_none() && direction.is_some() {
+      interpolation = parse_interpolation(input, vendor_prefix);
+    }
+
+    if direction.is_some() || interpolation.is_some() {
       input.expect_comma()?;
-      direction
-    } else {
-      LineDirection::Vertical(VerticalPositionKeyword::Bottom)
-    };
+    }
+
     let items = parse_items(input)?;
     Ok(LinearGradient {
-      direction,
+      direction: direction.unwrap_or(LineDirection::Vertical(VerticalPositionKeyword::Bottom)),
+      interpolation,
       items,
       vendor_prefix,
     })
@@ -259,6 +269,7 @@ impl LinearGradient {
   where
     W: std::fmt::Write,
   {
+    let (interpolation, items) = resolve_interpolation(&self.interpolation, &self.items, dest);
     let angle = match &self.direction {
       LineDirection::Vertical(VerticalPositionKeyword::Bottom) => 180.0,
       LineDirection::Vertical(VerticalPositionKeyword::Top) => 0.0,
@@ -268,13 +279,25 @@ impl LinearGradient {
 
     // We can omit `to bottom` or `180deg` because it is the default.
     if angle == 180.0 {
-      serialize_items(&self.items, dest)
+      if let Some(interpolation) = interpolation {
+        interpolation.to_css(dest)?;
+        dest.delim(',', false)?;
+      }
+      serialize_items(&items, dest)
 
     // If we have `to top` or `0deg`, and all of the positions and hints are percentages,
-    // we can flip the gradient the other direction and omit the direction.
+    // we can flip the gradient the other direction and omit the direction. Increasing and
+    // decreasing hue interpolation depends on the direction, so those cannot be flipped.
     } else if angle == 0.0
       && dest.minify
-      && self.items.iter().all(|item| {
+      && !matches!(
+        interpolation,
+        Some(ColorInterpolationMethod {
+          hue: HueInterpolationMethod::Increasing | HueInterpolationMethod::Decreasing,
+          ..
+        })
+      )
+      && items.iter().all(|item| {
         matches!(
           item,
           GradientItem::Hint(LengthPercentage::Percentage(_))
@@ -285,8 +308,7 @@ impl LinearGradient {
         )
       })
     {
-      let items: Vec<GradientItem<LengthPercentage>> = self
-        .items
+      let items: Vec<GradientItem<LengthPercentage>> = items
         .iter()
         .rev()
         .map(|item| {
@@ -306,22 +328,26 @@ impl LinearGradient {
           }
         })
         .collect();
+      if let Some(interpolation) = interpolation {
+        interpolation.to_css(dest)?;
+        dest.delim(',', false)?;
+      }
       serialize_items(&items, dest)
     } else {
-      if self.direction != LineDirection::Vertical(VerticalPositionKeyword::Bottom)
-        && self.direction != LineDirection::Angle(Angle::Deg(180.0))
-      {
-        self.direction.to_css(dest, is_prefixed)?;
-        dest.delim(',', false)?;
+      self.direction.to_css(dest, is_prefixed)?;
+      if let Some(interpolation) = interpolation {
+        dest.write_char(' ')?;
+        interpolation.to_css(dest)?;
       }
-
-      serialize_items(&self.items, dest)
+      dest.delim(',', false)?;
+      serialize_items(&items, dest)
     }
   }
 
   fn get_fallback(&self, kind: ColorFallbackKind) -> LinearGradient {
     LinearGradient {
       direction: self.direction.clone(),
+      interpolation: self.interpolation,
       items: self.items.iter().map(|item| item.get_fallback(kind)).collect(),
       vendor_prefix: self.vendor_prefix,
     }
@@ -344,6 +370,8 @@ pub struct RadialGradient {
   pub shape: EndingShape,
   /// The position of the gradient.
   pub position: Position,
+  /// The color interpolation method, if specified.
+  pub interpolation: Option<ColorInterpolationMethod>,
   /// The color stops and transition hints for the gradient.
   pub items: Vec<GradientItem<LengthPercentage>>,
 }
@@ -353,6 +381,7 @@ impl<'i> RadialGradient {
     input: &mut Parser<'i, 't>,
     vendor_prefix: VendorPrefix,
   ) -> Result<RadialGradient, ParseError<'i, ParserError<'i>>> {
+    let mut interpolation = parse_interpolation(input, vendor_prefix);
     let shape = input.try_parse(EndingShape::parse).ok();
     let position = input
       .try_parse(|input| {
@@ -360,8 +389,11 @@ impl<'i> RadialGradient {
         Position::parse(input)
       })
       .ok();
+    if interpolation.is_none() && (shape.is_some() || position.is_some()) {
+      interpolation = parse_interpolation(input, vendor_prefix);
+    }
 
-    if shape.is_some() || position.is_some() {
+    if shape.is_some() || position.is_some() || interpolation.is_some() {
       input.expect_comma()?;
     }
 
@@ -369,6 +401,7 @@ impl<'i> RadialGradient {
     Ok(RadialGradient {
       shape: shape.unwrap_or_default(),
       position: position.unwrap_or(Position::center()),
+      interpolation,
       items,
       vendor_prefix,
     })
@@ -380,22 +413,35 @@ impl ToCss for RadialGradient {
   where
     W: std::fmt::Write,
   {
+    let (interpolation, items) = resolve_interpolation(&self.interpolation, &self.items, dest);
+    let mut has_prelude = false;
     if self.shape != EndingShape::default() {
       self.shape.to_css(dest)?;
-      if self.position.is_center() {
-        dest.delim(',', false)?;
-      } else {
-        dest.write_char(' ')?;
-      }
+      has_prelude = true;
     }
 
     if !self.position.is_center() {
+      if has_prelude {
+        dest.write_char(' ')?;
+      }
       dest.write_str("at ")?;
       self.position.to_css(dest)?;
+      has_prelude = true;
+    }
+
+    if let Some(interpolation) = interpolation {
+      if has_prelude {
+        dest.write_char(' ')?;
+      }
+      interpolation.to_css(dest)?;
+      has_prelude = true;
+    }
+
+    if has_prelude {
       dest.delim(',', false)?;
     }
 
-    serialize_items(&self.items, dest)
+    serialize_items(&items, dest)
   }
 }
 
@@ -404,6 +450,7 @@ impl RadialGradient {
     RadialGradient {
       shape: self.shape.clone(),
       position: self.position.clone(),
+      interpolation: self.interpolation,
       items: self.items.iter().map(|item| item.get_fallback(kind)).collect(),
       vendor_prefix: self.vendor_prefix,
     }
@@ -743,12 +790,15 @@ pub struct ConicGradient {
   pub angle: Angle,
   /// The position of the gradient.
   pub position: Position,
+  /// The color interpolation method, if specified.
+  pub interpolation: Option<ColorInterpolationMethod>,
   /// The color stops and transition hints for the gradient.
   pub items: Vec<GradientItem<AnglePercentage>>,
 }
 
 impl ConicGradient {
   fn parse<'i, 't>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut interpolation = parse_interpolation(input, VendorPrefix::None);
     let angle = input.try_parse(|input| {
       input.expect_ident_matching("from")?;
       // Spec allows unitless zero angles for gradients.
@@ -761,7 +811,11 @@ impl ConicGradient {
       Position::parse(input)
     });
 
-    if angle.is_ok() || position.is_ok() {
+    if interpolation.is_none() && (angle.is_ok() || position.is_ok()) {
+      interpolation = parse_interpolation(input, VendorPrefix::None);
+    }
+
+    if angle.is_ok() || position.is_ok() || interpolation.is_some() {
       input.expect_comma()?;
     }
 
@@ -769,6 +823,7 @@ impl ConicGradient {
     Ok(ConicGradient {
       angle: angle.unwrap_or(Angle::Deg(0.0)),
       position: position.unwrap_or(Position::center()),
+      interpolation,
       items,
     })
   }
@@ -779,24 +834,36 @@ impl ToCss for ConicGradient {
   where
     W: std::fmt::Write,
   {
+    let (interpolation, items) = resolve_interpolation(&self.interpolation, &self.items, dest);
+    let mut has_prelude = false;
     if !self.angle.is_zero() {
       dest.write_str("from ")?;
       self.angle.to_css(dest)?;
-
-      if self.position.is_center() {
-        dest.delim(',', false)?;
-      } else {
-        dest.write_char(' ')?;
-      }
+      has_prelude = true;
     }
 
     if !self.position.is_center() {
+      if has_prelude {
+        dest.write_char(' ')?;
+      }
       dest.write_str("at ")?;
       self.position.to_css(dest)?;
+      has_prelude = true;
+    }
+
+    if let Some(interpolation) = interpolation {
+      if has_prelude {
+        dest.write_char(' ')?;
+      }
+      interpolation.to_css(dest)?;
+      has_prelude = true;
+    }
+
+    if has_prelude {
       dest.delim(',', false)?;
     }
 
-    serialize_items(&self.items, dest)
+    serialize_items(&items, dest)
   }
 }
 
@@ -805,6 +872,7 @@ impl ConicGradient {
     ConicGradient {
       angle: self.angle.clone(),
       position: self.position.clone(),
+      interpolation: self.interpolation,
       items: self.items.iter().map(|item| item.get_fallback(kind)).collect(),
     }
   }
@@ -950,6 +1018,149 @@ fn parse_items<'i, 't, D: Parse<'i>>(
   Ok(items)
 }
 
+fn parse_interpolation<'i, 't>(
+  input: &mut Parser<'i, 't>,
+  vendor_prefix: VendorPrefix,
+) -> Option<ColorInterpolationMethod> {
+  // Color interpolation methods are not supported in the legacy prefixed syntax.
+  if vendor_prefix != VendorPrefix::None {
+    return None;
+  }
+
+  input.try_parse(ColorInterpolationMethod::parse).ok()
+}
+
+/// The number of segments that each pair of adjacent color stops is divided into
+/// when lowering a color interpolation method.
+const INTERPOLATION_STEPS: u8 = 8;
+
+type ColorStopList<D> = Vec<GradientItem<DimensionPercentage<D>>>;
+
+/// Returns the interpolation method and color stops to serialize for the printer's targets.
+/// If the targets do not support color interpolation methods, the stops are sampled in the
+/// interpolation color space and emitted as sRGB stops so that the rendered gradient matches.
+/// When the stops cannot be sampled, the method is omitted and the gradient is interpolated in sRGB.
+fn resolve_interpolation<'a, D: Clone, W>(
+  interpolation: &'a Option<ColorInterpolationMethod>,
+  items: &'a Vec<GradientItem<DimensionPercentage<D>>>,
+  dest: &Printer<W>,
+) -> (Option<&'a ColorInterpolationMethod>, Cow<'a, ColorStopList<D>>)
+where
+  W: std::fmt::Write,
+{
+  if let (Some(method), Some(targets)) = (interpolation, dest.targets) {
+    if !compat::Feature::GradientInterpolation.is_compatible(targets) {
+      // Browsers without support always interpolate in sRGB, so the method can be omitted.
+      if method.space == ColorSpaceName::SRGB {
+        return (None, Cow::Borrowed(items));
+      }
+
+      if let Some(items) = sample_color_stops(method, items) {
+        return (None, Cow::Owned(items));
+      }
+
+      // Otherwise, the declaration would be dropped entirely.
+      return (None, Cow::Borrowed(items));
+    }
+  }
+
+  (interpolation.as_ref(), Cow::Borrowed(items))
+}
+
+fn sample_color_stops<D: Clone>(
+  method: &ColorInterpolationMethod,
+  items: &Vec<GradientItem<DimensionPercentage<D>>>,
+) -> Option<Vec<GradientItem<DimensionPercentage<D>>>> {
+  // Stops can only be sampled when their positions are known without layout information.
+  // Colors that depend on the computed value cannot be interpolated ahead of time either.
+  let mut colors = Vec::with_capacity(items.len());
+  let mut positions = Vec::with_capacity(items.len());
+  for item in items {
+    match item {
+      GradientItem::ColorStop(ColorStop { color, position }) => {
+        if matches!(color, CssColor::CurrentColor | CssColor::LightDark(..)) {
+          return None;
+        }
+
+        colors.push(color);
+        positions.push(match position {
+          None => None,
+          Some(DimensionPercentage::Percentage(p)) => Some(p.0),
+          Some(_) => return None,
+        });
+      }
+      GradientItem::Hint(..) => return None,
+    }
+  }
+
+  if colors.len() < 2 {
+    return None;
+  }
+
+  // Resolve the missing and out of order positions.
+  // https://drafts.csswg.org/css-images-4/#color-stop-fixup
+  let len = positions.len();
+  positions[0].get_or_insert(0.0);
+  positions[len - 1].get_or_insert(1.0);
+
+  let mut max = f32::MIN;
+  for position in positions.iter_mut().flatten() {
+    if *position < max {
+      *position = max;
+    } else {
+      max = *position;
+    }
+  }
+
+  let mut i = 1;
+  while i < len {
+    if positions[i].is_none() {
+      let start = i - 1;
+      let mut end = i;
+      while positions[end].is_none() {
+        end += 1;
+      }
+
+      let from = positions[start].unwrap();
+      let to = positions[end].unwrap();
+      for (j, position) in positions[i..end].iter_mut().enumerate() {
+        *position = Some(from + (to - from) * (i + j - start) as f32 / (end - start) as f32);
+      }
+      i = end;
+    }
+    i += 1;
+  }
+
+  let stop = |color: CssColor, position: f32| {
+    GradientItem::ColorStop(ColorStop {
+      color,
+      position: Some(DimensionPercentage::Percentage(Percentage(position))),
+    })
+  };
+
+  let mut res = Vec::with_capacity(len * INTERPOLATION_STEPS as usize);
+  res.push(stop(colors[0].clone(), positions[0].unwrap()));
+  for i in 1..len {
+    let (from, to) = (colors[i - 1], colors[i]);
+    let (start, end) = (positions[i - 1].unwrap(), positions[i].unwrap());
+
+    // Stops at the same position form a hard transition, so there is nothing to sample.
+    if end > start {
+      for step in 1..INTERPOLATION_STEPS {
+        let t = step as f32 / INTERPOLATION_STEPS as f32;
+        res.push(stop(
+          method.mix(from, 1.0 - t, to, t).to_rgb(),
+          start + (end - start) * t,
+        ));
+      }
+    }
+
+    res.push(stop(to.clone(), end));
+  }
+
+  Some(res)
+}
+
 fn serialize_items<
   D: ToCss + std::cmp::PartialEq<D> + std::ops::Mul<f32, Output = D> + TrySign + Clone + std::fmt::Debug,
   W,
@@ -1344,6 +1555,10 @@ impl WebKitGradient {
   pub fn from_standard(gradient: &Gradient) -> Result<WebKitGradient, ()> {
     match gradient {
       Gradient::Linear(linear) => {
+        if linear.interpolation.is_some() {
+          return Err(());
+        }
+
         // Convert from line direction to a from and to point, if possible.
         let (from, to) = match &linear.direction {
           LineDirection::Horizontal(horizontal) => match horizontal {
@@ -1389,6 +1604,10 @@ impl WebKitGradient {
         })
       }
       Gradient::Radial(radial) => {
+        if radial.interpolation.is_some() {
+          return Err(());
+        }
+
         // Webkit radial gradients are always circles, not ellipses, and must be specified in pixels.
         let radius = match &radial.shape {
           EndingShape::Circle(Circle::Radius(radius)) => {
@@ -4,7 +4,7 @@ use super::color::ColorFallbackKind;
 use super::gradient::*;
 use super::resolution::Resolution;
 use crate::compat;
-use crate::dependencies::{Dependency, UrlDependency};
+use crate::dependencies::Dependency;
 use crate::error::{ParserError, PrinterError};
 use crate::prefixes::{is_webkit_gradient, Feature};
 use crate::printer::Printer;
@@ -426,6 +426,11 @@ impl<'i> ToCss for ImageSet<'i> {
 /// An image option within the `image-set()` function. See [ImageSet](ImageSet).
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "visitor", derive(Visit))]
+// Images are recursive, so the child types of the image are listed explicitly.
+#[cfg_attr(
+  feature = "visitor",
+  visit_types(IMAGES | URLS | COLORS | LENGTHS | ANGLES | RESOLUTIONS)
+)]
 #[cfg_attr(feature = "into_owned", derive(lightningcss_derive::IntoOwned))]
 #[cfg_attr(
   feature = "serde",
@@ -435,7 +440,6 @@ impl<'i> ToCss for ImageSet<'i> {
 #[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
 pub struct ImageSetOption<'i> {
   /// The image for this option.
-  #[cfg_attr(feature = "visitor", skip_type)]
   pub image: Image<'i>,
   /// The resolution of the image.
   pub resolution: Resolution,
@@ -482,8 +486,8 @@ impl<'i> ImageSetOption<'i> {
       // Prefixed syntax didn't allow strings, only url()
       Image::Url(url) if !is_prefixed => {
         // Add dependency if needed. Normally this is handled by the Url type.
-        let dep = if dest.dependencies.is_some() {
-          Some(UrlDependency::new(url, dest.filename()))
+        let dep = if dest.dependencies.is_some() && !dest.record_inlined_asset(url) {
+          Some(dest.url_dependency(url))
         } else {
           None
         };
@@ -2,10 +2,13 @@
 
 use super::angle::impl_try_from_angle;
 use super::calc::{Calc, MathFunction};
+use super::ident::{DashedIdent, DashedIdentReference};
 use super::number::CSSNumber;
-use super::percentage::DimensionPercentage;
+use super::percentage::{DimensionPercentage, Percentage};
 use crate::error::{ParserError, PrinterError};
+use crate::macros::enum_property;
 use crate::printer::Printer;
+use crate::properties::css_modules::Specifier;
 use crate::targets::Browsers;
 use crate::traits::{
   private::{AddInternal, TryAdd},
@@ -13,7 +16,7 @@ use crate::traits::{
 };
 use crate::traits::{IsCompatible, TrySign};
 #[cfg(feature = "visitor")]
-use crate::visitor::Visit;
+use crate::visitor::{Visit, VisitTypes, Visitor};
 use const_str;
 use cssparser::*;
 
@@ -887,3 +890,322 @@ impl ToCss for LengthOrNumber {
     }
   }
 }
+
+impl TryFrom<LengthValue> for Length {
+  type Error = ();
+  fn try_from(value: LengthValue) -> Result<Self, Self::Error> {
+    Ok(Length::Value(value))
+  }
+}
+
+macro_rules! impl_try_from_length {
+  ($t: ty) => {
+    impl TryFrom<crate::values::length::LengthValue> for $t {
+      type Error = ();
+      fn try_from(_: crate::values::length::LengthValue) -> Result<Self, Self::Error> {
+        Err(())
+      }
+    }
+  };
+}
+
+pub(crate) use impl_try_from_length;
+
+enum_property! {
+  /// A side keyword for the [anchor()](https://drafts.csswg.org/css-anchor-position-1/#anchor-pos) function.
+  pub enum AnchorSideKeyword {
+    /// The inside side of the anchor element, relative to the positioned element's inset property.
+    "inside": Inside,
+    /// The outside side of the anchor element, relative to the positioned element's inset property.
+    "outside": Outside,
+    /// The top side of the anchor element.
+    "top": Top,
+    /// The left side of the anchor element.
+    "left": Left,
+    /// The right side of the anchor element.
+    "right": Right,
+    /// The bottom side of the anchor element.
+    "bottom": Bottom,
+    /// The start side of the anchor element in the containing block's writing mode.
+    "start": Start,
+    /// The end side of the anchor element in the containing block's writing mode.
+    "end": End,
+    /// The start side of the anchor element in the positioned element's writing mode.
+    "self-start": SelfStart,
+    /// The end side of the anchor element in the positioned element's writing mode.
+    "self-end": SelfEnd,
+    /// The center of the anchor element in the inset property's axis.
+    "center": Center,
+  }
+}
+
+/// An [anchor side](https://drafts.csswg.org/css-anchor-position-1/#typedef-anchor-side),
+/// as used in the `anchor()` function.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "visitor", derive(Visit))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum AnchorSide {
+  /// A side keyword.
+  Keyword(AnchorSideKeyword),
+  /// A position between the start (0%) and end (100%) sides of the anchor element.
+  Percentage(Percentage),
+}
+
+impl<'i> Parse<'i> for AnchorSide {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if let Ok(keyword) = input.try_parse(AnchorSideKeyword::parse) {
+      return Ok(AnchorSide::Keyword(keyword));
+    }
+
+    let percentage = Percentage::parse(input)?;
+    Ok(AnchorSide::Percentage(percentage))
+  }
+}
+
+impl ToCss for AnchorSide {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      AnchorSide::Keyword(keyword) => keyword.to_css(dest),
+      AnchorSide::Percentage(percentage) => percentage.to_css(dest),
+    }
+  }
+}
+
+/// Parses an optional anchor name, as used in the `anchor()` and `anchor-size()` functions.
+///
+/// Lengths do not borrow from the source, so the name is converted to an owned reference.
+/// Lengths are also parsed without parser options, so the CSS modules `from` syntax is
+/// always accepted. It is only used when printing with the `dashed_idents` option enabled.
+fn parse_anchor_name<'i, 't>(input: &mut Parser<'i, 't>) -> Option<DashedIdentReference<'static>> {
+  input
+    .try_parse(|input| {
+      let ident = DashedIdent::parse(input)?;
+      let from = if input.try_parse(|input| input.expect_ident_matching("from")).is_ok() {
+        Some(Specifier::parse(input)?)
+      } else {
+        None
+      };
+      Ok::<_, ParseError<'i, ParserError<'i>>>(DashedIdentReference { ident, from })
+    })
+    .ok()
+    .map(owned_anchor_name)
+}
+
+fn owned_anchor_name(name: DashedIdentReference) -> DashedIdentReference<'static> {
+  DashedIdentReference {
+    ident: DashedIdent(name.ident.0.into_owned()),
+    from: name.from.map(|from| match from {
+      Specifier::Global => Specifier::Global,
+      Specifier::File(file) => Specifier::File(file.into_owned()),
+      Specifier::SourceIndex(index) => Specifier::SourceIndex(index),
+    }),
+  }
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_anchor_name<'de, D>(deserializer: D) -> Result<Option<DashedIdentReference<'static>>, D::Error>
+where
+  D: serde::Deserializer<'de>,
+{
+  use serde::Deserialize;
+  let name = Option::<DashedIdentReference>::deserialize(deserializer)?;
+  Ok(name.map(owned_anchor_name))
+}
+
+/// Visits an anchor name. The name is owned, so the derived implementation for
+/// borrowed references cannot be used.
+#[cfg(feature = "visitor")]
+fn visit_anchor_name<'i, T: Visit<'i, T, V>, V: Visitor<'i, T>>(
+  name: &mut Option<DashedIdentReference<'static>>,
+  visitor: &mut V,
+) -> Result<(), V::Error> {
+  match name {
+    Some(name) if visitor.visit_types().contains(VisitTypes::DASHED_IDENTS) => {
+      visitor.visit_dashed_ident(&mut name.ident)
+    }
+    _ => Ok(()),
+  }
+}
+
+/// Parses an optional fallback value following a comma.
+fn parse_anchor_fallback<'i, 't>(
+  input: &mut Parser<'i, 't>,
+) -> Result<Option<LengthPercentage>, ParseError<'i, ParserError<'i>>> {
+  if input.try_parse(|input| input.expect_comma()).is_ok() {
+    Ok(Some(LengthPercentage::parse(input)?))
+  } else {
+    Ok(None)
+  }
+}
+
+fn write_anchor_fallback<W>(fallback: &Option<LengthPercentage>, dest: &mut Printer<W>) -> Result<(), PrinterError>
+where
+  W: std::fmt::Write,
+{
+  if let Some(fallback) = fallback {
+    dest.delim(',', false)?;
+    fallback.to_css(dest)?;
+  }
+  Ok(())
+}
+
+/// The [anchor()](https://drafts.csswg.org/css-anchor-position-1/#anchor-pos) function.
+///
+/// Resolves to the position of a side of an anchor element.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct AnchorFunction {
+  /// The name of the anchor element. If omitted, the positioned element's default anchor is used.
+  #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_anchor_name"))]
+  pub name: Option<DashedIdentReference<'static>>,
+  /// The side of the anchor element to resolve.
+  pub side: AnchorSide,
+  /// A fallback value used when the anchor reference is invalid.
+  pub fallback: Option<LengthPercentage>,
+}
+
+#[cfg(feature = "visitor")]
+impl<'i, T: Visit<'i, T, V>, V: Visitor<'i, T>> Visit<'i, T, V> for AnchorFunction {
+  const CHILD_TYPES: VisitTypes = VisitTypes::from_bits_truncate(
+    VisitTypes::DASHED_IDENTS.bits() | <Option<LengthPercentage> as Visit<'i, T, V>>::CHILD_TYPES.bits(),
+  );
+
+  fn visit_children(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+    visit_anchor_name(&mut self.name, visitor)?;
+    self.fallback.visit(visitor)
+  }
+}
+
+impl AnchorFunction {
+  /// Parses the arguments of an `anchor()` function.
+  pub(crate) fn parse_arguments<'i, 't>(
+    input: &mut Parser<'i, 't>,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut name = parse_anchor_name(input);
+    let side = AnchorSide::parse(input)?;
+    if name.is_none() {
+      name = parse_anchor_name(input);
+    }
+    let fallback = parse_anchor_fallback(input)?;
+    Ok(AnchorFunction { name, side, fallback })
+  }
+}
+
+impl ToCss for AnchorFunction {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    dest.write_str("anchor(")?;
+    if let Some(name) = &self.name {
+      name.to_css(dest)?;
+      dest.write_char(' ')?;
+    }
+    self.side.to_css(dest)?;
+    write_anchor_fallback(&self.fallback, dest)?;
+    dest.write_char(')')
+  }
+}
+
+enum_property! {
+  /// A size keyword for the [anchor-size()](https://drafts.csswg.org/css-anchor-position-1/#anchor-size-fn) function.
+  pub enum AnchorSizeKeyword {
+    /// The width of the anchor element.
+    "width": Width,
+    /// The height of the anchor element.
+    "height": Height,
+    /// The size of the anchor element in the containing block's block axis.
+    "block": Block,
+    /// The size of the anchor element in the containing block's inline axis.
+    "inline": Inline,
+    /// The size of the anchor element in the positioned element's block axis.
+    "self-block": SelfBlock,
+    /// The size of the anchor element in the positioned element's inline axis.
+    "self-inline": SelfInline,
+  }
+}
+
+/// The [anchor-size()](https://drafts.csswg.org/css-anchor-position-1/#anchor-size-fn) function.
+///
+/// Resolves to the size of an anchor element.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub struct AnchorSizeFunction {
+  /// The name of the anchor element. If omitted, the positioned element's default anchor is used.
+  #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_anchor_name"))]
+  pub name: Option<DashedIdentReference<'static>>,
+  /// The dimension of the anchor element to resolve.
+  /// If omitted, the axis of the property the function is used in is used.
+  pub size: Option<AnchorSizeKeyword>,
+  /// A fallback value used when the anchor reference is invalid.
+  pub fallback: Option<LengthPercentage>,
+}
+
+#[cfg(feature = "visitor")]
+impl<'i, T: Visit<'i, T, V>, V: Visitor<'i, T>> Visit<'i, T, V> for AnchorSizeFunction {
+  const CHILD_TYPES: VisitTypes = VisitTypes::from_bits_truncate(
+    VisitTypes::DASHED_IDENTS.bits() | <Option<LengthPercentage> as Visit<'i, T, V>>::CHILD_TYPES.bits(),
+  );
+
+  fn visit_children(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+    visit_anchor_name(&mut self.name, visitor)?;
+    self.fallback.visit(visitor)
+  }
+}
+
+impl AnchorSizeFunction {
+  /// Parses the arguments of an `anchor-size()` function.
+  pub(crate) fn parse_arguments<'i, 't>(
+    input: &mut Parser<'i, 't>,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut name = parse_anchor_name(input);
+    let size = input.try_parse(AnchorSizeKeyword::parse).ok();
+    if name.is_none() {
+      name = parse_anchor_name(input);
+    }
+
+    // A fallback without any preceding arguments is not preceded by a comma.
+    let fallback = if name.is_none() && size.is_none() {
+      input.try_parse(LengthPercentage::parse).ok()
+    } else {
+      parse_anchor_fallback(input)?
+    };
+    Ok(AnchorSizeFunction { name, size, fallback })
+  }
+}
+
+impl ToCss for AnchorSizeFunction {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    dest.write_str("anchor-size(")?;
+    if let Some(name) = &self.name {
+      name.to_css(dest)?;
+    }
+    if let Some(size) = &self.size {
+      if self.name.is_some() {
+        dest.write_char(' ')?;
+      }
+      size.to_css(dest)?;
+    }
+    if self.name.is_none() && self.size.is_none() {
+      if let Some(fallback) = &self.fallback {
+        fallback.to_css(dest)?;
+      }
+    } else {
+      write_anchor_fallback(&self.fallback, dest)?;
+    }
+    dest.write_char(')')
+  }
+}
@@ -2,10 +2,12 @@
 
 use super::angle::impl_try_from_angle;
 use super::calc::Calc;
+use super::length::impl_try_from_length;
 use crate::error::{ParserError, PrinterError};
 use crate::printer::Printer;
+use crate::targets::Browsers;
 use crate::traits::private::AddInternal;
-use crate::traits::{Map, Op, Parse, Sign, ToCss, Zero};
+use crate::traits::{IsCompatible, Map, Op, Parse, Sign, ToCss, Zero};
 use cssparser::*;
 
 /// A CSS [`<number>`](https://www.w3.org/TR/css-values-4/#numbers) value.
@@ -98,6 +100,12 @@ impl Sign for CSSNumber {
   }
 }
 
+impl IsCompatible for CSSNumber {
+  fn is_compatible(&self, _browsers: Browsers) -> bool {
+    true
+  }
+}
+
 impl Zero for CSSNumber {
   fn zero() -> Self {
     0.0
@@ -109,6 +117,7 @@ impl Zero for CSSNumber {
 }
 
 impl_try_from_angle!(CSSNumber);
+impl_try_from_length!(CSSNumber);
 
 /// A CSS [`<integer>`](https://www.w3.org/TR/css-values-4/#integers) value.
 pub type CSSInteger = i32;
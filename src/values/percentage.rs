@@ -2,6 +2,7 @@
 
 use super::angle::{impl_try_from_angle, Angle};
 use super::calc::{Calc, MathFunction};
+use super::length::{impl_try_from_length, LengthValue};
 use super::number::CSSNumber;
 use crate::error::{ParserError, PrinterError};
 use crate::printer::Printer;
@@ -141,6 +142,7 @@ impl_op!(Percentage, std::ops::Rem, rem);
 impl_op!(Percentage, std::ops::Add, add);
 
 impl_try_from_angle!(Percentage);
+impl_try_from_length!(Percentage);
 
 /// Either a `<number>` or `<percentage>`.
 #[derive(Debug, Clone, PartialEq)]
@@ -226,6 +228,7 @@ impl<
       + Zero
       + TrySign
       + TryFrom<Angle>
+      + TryFrom<LengthValue>
       + PartialOrd<D>
       + std::fmt::Debug,
   > Parse<'i> for DimensionPercentage<D>
@@ -457,6 +460,14 @@ impl<E, D: TryFrom<Angle, Error = E>> TryFrom<Angle> for DimensionPercentage<D>
   }
 }
 
+impl<E, D: TryFrom<LengthValue, Error = E>> TryFrom<LengthValue> for DimensionPercentage<D> {
+  type Error = E;
+
+  fn try_from(value: LengthValue) -> Result<Self, Self::Error> {
+    Ok(DimensionPercentage::Dimension(D::try_from(value)?))
+  }
+}
+
 impl<D: Zero> Zero for DimensionPercentage<D> {
   fn zero() -> Self {
     DimensionPercentage::Dimension(D::zero())
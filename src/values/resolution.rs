@@ -88,6 +88,17 @@ impl ToCss for Resolution {
   }
 }
 
+impl Resolution {
+  /// Returns the resolution in dots per px.
+  pub fn to_dppx(&self) -> CSSNumber {
+    match self {
+      Resolution::Dpi(dpi) => dpi / 96.0,
+      Resolution::Dpcm(dpcm) => dpcm * 2.54 / 96.0,
+      Resolution::Dppx(dppx) => *dppx,
+    }
+  }
+}
+
 impl std::ops::Add<CSSNumber> for Resolution {
   type Output = Self;
 
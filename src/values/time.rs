@@ -2,11 +2,13 @@
 
 use super::angle::impl_try_from_angle;
 use super::calc::Calc;
+use super::length::impl_try_from_length;
 use super::number::CSSNumber;
 use crate::error::{ParserError, PrinterError};
 use crate::printer::Printer;
+use crate::targets::Browsers;
 use crate::traits::private::AddInternal;
-use crate::traits::{impl_op, Map, Op, Parse, Sign, ToCss, Zero};
+use crate::traits::{impl_op, IsCompatible, Map, Op, Parse, Sign, ToCss, Zero};
 #[cfg(feature = "visitor")]
 use crate::visitor::Visit;
 use cssparser::*;
@@ -42,6 +44,12 @@ impl Time {
   }
 }
 
+impl IsCompatible for Time {
+  fn is_compatible(&self, _browsers: Browsers) -> bool {
+    true
+  }
+}
+
 impl Zero for Time {
   fn zero() -> Self {
     Time::Milliseconds(0.0)
@@ -202,3 +210,4 @@ impl_op!(Time, std::ops::Rem, rem);
 impl_op!(Time, std::ops::Add, add);
 
 impl_try_from_angle!(Time);
+impl_try_from_length!(Time);
@@ -1,6 +1,6 @@
 //! CSS url() values.
 
-use crate::dependencies::{Dependency, Location, UrlDependency};
+use crate::dependencies::{Dependency, Location};
 use crate::error::{ParserError, PrinterError};
 use crate::printer::Printer;
 use crate::traits::{Parse, ToCss};
@@ -43,8 +43,8 @@ impl<'i> ToCss for Url<'i> {
   where
     W: std::fmt::Write,
   {
-    let dep = if dest.dependencies.is_some() {
-      Some(UrlDependency::new(self, dest.filename()))
+    let dep = if dest.dependencies.is_some() && !dest.record_inlined_asset(self) {
+      Some(dest.url_dependency(self))
     } else {
       None
     };